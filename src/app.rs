@@ -1,15 +1,40 @@
 //! the higher level application
+use std::{cell::RefCell, collections::HashSet};
+
 use nu_protocol::{
     ast::{CellPath, PathMember},
-    Span, Value,
+    Range, Record, Span, Type, Value,
+};
+
+use crate::{
+    command::parse_path_member,
+    config::Config,
+    edit::Editor,
+    navigation::Direction,
+    nu::{
+        diff::{diff_pair, diff_records},
+        value::{estimate_size, expand_range, is_table, matches_query, mutate_value_cell, Table},
+    },
+    prompt::Prompt,
+    ui::{
+        format_cell_path, repr_data, repr_table, root_summary, DataRowRepr, ReprOptions, TableRepr,
+    },
 };
 
-use crate::{config::Config, edit::Editor};
+/// the time after which the letters typed to jump to a row are forgotten, in milliseconds
+pub(crate) const JUMP_PREFIX_TIMEOUT_MS: u64 = 1000;
+
+/// the maximum number of items of a range that are explored, e.g. for unbounded ranges
+pub(crate) const RANGE_LIMIT: usize = 10_000;
 
 /// the mode in which the application is
 #[derive(Clone, Debug, PartialEq, Default)]
 pub enum Mode {
     /// the *navigation* mode, where the user can move around in the data
+    ///
+    /// typing letters that are not bound to any action jumps to the first row starting with them,
+    /// e.g. `b` then `e` jumps to the first key starting with `be`, whereas digits always start a
+    /// count, see [`Mode::Waiting`]
     #[default]
     Normal,
     /// lets the user edit cells of the structured data
@@ -22,6 +47,26 @@ pub enum Mode {
     /// waits for more keys to perform an action, e.g. jumping to a line or motion repetition that
     /// both require to enter a number before the actual action
     Waiting(usize),
+    /// lets the user type a query to search for in the current level of the data
+    Searching,
+    /// lets the user select a range of rows in the current level of the data, starting from the
+    /// anchor row
+    Visual(usize),
+    /// lets the user type a command, e.g. `:q`, to run when pressing `<enter>`
+    Command,
+    /// lets the user type the path of a file to write the value under the cursor to
+    Writing,
+    /// lets the user type a query to only show the items of the current list matching it
+    Filtering,
+    /// waits for a motion after the goto prefix, e.g. `gg`, remembering the anchor of VISUAL mode
+    /// to go back to, if the prefix has been typed there
+    Goto(Option<usize>),
+    /// waits for a command after the scroll prefix, e.g. `zz`, to move the view around the cursor
+    Scroll,
+    /// waits for a command after the yank prefix, e.g. `yy`, to give back a value and quit
+    Yank,
+    /// shows all the bindings on top of the data, until any key is pressed
+    Help,
 }
 
 impl std::fmt::Display for Mode {
@@ -32,11 +77,75 @@ impl std::fmt::Display for Mode {
             Self::Peeking => "PEEKING",
             Self::Bottom => "BOTTOM",
             Self::Waiting(_) => "WAITING",
+            Self::Searching => "SEARCHING",
+            Self::Visual(_) => "VISUAL",
+            Self::Command => "COMMAND",
+            Self::Writing => "WRITING",
+            Self::Filtering => "FILTERING",
+            Self::Goto(_) => "GOTO",
+            Self::Scroll => "SCROLL",
+            Self::Yank => "YANK",
+            Self::Help => "HELP",
         };
         write!(f, "{}", repr)
     }
 }
 
+/// a non-destructive filter on the items of a list, i.e. the data is not modified
+#[derive(Clone, Debug, PartialEq)]
+pub enum Filter {
+    /// only keep the items with the same shape, i.e. the same type without looking at the inner
+    /// types of lists and records
+    Shape(Type),
+    /// only keep the items containing the query, either in themselves or in any of their columns
+    Query(String),
+}
+
+impl Filter {
+    /// whether or not a value passes through the filter
+    pub(crate) fn matches(&self, value: &Value) -> bool {
+        match self {
+            Self::Shape(ty) => {
+                std::mem::discriminant(&value.get_type()) == std::mem::discriminant(ty)
+            }
+            Self::Query(query) => matches_query(value, query),
+        }
+    }
+}
+
+/// a path in the data, along with the filter at the time something has been cached there
+type CacheKey = (CellPath, Option<(CellPath, Filter)>);
+
+/// the representation of the rows of the level of the data being displayed
+///
+/// computing the representation of the rows is expensive for long or wide data, e.g. the 1M
+/// integers of `seq 1 1_000_000` took about 400ms in release mode on every redraw, i.e. at least
+/// every tick of 250ms. the rows are now only computed again when the level, the filter or the
+/// data changes and a redraw only borrows the ones in the frame, which takes about 160ms for the
+/// same data, most of it being spent cloning the level, and about 220ms for a table of 1M rows.
+#[derive(Clone, Default)]
+pub(crate) struct RowsCache {
+    /// the path to the level and the filter at the time the rows have been computed, if any
+    key: Option<CacheKey>,
+    /// the representation of the rows
+    pub(crate) rows: Vec<DataRowRepr>,
+    /// the representation of the columns, the shapes and the cells when the level is a table,
+    /// which is not shown row by row, see [`crate::ui::repr_table`]
+    pub(crate) table: Option<TableRepr>,
+    /// whether the level is a valid table or not, and why, see [`is_table`]
+    pub(crate) table_type: Option<Table>,
+}
+
+impl RowsCache {
+    /// the number of rows, whether the level is a table or not
+    pub(crate) fn len(&self) -> usize {
+        match &self.table {
+            Some((_, _, cells)) => cells.len(),
+            None => self.rows.len(),
+        }
+    }
+}
+
 #[derive(Clone)]
 /// the complete state of the application
 pub struct App {
@@ -44,14 +153,62 @@ pub struct App {
     pub position: CellPath,
     /// used for rendering
     pub rendering_tops: Vec<i32>,
+    /// the number of columns of a table scrolled past on the left, after the pinned ones, see
+    /// [`crate::config::Config::pinned_columns`]
+    pub horizontal_offset: usize,
     /// the current [`Mode`]
     pub mode: Mode,
     /// the editor to modify the cells of the data
     pub editor: Editor,
+    /// the prompt to type the path of a file in WRITING mode
+    pub prompt: Prompt,
     /// the value that is being explored
     pub value: Value,
+    /// the value as it was given, before any change, see [`App::reload`]
+    pub original_value: Value,
     /// the configuration for the app
     pub config: Config,
+    /// the filter on the items of a list, along with the cell path to that list
+    pub filter: Option<(CellPath, Filter)>,
+    /// the cell path to the record whose keys are being explored as a list, if any
+    ///
+    /// the data itself is left untouched, see [`App::enter_keys_view`]
+    pub keys: Option<CellPath>,
+    /// the search query, either being typed or the last one
+    pub search: String,
+    /// the command being typed in COMMAND mode, without the leading `:`
+    pub command: String,
+    /// the letters typed in NORMAL mode to jump to the first row starting with them
+    pub jump_prefix: String,
+    /// the number of ticks since the last letter of [`App::jump_prefix`] has been typed
+    pub jump_prefix_ticks: usize,
+    /// a message to show to the user until the next key is pressed
+    pub flash: Option<String>,
+    /// show the quick-start tour until the first key is pressed, see [`crate::help::tour`]
+    pub(crate) tour: bool,
+    /// the top-level type of the data, shown in the status bar until the first key is pressed,
+    /// see [`crate::ui::root_summary`]
+    pub(crate) root_summary: Option<String>,
+    /// the previous states of the data, along with the position of the cursor, most recent last
+    pub undo: Vec<(Value, CellPath)>,
+    /// the states of the data that have been undone, most recent last
+    pub redo: Vec<(Value, CellPath)>,
+    /// the rows of the level being displayed, see [`App::cache_rows`]
+    pub(crate) rows_cache: RowsCache,
+    /// the rows whose value is shown below them, as the string of their cell path, see
+    /// [`App::toggle_expansion`]
+    pub(crate) expanded: HashSet<String>,
+    /// the last range explored as a list, along with that list, because expanding a range again
+    /// on every call to [`App::value_under_cursor`], i.e. several times per draw, is expensive
+    pub(crate) range_cache: RefCell<Option<(Range, Value)>>,
+    /// the position and the filter at the time the size of the value under the cursor has been
+    /// estimated, along with that size, see [`App::cache_size`]
+    pub(crate) size_cache: Option<(CacheKey, usize)>,
+    /// the row of the key under the cursor when two records are compared side by side, see
+    /// [`Config::diff`] and [`App::diff_keys`]
+    pub(crate) diff_cursor: usize,
+    /// the first row of the help in the frame, see [`crate::help::render`]
+    pub(crate) help_top: usize,
 }
 
 impl Default for App {
@@ -59,22 +216,58 @@ impl Default for App {
         Self {
             position: CellPath { members: vec![] },
             rendering_tops: vec![],
+            horizontal_offset: 0,
             mode: Mode::default(),
             editor: Editor::default(),
+            prompt: Prompt::default(),
             value: Value::default(),
+            original_value: Value::default(),
             config: Config::default(),
+            filter: None,
+            keys: None,
+            search: String::new(),
+            command: String::new(),
+            jump_prefix: String::new(),
+            jump_prefix_ticks: 0,
+            flash: None,
+            tour: false,
+            root_summary: None,
+            undo: vec![],
+            redo: vec![],
+            rows_cache: RowsCache::default(),
+            expanded: HashSet::new(),
+            range_cache: RefCell::new(None),
+            size_cache: None,
+            diff_cursor: 0,
+            help_top: 0,
         }
     }
 }
 
 impl App {
     /// Handles the tick event of the terminal.
-    pub fn tick(&self) {}
+    ///
+    /// > :bulb: **Note**  
+    /// > the prefix to jump to is forgotten after [`JUMP_PREFIX_TIMEOUT_MS`]
+    pub fn tick(&mut self) {
+        if !self.jump_prefix.is_empty() {
+            self.jump_prefix_ticks += 1;
+            if self.jump_prefix_ticks as u64 * self.config.tick_rate_ms >= JUMP_PREFIX_TIMEOUT_MS {
+                self.jump_prefix.clear();
+            }
+        }
+    }
 
     pub(super) fn from_value(value: Value) -> Self {
-        let mut app = Self::default();
+        let mut app = Self {
+            original_value: value.clone(),
+            value,
+            ..Default::default()
+        };
+        app.root_summary = Some(root_summary(&app.value, !app.config.strict_tables));
 
-        match &value {
+        // NOTE: the root is seen as it's explored, e.g. a range as a list
+        match &app.value_under_cursor(None) {
             Value::List { vals, .. } => app.position.members.push(PathMember::Int {
                 val: 0,
                 span: Span::unknown(),
@@ -92,8 +285,6 @@ impl App {
             _ => {}
         }
 
-        app.value = value;
-
         app
     }
 
@@ -105,33 +296,588 @@ impl App {
         self.mode = Mode::Bottom;
     }
 
-    pub(super) fn enter_editor(&mut self) {
-        let value = self.value_under_cursor(None);
+    pub(super) fn enter_editor(&mut self) -> Result<(), String> {
+        let value = self.value_at(&self.position);
 
+        self.editor = Editor::from_value(&value, self.config.edit_format)?;
+        if self.config.confirm_type_change {
+            self.editor.confirm_type_change(&value);
+        }
         self.mode = Mode::Insert;
-        self.editor = Editor::from_value(&value);
+
+        Ok(())
+    }
+
+    /// explore the keys of the record the cursor is in as a list of strings, with the cursor on
+    /// the key it was on
+    ///
+    /// > :bulb: **Note**  
+    /// > the data is not modified, only the way it's looked at, until [`App::leave_keys_view`]
+    pub(super) fn enter_keys_view(&mut self) -> Result<(), String> {
+        let mut path = self.position.clone();
+        let current = path.members.pop();
+
+        match (current, self.value_under_cursor(Some(path.clone()))) {
+            (Some(PathMember::String { val, .. }), Value::Record { val: rec, .. }) => {
+                // NOTE: app.position.members should never be empty by construction
+                *self.position.members.last_mut().unwrap() = PathMember::Int {
+                    val: rec.columns().position(|col| col == &val).unwrap_or(0),
+                    span: Span::unknown(),
+                    optional: rec.is_empty(),
+                };
+                self.keys = Some(path);
+                self.rows_cache = RowsCache::default();
+                self.size_cache = None;
+                Ok(())
+            }
+            _ => Err("only the keys of a record can be explored".into()),
+        }
+    }
+
+    /// go back to the record whose keys are being explored, with the cursor on the key it was on
+    ///
+    /// > :bulb: **Note**  
+    /// > nothing happens if the keys of a record are not being explored
+    pub(super) fn leave_keys_view(&mut self) {
+        let Some(path) = self.keys.take() else {
+            return;
+        };
+
+        let index = match self.position.members.get(path.members.len()) {
+            Some(PathMember::Int { val, .. }) => *val,
+            _ => 0,
+        };
+        let cols = match self.value_under_cursor(Some(path.clone())) {
+            Value::Record { val: rec, .. } => rec.columns().cloned().collect::<Vec<_>>(),
+            _ => vec![],
+        };
+
+        self.position.members.truncate(path.members.len());
+        self.position.members.push(PathMember::String {
+            val: cols.get(index).cloned().unwrap_or_default(),
+            span: Span::unknown(),
+            optional: cols.is_empty(),
+        });
+        if self.filter.as_ref().is_some_and(|(p, _)| p == &path) {
+            self.filter = None;
+        }
+        self.rows_cache = RowsCache::default();
+        self.size_cache = None;
+    }
+
+    /// show the rows of the value at *path* below its own row, or hide them again, telling whether
+    /// they are now shown
+    ///
+    /// > :bulb: **Note**  
+    /// > the rows are tracked by their cell path, e.g. `$.foo.2`, so that they stay expanded when
+    /// > coming back to their level
+    pub(crate) fn toggle_expansion(&mut self, path: &CellPath) -> bool {
+        let key = format_cell_path(&path.members);
+        if self.expanded.remove(&key) {
+            false
+        } else {
+            self.expanded.insert(key);
+            true
+        }
+    }
+
+    /// tell whether the row at *path* is expanded, see [`App::toggle_expansion`]
+    pub(crate) fn is_expanded(&self, path: &CellPath) -> bool {
+        self.expanded.contains(&format_cell_path(&path.members))
     }
 
+    /// the data as seen by the user, i.e. with the record whose keys are being explored, if any,
+    /// replaced by the list of its keys
+    fn data(&self) -> Value {
+        let Some(path) = &self.keys else {
+            return self.value.clone();
+        };
+
+        match self.value.clone().follow_cell_path(&path.members, false) {
+            Ok(Value::Record { val: rec, .. }) => {
+                let keys = Value::list(
+                    rec.columns()
+                        .map(|col| Value::string(col, Span::unknown()))
+                        .collect(),
+                    Span::unknown(),
+                );
+                mutate_value_cell(&self.value, path, &keys).unwrap_or_else(|| self.value.clone())
+            }
+            _ => self.value.clone(),
+        }
+    }
+
+    /// the number of keys of the two records compared side by side at the top level, if they are,
+    /// see [`Config::diff`]
+    pub(crate) fn diff_keys(&self) -> Option<usize> {
+        if !self.config.diff || self.position.members.len() != 1 || self.is_at_bottom() {
+            return None;
+        }
+
+        diff_pair(&self.view_at(&CellPath { members: vec![] }))
+            .map(|(a, b)| diff_records(a, b).len())
+    }
+
+    /// the value under the cursor, or at the *alternate_cursor*, as it's explored
+    ///
+    /// > :bulb: **Note**  
+    /// > a range is explored as the list of its first [`RANGE_LIMIT`] items, see
+    /// > [`App::value_at`] to get it as is, which are only computed once, see [`App::range_cache`]
     pub(crate) fn value_under_cursor(&self, alternate_cursor: Option<CellPath>) -> Value {
-        self.value
-            .clone()
-            .follow_cell_path(
-                &alternate_cursor.unwrap_or(self.position.clone()).members,
-                false,
-            )
+        match self.value_at(&alternate_cursor.unwrap_or(self.position.clone())) {
+            Value::Range { val, .. } => {
+                let mut cache = self.range_cache.borrow_mut();
+                match cache.as_ref() {
+                    Some((range, list)) if range == val.as_ref() => list.clone(),
+                    _ => {
+                        let list = Value::list(expand_range(&val, RANGE_LIMIT).0, Span::unknown());
+                        *cache = Some((*val, list.clone()));
+                        list
+                    }
+                }
+            }
+            value => value,
+        }
+    }
+
+    /// the value at *path*, as it is in the data, e.g. to be peeked
+    pub(crate) fn value_at(&self, path: &CellPath) -> Value {
+        self.data()
+            .follow_cell_path(&path.members, false)
             .unwrap_or_else(|_| {
                 panic!(
                     "unexpected error when following {:?} in {}",
-                    self.position.members,
+                    path.members,
                     self.value
                         .to_expanded_string(" ", &nu_protocol::Config::default())
                 )
             })
     }
 
+    /// the indices of the items of the list at `path` that pass through the filter
+    ///
+    /// > **Note**  
+    /// > all the indices are given if there is no filter on the list at `path`
+    pub(crate) fn visible_indices(&self, path: &CellPath, vals: &[Value]) -> Vec<usize> {
+        match &self.filter {
+            Some((filter_path, filter)) if filter_path == path => vals
+                .iter()
+                .enumerate()
+                .filter(|(_, v)| filter.matches(v))
+                .map(|(i, _)| i)
+                .collect(),
+            _ => (0..vals.len()).collect(),
+        }
+    }
+
+    /// the keys of a record, in the order they are shown to the user, i.e. sorted with
+    /// [`Config::view_sort_keys`]
+    pub(crate) fn columns_in_view(&self, rec: &Record) -> Vec<String> {
+        let mut cols = rec.columns().cloned().collect::<Vec<_>>();
+        if self.config.view_sort_keys {
+            cols.sort();
+        }
+        cols
+    }
+
+    /// the value at `path`, as seen by the user, i.e. with the filter applied and the keys sorted
+    /// if any
+    pub(crate) fn view_at(&self, path: &CellPath) -> Value {
+        match self.value_under_cursor(Some(path.clone())) {
+            Value::Record { val: rec, .. } if self.config.view_sort_keys => Value::record(
+                self.columns_in_view(&rec)
+                    .into_iter()
+                    .map(|col| {
+                        let val = rec.get(&col).cloned().unwrap_or_default();
+                        (col, val)
+                    })
+                    .collect(),
+                Span::unknown(),
+            ),
+            Value::List { vals, .. } => Value::list(
+                self.visible_indices(path, &vals)
+                    .iter()
+                    .map(|&i| vals[i].clone())
+                    .collect(),
+                Span::unknown(),
+            ),
+            value => value,
+        }
+    }
+
+    /// compute the representation of the rows of *value*, the level at *path*, or of the whole
+    /// table when it's a valid one, into [`App::rows_cache`], unless they are already there
+    pub(crate) fn cache_rows(&mut self, path: &CellPath, value: &Value) {
+        let key = Some((path.clone(), self.filter.clone()));
+        if self.rows_cache.key == key {
+            return;
+        }
+
+        let options = ReprOptions::from_config(&self.config);
+        let table_type = Some(is_table(value, !self.config.strict_tables));
+        self.rows_cache = match value {
+            Value::List { vals, .. } if table_type == Some(Table::IsValid) => {
+                // NOTE: a valid table is always a list of records
+                let recs = vals
+                    .iter()
+                    .map(|v| v.as_record().unwrap().clone())
+                    .collect::<Vec<Record>>();
+                RowsCache {
+                    key,
+                    rows: vec![],
+                    table: Some(repr_table(&recs, options)),
+                    table_type,
+                }
+            }
+            _ => RowsCache {
+                key,
+                rows: repr_data(value, options),
+                table: None,
+                table_type,
+            },
+        };
+    }
+
+    /// estimate the size of the value under the cursor into [`App::size_cache`], unless the cursor
+    /// has not moved since
+    ///
+    /// > :bulb: **Note**  
+    /// > the whole value is walked, which is why it's not done on every draw
+    pub(crate) fn cache_size(&mut self) {
+        let key = (self.position.clone(), self.filter.clone());
+        if self.size_cache.as_ref().is_some_and(|(k, _)| k == &key) {
+            return;
+        }
+
+        let size = estimate_size(&self.value_under_cursor(None));
+        self.size_cache = Some((key, size));
+    }
+
+    /// the rows of the current level that are selected in VISUAL mode, both bounds included
+    pub(crate) fn visual_selection(&self) -> Option<(usize, usize)> {
+        match self.mode {
+            Mode::Visual(anchor) => {
+                let cursor = self.selected_row();
+                Some((anchor.min(cursor), anchor.max(cursor)))
+            }
+            _ => None,
+        }
+    }
+
+    /// change the cell at `path` to `cell`, remembering the previous state of the data
+    ///
+    /// `position` is where the cursor was before the change, to be restored on undo.
+    ///
+    /// > :bulb: **Note**  
+    /// > - at most [`Config::undo_depth`] changes are remembered, the oldest ones being forgotten
+    /// > - the items of an unbounded range, or of a range of more than [`RANGE_LIMIT`] items,
+    /// >   can't be changed, which is flashed to the user
+    pub(crate) fn mutate(&mut self, cell: &Value, path: &CellPath, position: CellPath) {
+        let Some(value) = mutate_value_cell(&self.value, path, cell) else {
+            self.position = position;
+            self.flash = Some(format!(
+                "the items of an unbounded range or of a range of more than {} items can't be changed",
+                RANGE_LIMIT
+            ));
+            return;
+        };
+
+        self.undo
+            .push((std::mem::replace(&mut self.value, value), position));
+        if self.undo.len() > self.config.undo_depth {
+            self.undo.drain(..self.undo.len() - self.config.undo_depth);
+        }
+        self.redo.clear();
+        self.rows_cache = RowsCache::default();
+        self.size_cache = None;
+    }
+
+    /// go back to the previous state of the data, returns `false` if there is nothing to undo
+    pub(crate) fn undo(&mut self) -> bool {
+        match self.undo.pop() {
+            Some((value, position)) => {
+                self.rows_cache = RowsCache::default();
+                self.size_cache = None;
+                let value = std::mem::replace(&mut self.value, value);
+                let position = std::mem::replace(&mut self.position, position);
+                self.redo.push((value, position));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// go forward to the last state of the data that has been undone, returns `false` if there is
+    /// nothing to redo
+    pub(crate) fn redo(&mut self) -> bool {
+        match self.redo.pop() {
+            Some((value, position)) => {
+                self.rows_cache = RowsCache::default();
+                self.size_cache = None;
+                let value = std::mem::replace(&mut self.value, value);
+                let position = std::mem::replace(&mut self.position, position);
+                self.undo.push((value, position));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// go back to [`App::original_value`], at the root, forgetting all the changes
+    ///
+    /// > :bulb: **Note**  
+    /// > the changes can't be undone afterwards and the filter, the keys view, the expanded rows
+    /// > and the columns scrolled past are cleared, as they point into the changed data
+    pub(crate) fn reload(&mut self) {
+        let app = Self::from_value(self.original_value.clone());
+        self.value = app.value;
+        self.position = app.position;
+        self.mode = Mode::Normal;
+        self.rendering_tops.clear();
+        self.horizontal_offset = 0;
+        self.diff_cursor = 0;
+        self.filter = None;
+        self.keys = None;
+        self.expanded.clear();
+        self.undo.clear();
+        self.redo.clear();
+        self.rows_cache = RowsCache::default();
+        self.size_cache = None;
+        *self.range_cache.borrow_mut() = None;
+    }
+
+    /// use the *config*, starting at [`Config::start_path`] if it exists in the data
+    ///
+    /// > :bulb: **Note**  
+    /// > an invalid start path is flashed to the user and the app starts at the root instead
     pub(crate) fn with_config(&self, config: Config) -> Self {
         let mut app = self.clone();
         app.config = config;
+
+        // NOTE: a loose table is only a table with the loose config
+        if !app.config.strict_tables && app.root_summary.is_some() {
+            app.root_summary = Some(root_summary(&app.value, true));
+        }
+
+        // NOTE: the cursor starts on the first key as shown, which depends on the config
+        if app.config.view_sort_keys && matches!(app.value, Value::Record { .. }) {
+            app.go_up_or_down_in_data(Direction::Top);
+        }
+
+        if app.config.diff && diff_pair(&app.value).is_none() {
+            app.flash = Some("only a list of two records can be compared".into());
+        }
+
+        // NOTE: an empty start path is the root, where the app starts anyway
+        if let Some(start_path) = app.config.start_path.clone().filter(|p| !p.is_empty()) {
+            let path = CellPath {
+                members: start_path.iter().map(|m| parse_path_member(m)).collect(),
+            };
+            if !app.go_to_cell_path(path) {
+                app.flash = Some(format!(
+                    "'{}' is not a valid start path in the data, starting at the root",
+                    start_path.join(".")
+                ));
+            }
+        }
+
         app
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use nu_protocol::{
+        ast::{CellPath, RangeInclusion},
+        record, Range, Span, Value,
+    };
+
+    use super::{App, RANGE_LIMIT};
+    use crate::{
+        config::Config,
+        nu::cell_path::{to_path_member_vec, PM},
+    };
+
+    #[test]
+    fn invalidate_the_rows_cache() {
+        let mut app = App::from_value(Value::test_list(vec![
+            Value::test_int(1),
+            Value::test_int(2),
+        ]));
+        let root = CellPath { members: vec![] };
+
+        app.cache_rows(&root, &app.view_at(&root));
+        assert_eq!(app.rows_cache.rows[0].data, "1");
+
+        // the value is not looked at as long as the level is the same
+        app.cache_rows(&root, &Value::test_list(vec![]));
+        assert_eq!(app.rows_cache.rows.len(), 2);
+
+        let path = CellPath {
+            members: to_path_member_vec(&[PM::I(0)]),
+        };
+        app.mutate(&Value::test_int(3), &path, path.clone());
+        app.cache_rows(&root, &app.view_at(&root));
+        assert_eq!(app.rows_cache.rows[0].data, "3");
+
+        app.undo();
+        app.cache_rows(&root, &app.view_at(&root));
+        assert_eq!(app.rows_cache.rows[0].data, "1");
+
+        app.redo();
+        app.cache_rows(&root, &app.view_at(&root));
+        assert_eq!(app.rows_cache.rows[0].data, "3");
+
+        // the representation of a table is cached in the same way
+        let mut app = App::from_value(Value::test_list(vec![Value::test_record(
+            record! { "a" => Value::test_int(1) },
+        )]));
+        app.cache_rows(&root, &app.view_at(&root));
+        let cells = |app: &App| {
+            app.rows_cache
+                .table
+                .as_ref()
+                .map(|(_, _, cells)| cells.clone())
+        };
+        assert_eq!(cells(&app), Some(vec![vec!["1".to_string()]]));
+
+        app.cache_rows(&root, &Value::test_list(vec![]));
+        assert_eq!(cells(&app), Some(vec![vec!["1".to_string()]]));
+    }
+
+    #[test]
+    fn cache_the_size_under_the_cursor() {
+        let mut app = App::from_value(Value::test_list(vec![
+            Value::test_string("a"),
+            Value::test_string("abcdefghij"),
+        ]));
+        let size = |app: &App| app.size_cache.as_ref().map(|(_, size)| *size);
+
+        app.cache_size();
+        let first = size(&app).unwrap();
+
+        // the size is not estimated again as long as the cursor does not move
+        app.size_cache = Some(((app.position.clone(), None), 0));
+        app.cache_size();
+        assert_eq!(size(&app), Some(0));
+
+        app.position = CellPath {
+            members: to_path_member_vec(&[PM::I(1)]),
+        };
+        app.cache_size();
+        assert!(size(&app).unwrap() > first);
+
+        // ... nor as long as the data does not change
+        let path = app.position.clone();
+        app.mutate(&Value::test_string("a"), &path, path.clone());
+        assert_eq!(size(&app), None);
+        app.cache_size();
+        assert_eq!(size(&app), Some(first));
+    }
+
+    #[test]
+    fn expand_ranges_once() {
+        let range = |end: i64| {
+            Value::test_range(
+                Range::new(
+                    Value::test_int(1),
+                    Value::test_nothing(),
+                    Value::test_int(end),
+                    RangeInclusion::Inclusive,
+                    Span::test_data(),
+                )
+                .unwrap(),
+            )
+        };
+        let mut app = App::from_value(Value::test_record(record! {
+            "r" => range(1_000_000_000),
+            "s" => range(3),
+        }));
+        let r = CellPath {
+            members: to_path_member_vec(&[PM::S("r")]),
+        };
+
+        let list = app.value_under_cursor(Some(r.clone()));
+        assert_eq!(list.as_list().unwrap().len(), RANGE_LIMIT);
+        assert!(app.range_cache.borrow().is_some());
+        assert_eq!(app.value_under_cursor(Some(r.clone())), list);
+
+        // the cache follows the range being explored
+        let s = CellPath {
+            members: to_path_member_vec(&[PM::S("s")]),
+        };
+        assert_eq!(
+            app.value_under_cursor(Some(s)),
+            Value::test_list(vec![
+                Value::test_int(1),
+                Value::test_int(2),
+                Value::test_int(3)
+            ])
+        );
+
+        // the items of a range too big to be a list can't be changed
+        let path = CellPath {
+            members: to_path_member_vec(&[PM::S("r"), PM::I(0)]),
+        };
+        app.mutate(&Value::test_int(0), &path, path.clone());
+        assert!(app.flash.is_some());
+        assert!(app.undo.is_empty());
+    }
+
+    #[test]
+    fn start_at_a_cell_path() {
+        let value = Value::test_record(record! {
+            "config" => Value::test_record(record! {
+                "plugins" => Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+            }),
+            "env" => Value::test_nothing(),
+        });
+        let with_start_path = |path: &[&str]| {
+            let config = Config {
+                start_path: Some(path.iter().map(|m| m.to_string()).collect()),
+                ..Default::default()
+            };
+            App::from_value(value.clone()).with_config(config)
+        };
+
+        let app = with_start_path(&["config", "plugins", "1"]);
+        assert_eq!(
+            app.position.members,
+            to_path_member_vec(&[PM::S("config"), PM::S("plugins"), PM::I(1)])
+        );
+        assert_eq!(app.rendering_tops, vec![0, 0, 0]);
+        assert_eq!(app.flash, None);
+
+        // an invalid path starts at the root
+        let app = with_start_path(&["config", "nope"]);
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("config")]));
+        assert!(app.flash.is_some());
+
+        let app = with_start_path(&[]);
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("config")]));
+        assert_eq!(app.flash, None);
+    }
+
+    #[test]
+    fn track_the_expanded_rows() {
+        let mut app = App::from_value(Value::test_nothing());
+        let path = |members: &[PM]| CellPath {
+            members: to_path_member_vec(members),
+        };
+
+        let a = path(&[PM::S("a"), PM::I(2)]);
+        assert!(!app.is_expanded(&a));
+
+        assert!(app.toggle_expansion(&a));
+        assert!(app.is_expanded(&a));
+        // the other rows are not expanded, even the ones above or below
+        assert!(!app.is_expanded(&path(&[PM::S("a")])));
+        assert!(!app.is_expanded(&path(&[PM::S("a"), PM::I(1)])));
+        assert!(!app.is_expanded(&path(&[PM::S("a"), PM::I(2), PM::S("b")])));
+
+        assert!(app.toggle_expansion(&path(&[PM::S("b")])));
+        assert!(!app.toggle_expansion(&a));
+        assert!(!app.is_expanded(&a));
+        assert!(app.is_expanded(&path(&[PM::S("b")])));
+    }
+}
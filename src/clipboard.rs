@@ -0,0 +1,25 @@
+//! copy values to the system clipboard
+use nu_protocol::Value;
+use nuon::{to_nuon, ToStyle};
+
+/// copy the NUON representation of a *value* to the system clipboard
+///
+/// > :bulb: **Note**  
+/// > this requires the `clipboard` feature, otherwise an error is always returned
+pub(crate) fn copy_to_clipboard(value: &Value) -> Result<(), String> {
+    let text = to_nuon(value, ToStyle::Raw, None).map_err(|e| e.to_string())?;
+
+    copy_text(text)
+}
+
+#[cfg(feature = "clipboard")]
+fn copy_text(text: String) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|e| format!("could not copy to the clipboard: {}", e))
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn copy_text(_text: String) -> Result<(), String> {
+    Err("the clipboard is not supported, please compile with the `clipboard` feature".into())
+}
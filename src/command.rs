@@ -0,0 +1,337 @@
+//! the commands that can be typed in COMMAND mode, e.g. `:q`
+use std::path::{Path, PathBuf};
+
+use nu_protocol::{
+    ast::{CellPath, PathMember},
+    Span, Value,
+};
+use nuon::{to_nuon, ToStyle};
+
+use crate::{
+    app::App,
+    config::Layout,
+    handler::TransitionResult,
+    nu::serialize::{serialize_value, Format},
+};
+
+/// a command that has been typed after `:`
+#[derive(Debug, PartialEq)]
+pub(crate) enum Command {
+    /// `:q`, quit the application
+    Quit,
+    /// `:w <path>`, write the current value to a file as NUON
+    Write(PathBuf),
+    /// `:goto <cellpath>`, jump to an arbitrary cell path, e.g. `foo.0.bar`
+    Goto(CellPath),
+    /// `:layout table|compact`, change the layout of the data
+    Layout(Layout),
+    /// `:set [no]<option>[!]`, turn an option on or off, or toggle it with a trailing `!`
+    Set(Setting, Switch),
+    /// `:config`, give back the current config, e.g. after changing the layout
+    Config,
+    /// `:peek json|yaml|toml|nuon`, give back the value under the cursor as a string in a format
+    Peek(Format),
+}
+
+/// an option that can be changed at runtime with `:set`
+#[derive(Debug, PartialEq)]
+pub(crate) enum Setting {
+    /// `number`, see [`Config::number`](crate::config::Config::number)
+    Number,
+    /// `relativenumber`, see [`Config::relativenumber`](crate::config::Config::relativenumber)
+    RelativeNumber,
+}
+
+/// how to change a [`Setting`]
+#[derive(Debug, PartialEq)]
+pub(crate) enum Switch {
+    On,
+    Off,
+    Toggle,
+}
+
+impl Switch {
+    /// the new state of an option that was *current*
+    fn apply(&self, current: bool) -> bool {
+        match self {
+            Self::On => true,
+            Self::Off => false,
+            Self::Toggle => !current,
+        }
+    }
+}
+
+/// parse an option such as `number`, `nonumber` or `number!`
+///
+/// both `number` and `relativenumber` can be set, to get absolute numbers on the line under the
+/// cursor and relative ones elsewhere, i.e. *hybrid* numbers.
+fn parse_setting(option: &str) -> Result<(Setting, Switch), String> {
+    let (option, switch) = if let Some(option) = option.strip_suffix('!') {
+        (option, Switch::Toggle)
+    } else if let Some(option) = option.strip_prefix("no") {
+        (option, Switch::Off)
+    } else {
+        (option, Switch::On)
+    };
+
+    let setting = match option {
+        "number" | "nu" => Setting::Number,
+        "relativenumber" | "rnu" => Setting::RelativeNumber,
+        _ => return Err(format!("unknown option '{}'", option)),
+    };
+
+    Ok((setting, switch))
+}
+
+/// write the NUON representation of a *value* to the file at *path*
+pub(crate) fn write_nuon(value: &Value, path: &Path) -> Result<(), String> {
+    let nuon = to_nuon(value, ToStyle::Raw, None).map_err(|e| e.to_string())?;
+
+    std::fs::write(path, nuon)
+        .map_err(|e| format!("could not write to '{}': {}", path.display(), e))
+}
+
+/// parse a single member of a cell path, an integer being a list index and anything else a key
+pub(crate) fn parse_path_member(member: &str) -> PathMember {
+    match member.parse::<usize>() {
+        Ok(val) => PathMember::Int {
+            val,
+            span: Span::unknown(),
+            optional: false,
+        },
+        Err(_) => PathMember::String {
+            val: member.into(),
+            span: Span::unknown(),
+            optional: false,
+        },
+    }
+}
+
+/// parse a cell path such as `foo.0.bar` or `$.foo.0.bar`, integers being list indices
+fn parse_cell_path(path: &str) -> Result<CellPath, String> {
+    let path = path.strip_prefix("$.").unwrap_or(path);
+
+    let members = path
+        .split('.')
+        .map(|member| {
+            if member.is_empty() {
+                return Err(format!("invalid cell path '{}'", path));
+            }
+
+            Ok(parse_path_member(member))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(CellPath { members })
+}
+
+impl Command {
+    /// parse the command line, without the leading `:`
+    pub(crate) fn parse(line: &str) -> Result<Self, String> {
+        let mut tokens = line.split_whitespace();
+        let (name, args) = match tokens.next() {
+            Some(name) => (name, tokens.collect::<Vec<&str>>()),
+            None => return Err("empty command".into()),
+        };
+
+        match (name, args.as_slice()) {
+            ("q", []) => Ok(Self::Quit),
+            ("w", [path]) => Ok(Self::Write(PathBuf::from(path))),
+            ("w", _) => Err("usage: :w <path>".into()),
+            ("goto", [path]) => Ok(Self::Goto(parse_cell_path(path)?)),
+            ("goto", _) => Err("usage: :goto <cellpath>".into()),
+            ("layout", ["table"]) => Ok(Self::Layout(Layout::Table)),
+            ("layout", ["compact"]) => Ok(Self::Layout(Layout::Compact)),
+            ("layout", _) => Err("usage: :layout table|compact".into()),
+            ("set", [option]) => {
+                let (setting, switch) = parse_setting(option)?;
+                Ok(Self::Set(setting, switch))
+            }
+            ("set", _) => Err("usage: :set [no]<option>[!]".into()),
+            ("config", []) => Ok(Self::Config),
+            ("config", _) => Err("usage: :config".into()),
+            ("peek", ["json"]) => Ok(Self::Peek(Format::Json)),
+            ("peek", ["yaml"]) => Ok(Self::Peek(Format::Yaml)),
+            ("peek", ["toml"]) => Ok(Self::Peek(Format::Toml)),
+            ("peek", ["nuon"]) => Ok(Self::Peek(Format::Nuon)),
+            ("peek", _) => Err("usage: :peek json|yaml|toml|nuon".into()),
+            (x, _) => Err(format!("unknown command '{}'", x)),
+        }
+    }
+}
+
+impl App {
+    /// parse and run the command line, without the leading `:`
+    pub(crate) fn run_command(&mut self, line: &str) -> TransitionResult {
+        let command = match Command::parse(line) {
+            Ok(command) => command,
+            Err(err) => return TransitionResult::Error(err),
+        };
+
+        match command {
+            Command::Quit => return self.quit(),
+            Command::Write(path) => {
+                if let Err(err) = write_nuon(&self.value, &path) {
+                    return TransitionResult::Error(err);
+                }
+                self.flash = Some(format!("written to '{}'", path.display()));
+            }
+            Command::Goto(path) => {
+                if !self.go_to_cell_path(path) {
+                    return TransitionResult::Error(format!(
+                        "'{}' is not a valid cell path in the data",
+                        line.trim_start_matches("goto").trim()
+                    ));
+                }
+            }
+            Command::Layout(layout) => self.config.layout = layout,
+            Command::Set(Setting::Number, switch) => {
+                self.config.number = switch.apply(self.config.number)
+            }
+            Command::Set(Setting::RelativeNumber, switch) => {
+                self.config.relativenumber = switch.apply(self.config.relativenumber)
+            }
+            Command::Config => return TransitionResult::Return(self.config.to_value()),
+            Command::Peek(format) => {
+                return match serialize_value(&self.value_under_cursor(None), format) {
+                    Ok(text) => TransitionResult::Return(Value::string(text, Span::unknown())),
+                    Err(err) => TransitionResult::Error(err),
+                }
+            }
+        }
+
+        TransitionResult::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use nu_protocol::{ast::PathMember, record, Span, Value};
+
+    use super::{Command, Format, Setting, Switch};
+    use crate::{app::App, config::Layout, handler::TransitionResult};
+
+    fn cell_path(members: &[PathMember]) -> nu_protocol::ast::CellPath {
+        nu_protocol::ast::CellPath {
+            members: members.to_vec(),
+        }
+    }
+
+    fn s(val: &str) -> PathMember {
+        PathMember::String {
+            val: val.into(),
+            span: Span::unknown(),
+            optional: false,
+        }
+    }
+
+    fn i(val: usize) -> PathMember {
+        PathMember::Int {
+            val,
+            span: Span::unknown(),
+            optional: false,
+        }
+    }
+
+    #[test]
+    fn parse_commands() {
+        #[rustfmt::skip]
+        let cases = vec![
+            ("q", Ok(Command::Quit)),
+            ("  q  ", Ok(Command::Quit)),
+            ("w foo.nuon", Ok(Command::Write(PathBuf::from("foo.nuon")))),
+            ("goto foo.0.bar", Ok(Command::Goto(cell_path(&[s("foo"), i(0), s("bar")])))),
+            ("goto $.foo", Ok(Command::Goto(cell_path(&[s("foo")])))),
+            ("layout table", Ok(Command::Layout(Layout::Table))),
+            ("layout compact", Ok(Command::Layout(Layout::Compact))),
+            ("", Err("empty command".to_string())),
+            ("q!", Err("unknown command 'q!'".to_string())),
+            ("w", Err("usage: :w <path>".to_string())),
+            ("w foo bar", Err("usage: :w <path>".to_string())),
+            ("goto", Err("usage: :goto <cellpath>".to_string())),
+            ("goto foo..bar", Err("invalid cell path 'foo..bar'".to_string())),
+            ("layout foo", Err("usage: :layout table|compact".to_string())),
+            ("set number", Ok(Command::Set(Setting::Number, Switch::On))),
+            ("set nonu", Ok(Command::Set(Setting::Number, Switch::Off))),
+            ("set relativenumber!", Ok(Command::Set(Setting::RelativeNumber, Switch::Toggle))),
+            ("set rnu", Ok(Command::Set(Setting::RelativeNumber, Switch::On))),
+            ("set", Err("usage: :set [no]<option>[!]".to_string())),
+            ("set foo", Err("unknown option 'foo'".to_string())),
+            ("config", Ok(Command::Config)),
+            ("config foo", Err("usage: :config".to_string())),
+            ("peek json", Ok(Command::Peek(Format::Json))),
+            ("peek nuon", Ok(Command::Peek(Format::Nuon))),
+            ("peek", Err("usage: :peek json|yaml|toml|nuon".to_string())),
+            ("peek xml", Err("usage: :peek json|yaml|toml|nuon".to_string())),
+        ];
+
+        for (line, expected) in cases {
+            assert_eq!(Command::parse(line), expected, "parsing ':{}'", line);
+        }
+    }
+
+    #[test]
+    fn run_commands() {
+        let value = Value::test_record(record! {
+            "foo" => Value::test_list(vec![
+                Value::test_record(record! { "bar" => Value::test_int(1) }),
+            ]),
+            "baz" => Value::test_string("hello"),
+        });
+        let mut app = App::from_value(value);
+
+        assert_eq!(app.run_command("q"), TransitionResult::Quit);
+
+        assert_eq!(
+            app.run_command("goto foo.0.bar"),
+            TransitionResult::Continue
+        );
+        assert_eq!(app.position, cell_path(&[s("foo"), i(0), s("bar")]));
+        assert_eq!(app.rendering_tops.len(), 3);
+
+        assert!(matches!(
+            app.run_command("goto foo.1"),
+            TransitionResult::Error(_)
+        ));
+        assert_eq!(app.position, cell_path(&[s("foo"), i(0), s("bar")]));
+
+        assert_eq!(
+            app.run_command("layout compact"),
+            TransitionResult::Continue
+        );
+        assert_eq!(app.config.layout, Layout::Compact);
+        assert_eq!(
+            app.run_command("config"),
+            TransitionResult::Return(app.config.to_value())
+        );
+
+        assert!(matches!(
+            app.run_command("nope"),
+            TransitionResult::Error(_)
+        ));
+
+        // NOTE: the cursor is on `foo.0.bar`
+        assert_eq!(
+            app.run_command("peek json"),
+            TransitionResult::Return(Value::string("1", Span::unknown()))
+        );
+        assert!(matches!(
+            app.run_command("peek toml"),
+            TransitionResult::Error(_)
+        ));
+
+        let file = std::env::temp_dir().join(format!(
+            "nu_plugin_explore-command-{}.nuon",
+            std::process::id()
+        ));
+        assert_eq!(
+            app.run_command(&format!("w {}", file.display())),
+            TransitionResult::Continue
+        );
+        let written = nuon::from_nuon(&std::fs::read_to_string(&file).unwrap(), None).unwrap();
+        assert_eq!(written, app.value);
+        std::fs::remove_file(&file).unwrap();
+    }
+}
@@ -0,0 +1,374 @@
+//! the other way around from [`parsing`](super::parsing), i.e. dump a [`Config`] back into a
+//! [`Value`], see [`Config::to_value`]
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    style::{Color, Modifier},
+    widgets::BorderType,
+};
+
+use nu_protocol::{record, Record, Span, Value};
+
+use super::{BgFgColorConfig, Config, DataAlignment, EditFormat, Layout, ReturnOnQuit};
+
+fn string(val: impl Into<String>) -> Value {
+    Value::string(val, Span::unknown())
+}
+
+fn bool(val: bool) -> Value {
+    Value::bool(val, Span::unknown())
+}
+
+fn int(val: usize) -> Value {
+    Value::int(val as i64, Span::unknown())
+}
+
+fn nothing() -> Value {
+    Value::nothing(Span::unknown())
+}
+
+fn rec(val: Record) -> Value {
+    Value::record(val, Span::unknown())
+}
+
+/// dump a color, either by its name or as `#rrggbb`
+///
+/// > :bulb: **Note**  
+/// > the indexed colors can't be parsed from a config, they are dumped as `reset`
+fn color(color: &Color) -> Value {
+    string(match color {
+        Color::Rgb(r, g, b) => return string(format!("#{:02x}{:02x}{:02x}", r, g, b)),
+        Color::Black => "black",
+        Color::Red => "red",
+        Color::Green => "green",
+        Color::Yellow => "yellow",
+        Color::Blue => "blue",
+        Color::Magenta => "magenta",
+        Color::Cyan => "cyan",
+        Color::Gray => "gray",
+        Color::DarkGray => "darkgray",
+        Color::LightRed => "lightred",
+        Color::LightGreen => "lightgreen",
+        Color::LightYellow => "lightyellow",
+        Color::LightBlue => "lightblue",
+        Color::LightMagenta => "lightmagenta",
+        Color::LightCyan => "lightcyan",
+        Color::White => "white",
+        Color::Reset | Color::Indexed(_) => "reset",
+    })
+}
+
+fn fg_bg_colors(colors: &BgFgColorConfig) -> Value {
+    rec(record! {
+        "background" => color(&colors.background),
+        "foreground" => color(&colors.foreground),
+    })
+}
+
+/// dump ANSI modifiers as a space-separated list, e.g. `"bold underline"`
+fn modifier(modifier: &Modifier) -> Value {
+    let names = [
+        (Modifier::BOLD, "bold"),
+        (Modifier::ITALIC, "italic"),
+        (Modifier::UNDERLINED, "underline"),
+        (Modifier::SLOW_BLINK, "blink"),
+        (Modifier::REVERSED, "reversed"),
+        (Modifier::DIM, "dim"),
+        (Modifier::CROSSED_OUT, "crossed_out"),
+    ];
+
+    string(
+        names
+            .iter()
+            .filter(|(m, _)| modifier.contains(*m))
+            .map(|(_, name)| *name)
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+/// dump a key as it's written in the config, e.g. `'q'`, `"<c-d>"` or `"escape"`
+///
+/// > :bulb: **Note**  
+/// > this is not [`repr_key`](crate::handler::repr_key), which is meant to be read in the TUI and
+/// > can't be parsed back
+fn key(key: &KeyEvent) -> Value {
+    let code = match key.code {
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("f{}", n),
+        KeyCode::Up => return string("up"),
+        KeyCode::Down => return string("down"),
+        KeyCode::Left => return string("left"),
+        KeyCode::Right => return string("right"),
+        KeyCode::Esc => return string("escape"),
+        // NOTE: the other keys can't be parsed from a config
+        _ => return string("escape"),
+    };
+
+    let mut prefix = String::new();
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        prefix.push_str("c-");
+    }
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        prefix.push_str("a-");
+    }
+
+    match (prefix.is_empty(), key.code) {
+        (true, KeyCode::Char(_)) => string(code),
+        _ => string(format!("<{}{}>", prefix, code)),
+    }
+}
+
+impl Config {
+    /// dump the config into a record that can be given back to [`Config::from_value`], e.g. to
+    /// save a config tweaked while exploring, see `:config`
+    pub fn to_value(&self) -> Value {
+        let layout = match self.layout {
+            Layout::Table => "table",
+            Layout::Compact => "compact",
+        };
+        let border_type = match self.border_type {
+            BorderType::Rounded => "rounded",
+            BorderType::Double => "double",
+            // NOTE: the other border types can't be parsed from a config
+            _ => "plain",
+        };
+        let data_alignment = match self.data_alignment {
+            DataAlignment::Left => "left",
+            DataAlignment::Right => "right",
+            DataAlignment::Center => "center",
+        };
+        let edit_format = match self.edit_format {
+            EditFormat::Nuon => "nuon",
+            EditFormat::Json => "json",
+            EditFormat::Yaml => "yaml",
+        };
+        let return_on_quit = match self.return_on_quit {
+            ReturnOnQuit::Nothing => "nothing",
+            ReturnOnQuit::MutatedValue => "mutated_value",
+        };
+
+        // NOTE: the types are sorted for the dump to be the same from one run to the next
+        let mut types = self.colors.types.iter().collect::<Vec<_>>();
+        types.sort_by_key(|(ty, _)| *ty);
+
+        let colors = &self.colors;
+        let kmap = &self.keybindings;
+        rec(record! {
+            "show_cell_path" => bool(self.show_cell_path),
+            "show_table_header" => bool(self.show_table_header),
+            "show_hints" => bool(self.show_hints),
+            "show_shape_summary" => bool(self.show_shape_summary),
+            "show_size" => bool(self.show_size),
+            "announce_changes" => bool(self.announce_changes),
+            "persist_ui_state" => bool(self.persist_ui_state),
+            "mouse" => bool(self.mouse),
+            "mouse_scroll" => int(self.mouse_scroll),
+            "preview_max_length" => int(self.preview_max_length),
+            "truncation_symbol" => string(&self.truncation_symbol),
+            "undo_depth" => int(self.undo_depth),
+            "strict_tables" => bool(self.strict_tables),
+            "editable" => bool(self.editable),
+            "diff" => bool(self.diff),
+            "confirm_type_change" => bool(self.confirm_type_change),
+            "enter_peeks" => bool(self.enter_peeks),
+            "date_format" => self.date_format.as_ref().map_or_else(nothing, string),
+            "wrap_bottom_value" => bool(self.wrap_bottom_value),
+            "wrap_navigation" => bool(self.wrap_navigation),
+            "view_sort_keys" => bool(self.view_sort_keys),
+            "show_scrollbar" => bool(self.show_scrollbar),
+            "null_placeholder" => string(&self.null_placeholder),
+            "show_empty_string_quotes" => bool(self.show_empty_string_quotes),
+            "bool_glyphs" => bool(self.bool_glyphs),
+            "glyphs" => rec(record! {
+                "yes" => string(&self.glyphs.yes),
+                "no" => string(&self.glyphs.no),
+            }),
+            "tick_rate_ms" => int(self.tick_rate_ms as usize),
+            "max_column_width" => int(self.max_column_width),
+            "pinned_columns" => int(self.pinned_columns),
+            "inline_nested_preview" => bool(self.inline_nested_preview),
+            "preview_first_item" => bool(self.preview_first_item),
+            "start_path" => self.start_path.as_ref().map_or_else(nothing, |path| {
+                Value::list(path.iter().map(string).collect(), Span::unknown())
+            }),
+            "return_on_quit" => string(return_on_quit),
+            "edit_format" => string(edit_format),
+            "peek_nuon_pretty" => bool(self.peek_nuon_pretty),
+            "peek_view_is_parent" => bool(self.peek_view_is_parent),
+            "show_borders" => bool(self.show_borders),
+            "border_type" => string(border_type),
+            "data_alignment" => string(data_alignment),
+            "auto_align_numbers" => bool(self.auto_align_numbers),
+            "show_frame_title" => bool(self.show_frame_title),
+            "layout" => string(layout),
+            "margin" => int(self.margin),
+            "scroll_amount" => self.scroll_amount.map_or_else(nothing, int),
+            "number" => bool(self.number),
+            "relativenumber" => bool(self.relativenumber),
+            "colors" => rec(record! {
+                "enabled" => bool(colors.enabled),
+                "normal" => rec(record! {
+                    "name" => fg_bg_colors(&colors.normal.name),
+                    "data" => fg_bg_colors(&colors.normal.data),
+                    "shape" => fg_bg_colors(&colors.normal.shape),
+                }),
+                "selected" => fg_bg_colors(&colors.selected),
+                "selected_modifier" => modifier(&colors.selected_modifier),
+                "selected_symbol" => string(&colors.selected_symbol),
+                "status_bar" => rec(record! {
+                    "normal" => fg_bg_colors(&colors.status_bar.normal),
+                    "insert" => fg_bg_colors(&colors.status_bar.insert),
+                    "peek" => fg_bg_colors(&colors.status_bar.peek),
+                    "bottom" => fg_bg_colors(&colors.status_bar.bottom),
+                }),
+                "editor" => rec(record! {
+                    "frame" => fg_bg_colors(&colors.editor.frame),
+                    "buffer" => fg_bg_colors(&colors.editor.buffer),
+                }),
+                "warning" => fg_bg_colors(&colors.warning),
+                "line_numbers" => rec(record! {
+                    "normal" => fg_bg_colors(&colors.line_numbers.normal),
+                    "selected" => fg_bg_colors(&colors.line_numbers.selected),
+                }),
+                "scrollbar" => fg_bg_colors(&colors.scrollbar),
+                "truncation" => fg_bg_colors(&colors.truncation),
+                "match" => fg_bg_colors(&colors.r#match),
+                "diff" => fg_bg_colors(&colors.diff),
+                "types" => rec(
+                    types
+                        .into_iter()
+                        .map(|(ty, c)| (ty.clone(), fg_bg_colors(c)))
+                        .collect()
+                ),
+            }),
+            "keybindings" => rec(record! {
+                "quit" => key(&kmap.quit),
+                "insert" => key(&kmap.insert),
+                "normal" => key(&kmap.normal),
+                "navigation" => rec(record! {
+                    "left" => key(&kmap.navigation.left),
+                    "down" => key(&kmap.navigation.down),
+                    "up" => key(&kmap.navigation.up),
+                    "right" => key(&kmap.navigation.right),
+                    "half_page_down" => key(&kmap.navigation.half_page_down),
+                    "half_page_up" => key(&kmap.navigation.half_page_up),
+                    "goto_bottom" => key(&kmap.navigation.goto_bottom),
+                    "goto_line" => key(&kmap.navigation.goto_line),
+                    "goto_root" => key(&kmap.navigation.goto_root),
+                    "search" => key(&kmap.navigation.search),
+                    "next_match" => key(&kmap.navigation.next_match),
+                    "previous_match" => key(&kmap.navigation.previous_match),
+                    "next_collection" => key(&kmap.navigation.next_collection),
+                    "previous_collection" => key(&kmap.navigation.previous_collection),
+                    "scroll_left" => key(&kmap.navigation.scroll_left),
+                    "scroll_right" => key(&kmap.navigation.scroll_right),
+                }),
+                "goto" => rec(record! {
+                    "prefix" => key(&kmap.goto.prefix),
+                    "top" => key(&kmap.goto.top),
+                    "end" => key(&kmap.goto.end),
+                    "middle" => key(&kmap.goto.middle),
+                }),
+                "scroll" => rec(record! {
+                    "prefix" => key(&kmap.scroll.prefix),
+                    "top" => key(&kmap.scroll.top),
+                    "center" => key(&kmap.scroll.center),
+                    "bottom" => key(&kmap.scroll.bottom),
+                }),
+                "yank" => rec(record! {
+                    "prefix" => key(&kmap.yank.prefix),
+                    "under" => key(&kmap.yank.under),
+                }),
+                "peek" => key(&kmap.peek),
+                "peeking" => rec(record! {
+                    "all" => key(&kmap.peeking.all),
+                    "cell_path" => key(&kmap.peeking.cell_path),
+                    "cell_path_string" => key(&kmap.peeking.cell_path_string),
+                    "under" => key(&kmap.peeking.under),
+                    "view" => key(&kmap.peeking.view),
+                    "yank_schema" => key(&kmap.peeking.yank_schema),
+                    "clipboard" => key(&kmap.peeking.clipboard),
+                    "write" => key(&kmap.peeking.write),
+                    "nuon" => key(&kmap.peeking.nuon),
+                    "type" => key(&kmap.peeking.ty),
+                    "markdown" => key(&kmap.peeking.markdown),
+                    "trail" => key(&kmap.peeking.trail),
+                    "path" => key(&kmap.peeking.path),
+                }),
+                "transpose" => key(&kmap.transpose),
+                "keys" => key(&kmap.keys),
+                "filter_shape" => key(&kmap.filter_shape),
+                "filter" => key(&kmap.filter),
+                "clear_filter" => key(&kmap.clear_filter),
+                "undo" => key(&kmap.undo),
+                "redo" => key(&kmap.redo),
+                "reload" => key(&kmap.reload),
+                "visual" => key(&kmap.visual),
+                "sort_ascending" => key(&kmap.sort_ascending),
+                "sort_descending" => key(&kmap.sort_descending),
+                "move_down" => key(&kmap.move_down),
+                "move_up" => key(&kmap.move_up),
+                "delete" => key(&kmap.delete),
+                "parse" => key(&kmap.parse),
+                "toggle_wrap" => key(&kmap.toggle_wrap),
+                "toggle_layout" => key(&kmap.toggle_layout),
+                "toggle_sort_keys" => key(&kmap.toggle_sort_keys),
+                "toggle_cell_path" => key(&kmap.toggle_cell_path),
+                "expand" => key(&kmap.expand),
+                "command" => key(&kmap.command),
+                "help" => key(&kmap.help),
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{
+        style::{Color, Modifier},
+        widgets::BorderType,
+    };
+
+    use crate::config::{BgFgColorConfig, Config, DataAlignment, EditFormat, Layout, ReturnOnQuit};
+
+    #[test]
+    fn round_trip() {
+        let config = Config::default();
+        assert_eq!(Config::from_value(&config.to_value()), Ok(config));
+
+        let mut config = Config {
+            layout: Layout::Compact,
+            scroll_amount: Some(5),
+            date_format: Some("%Y-%m-%d".into()),
+            start_path: Some(vec!["foo".into(), "0".into()]),
+            border_type: BorderType::Rounded,
+            data_alignment: DataAlignment::Center,
+            auto_align_numbers: true,
+            edit_format: EditFormat::Json,
+            return_on_quit: ReturnOnQuit::MutatedValue,
+            bool_glyphs: true,
+            number: true,
+            ..Config::default()
+        };
+        config.colors.selected.background = Color::Rgb(0x12, 0xab, 0xff);
+        config.colors.selected_modifier = Modifier::BOLD | Modifier::UNDERLINED;
+        config.colors.types.insert(
+            "int".into(),
+            BgFgColorConfig {
+                background: Color::Reset,
+                foreground: Color::LightCyan,
+            },
+        );
+        config.keybindings.quit = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL);
+        config.keybindings.navigation.up = KeyEvent::new(KeyCode::Up, KeyModifiers::NONE);
+        config.keybindings.normal = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        config.keybindings.help = KeyEvent::new(KeyCode::F(1), KeyModifiers::NONE);
+        config.keybindings.undo = KeyEvent::new(
+            KeyCode::Char('u'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        );
+
+        assert_eq!(Config::from_value(&config.to_value()), Ok(config));
+    }
+}
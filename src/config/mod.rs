@@ -4,18 +4,30 @@
 //! 1. holds the data structure of the [`Config`]
 //! 1. gives default values to a [`Config`] with [`Config::default`]
 //! 1. parses a Nushell [`Value`](https://docs.rs/nu-protocol/0.83.1/nu_protocol/enum.Value.html) into a valid [`Config`]
+//! 1. loads a standalone NUON config file, see [`Config::load`]
+//! 1. restores the state of the UI of the last session, see [`Config::persist_ui_state`]
+//! 1. dumps a [`Config`] back into a Nushell value, see [`Config::to_value`]
+use std::{collections::HashMap, path::Path};
+
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use ratatui::style::{Color, Modifier};
+use ratatui::{
+    style::{Color, Modifier},
+    widgets::BorderType,
+};
 
 use nu_protocol::{LabeledError, Span, Value};
 
+mod dump;
 mod parsing;
+mod state;
 use parsing::{
-    follow_cell_path, invalid_field, invalid_type, positive_integer, try_bool, try_fg_bg_colors,
-    try_int, try_key, try_layout, try_modifier, try_string,
+    follow_cell_path, invalid_field, invalid_type, positive_integer, try_bool, try_border_type,
+    try_data_alignment, try_edit_format, try_fg_bg_colors, try_int, try_key, try_layout,
+    try_modifier, try_return_on_quit, try_string,
 };
+pub(crate) use state::UiState;
 
-/// the configuration for the status bar colors in all [`crate::app::Mode`]s
+/// the configuration for the status bar colors in all modes
 #[derive(Clone, PartialEq, Debug)]
 pub struct StatusBarColorConfig {
     pub normal: BgFgColorConfig,
@@ -58,7 +70,7 @@ pub struct ColorConfig {
     pub normal: TableRowColorConfig,
     /// the color when a row is selected
     pub selected: BgFgColorConfig,
-    /// the modifier to apply to the row under the cursor
+    /// the modifiers to apply to the row under the cursor, e.g. bold and underlined
     pub selected_modifier: Modifier,
     /// the symbol to show to the left of the selected row under the cursor
     pub selected_symbol: String,
@@ -69,6 +81,18 @@ pub struct ColorConfig {
     pub warning: BgFgColorConfig,
     /// the color of the line numbers
     pub line_numbers: LineNumbersColorConfig,
+    /// the color of the scrollbar
+    pub scrollbar: BgFgColorConfig,
+    /// the color of the symbol at the end of truncated cells, see [`Config::truncation_symbol`]
+    pub truncation: BgFgColorConfig,
+    /// the color of the parts of the rows matching the search query, if any
+    pub r#match: BgFgColorConfig,
+    /// the color of the keys that differ between two records, see [`Config::diff`]
+    pub diff: BgFgColorConfig,
+    /// the colors of the data of some types, e.g. `int`, instead of [`TableRowColorConfig::data`]
+    pub types: HashMap<String, BgFgColorConfig>,
+    /// use the colors above, otherwise everything is plain, e.g. when `$env.NO_COLOR` is set
+    pub enabled: bool,
 }
 
 /// a pair of background / foreground colors
@@ -78,7 +102,7 @@ pub struct BgFgColorConfig {
     pub foreground: Color,
 }
 
-/// the bindings in NORMAL mode (see [crate::app::Mode::Normal])
+/// the bindings in NORMAL mode (see `Mode::Normal`)
 #[derive(Clone, PartialEq, Debug)]
 pub struct NavigationBindingsMap {
     /// go one row up in the data
@@ -93,40 +117,318 @@ pub struct NavigationBindingsMap {
     pub half_page_up: KeyEvent,
     /// go one half page down in the data
     pub half_page_down: KeyEvent,
-    /// go to the top of the data, i.e. the first element or the first key
-    pub goto_top: KeyEvent,
     /// go to the bottom of the data, i.e. the last element or the last key
     pub goto_bottom: KeyEvent,
     /// go at a particular line in the data
+    ///
+    /// > **Note**  
+    /// > only after a number has been typed, i.e. in WAITING mode, e.g. `5G` goes to line 5
+    /// > whereas `G` alone is [`NavigationBindingsMap::goto_bottom`] in NORMAL mode
     pub goto_line: KeyEvent,
+    /// go back to the top level of the data, where the current path has been entered from
+    pub goto_root: KeyEvent,
+    /// start typing a query to search for in the current level
+    pub search: KeyEvent,
+    /// go to the next row matching the search query
+    pub next_match: KeyEvent,
+    /// go to the previous row matching the search query
+    pub previous_match: KeyEvent,
+    /// go to the next row holding a list or a record, skipping the other values
+    pub next_collection: KeyEvent,
+    /// go to the previous row holding a list or a record, skipping the other values
+    pub previous_collection: KeyEvent,
+    /// scroll the columns of a table to the left, see [`Config::pinned_columns`]
+    pub scroll_left: KeyEvent,
+    /// scroll the columns of a table to the right, see [`Config::pinned_columns`]
+    pub scroll_right: KeyEvent,
+}
+
+/// the motions starting with a common prefix, e.g. `gg` to go to the top (see `Mode::Goto`)
+#[derive(Clone, PartialEq, Debug)]
+pub struct GotoBindingsMap {
+    /// wait for one of the motions below
+    pub prefix: KeyEvent,
+    /// go to the top of the data, i.e. the first element or the first key
+    pub top: KeyEvent,
+    /// go to the end of the data, i.e. the last element or the last key
+    pub end: KeyEvent,
+    /// go to the row in the middle of the frame
+    pub middle: KeyEvent,
+}
+
+/// the commands to scroll the view around the cursor, e.g. `zz` to center it (see `Mode::Scroll`)
+#[derive(Clone, PartialEq, Debug)]
+pub struct ScrollBindingsMap {
+    /// wait for one of the commands below
+    pub prefix: KeyEvent,
+    /// scroll the view so that the row under the cursor is at the top of the frame
+    pub top: KeyEvent,
+    /// scroll the view so that the row under the cursor is in the middle of the frame
+    pub center: KeyEvent,
+    /// scroll the view so that the row under the cursor is at the bottom of the frame
+    pub bottom: KeyEvent,
+}
+
+/// the commands to grab a value and quit in one go, e.g. `yy` for the value under the cursor (see
+/// `Mode::Yank`)
+#[derive(Clone, PartialEq, Debug)]
+pub struct YankBindingsMap {
+    /// wait for one of the commands below
+    pub prefix: KeyEvent,
+    /// give back the value under the cursor and quit, like [`PeekingBindingsMap::under`]
+    pub under: KeyEvent,
 }
 
-/// the bindings in PEEKING mode (see [crate::app::Mode::Peeking])
+/// the bindings in PEEKING mode (see `Mode::Peeking`)
 #[derive(Clone, PartialEq, Debug)]
 pub struct PeekingBindingsMap {
     /// peek the whole data structure
     pub all: KeyEvent,
     /// peek the current cell path
     pub cell_path: KeyEvent,
+    /// peek the current cell path as a string, e.g. `$.foo.2.bar`
+    pub cell_path_string: KeyEvent,
     /// peek the current level, but only the row under the cursor
     pub under: KeyEvent,
     /// peek the current view
     pub view: KeyEvent,
+    /// copy the schema of the value under the cursor to the system clipboard, i.e. its columns and
+    /// types, as NUON
+    pub yank_schema: KeyEvent,
+    /// copy the value under the cursor to the system clipboard, as NUON
+    pub clipboard: KeyEvent,
+    /// write the value under the cursor to a file, as NUON
+    pub write: KeyEvent,
+    /// peek the value under the cursor as a NUON string
+    pub nuon: KeyEvent,
+    /// peek the type of the value under the cursor, e.g. `"int"`, bound to `type` in the config
+    pub ty: KeyEvent,
+    /// peek the current table as a Markdown string
+    pub markdown: KeyEvent,
+    /// peek all the values from the root down to the one under the cursor, as a list
+    pub trail: KeyEvent,
+    /// peek the metadata of the path under the cursor, see [`crate::nu::strings::stat_path`]
+    pub path: KeyEvent,
 }
 
 /// the keybindings mapping
 #[derive(Clone, PartialEq, Debug)]
 pub struct KeyBindingsMap {
     pub quit: KeyEvent,
-    /// go into INSERT mode (see [crate::app::Mode::Insert])
+    /// go into INSERT mode (see `Mode::Insert`)
     pub insert: KeyEvent,
-    /// go back into NORMAL mode (see [crate::app::Mode::Normal])
+    /// go back into NORMAL mode (see `Mode::Normal`)
     pub normal: KeyEvent,
     pub navigation: NavigationBindingsMap,
-    /// go into PEEKING mode (see [crate::app::Mode::Peeking])
+    pub goto: GotoBindingsMap,
+    pub scroll: ScrollBindingsMap,
+    pub yank: YankBindingsMap,
+    /// go into PEEKING mode (see `Mode::Peeking`)
     pub peek: KeyEvent,
     pub peeking: PeekingBindingsMap,
     pub transpose: KeyEvent,
+    /// explore the keys of the current record as a list, without changing the data
+    pub keys: KeyEvent,
+    /// only show the items of the current list with the same shape as the item under the cursor
+    pub filter_shape: KeyEvent,
+    /// only show the items of the current list matching a query typed in FILTERING mode
+    pub filter: KeyEvent,
+    /// remove the filter on the current list
+    pub clear_filter: KeyEvent,
+    /// undo the last change to the data
+    pub undo: KeyEvent,
+    /// redo the last change that has been undone
+    pub redo: KeyEvent,
+    /// go back to the original data, at the root, forgetting all the changes
+    pub reload: KeyEvent,
+    /// go into VISUAL mode (see `Mode::Visual`)
+    pub visual: KeyEvent,
+    /// sort the current level in ascending order
+    pub sort_ascending: KeyEvent,
+    /// sort the current level in descending order
+    pub sort_descending: KeyEvent,
+    /// move the row under the cursor down in its list or record, the cursor following it
+    pub move_down: KeyEvent,
+    /// move the row under the cursor up in its list or record, the cursor following it
+    pub move_up: KeyEvent,
+    /// delete the row under the cursor from its list or record
+    pub delete: KeyEvent,
+    /// replace the JSON or NUON string under the cursor with the data it holds
+    pub parse: KeyEvent,
+    /// toggle the wrapping of the value at the bottom of the data
+    pub toggle_wrap: KeyEvent,
+    /// switch the layout between [`Layout::Table`] and [`Layout::Compact`]
+    pub toggle_layout: KeyEvent,
+    /// toggle [`Config::view_sort_keys`]
+    pub toggle_sort_keys: KeyEvent,
+    /// show or hide the cell path above the status bar, see [`Config::show_cell_path`]
+    pub toggle_cell_path: KeyEvent,
+    /// show the rows of the list or record under the cursor below it, or hide them again
+    pub expand: KeyEvent,
+    /// go to COMMAND mode to type a command, e.g. `:q`
+    pub command: KeyEvent,
+    /// show the help, i.e. all the bindings (see `Mode::Help`)
+    pub help: KeyEvent,
+}
+
+impl KeyBindingsMap {
+    /// tell whether the *key* is bound to anything, in any mode
+    ///
+    /// > :bulb: **Note**  
+    /// > all the fields are destructured, so that a new binding can't be forgotten here
+    pub(crate) fn is_bound(&self, key: &KeyEvent) -> bool {
+        let Self {
+            quit,
+            insert,
+            normal,
+            navigation:
+                NavigationBindingsMap {
+                    up,
+                    down,
+                    left,
+                    right,
+                    half_page_up,
+                    half_page_down,
+                    goto_bottom,
+                    goto_line,
+                    goto_root,
+                    search,
+                    next_match,
+                    previous_match,
+                    next_collection,
+                    previous_collection,
+                    scroll_left,
+                    scroll_right,
+                },
+            goto:
+                GotoBindingsMap {
+                    prefix,
+                    top,
+                    end,
+                    middle,
+                },
+            scroll:
+                ScrollBindingsMap {
+                    prefix: scroll_prefix,
+                    top: scroll_top,
+                    center,
+                    bottom,
+                },
+            yank:
+                YankBindingsMap {
+                    prefix: yank_prefix,
+                    under: yank_under,
+                },
+            peek,
+            peeking:
+                PeekingBindingsMap {
+                    all,
+                    cell_path,
+                    cell_path_string,
+                    under,
+                    view,
+                    yank_schema,
+                    clipboard,
+                    write,
+                    nuon,
+                    ty,
+                    markdown,
+                    trail,
+                    path,
+                },
+            transpose,
+            keys,
+            filter_shape,
+            filter,
+            clear_filter,
+            undo,
+            redo,
+            reload,
+            visual,
+            sort_ascending,
+            sort_descending,
+            move_down,
+            move_up,
+            delete,
+            parse,
+            toggle_wrap,
+            toggle_layout,
+            toggle_sort_keys,
+            toggle_cell_path,
+            expand,
+            command,
+            help,
+        } = self;
+
+        [
+            quit,
+            insert,
+            normal,
+            up,
+            down,
+            left,
+            right,
+            half_page_up,
+            half_page_down,
+            goto_bottom,
+            goto_line,
+            goto_root,
+            search,
+            next_match,
+            previous_match,
+            next_collection,
+            previous_collection,
+            scroll_left,
+            scroll_right,
+            prefix,
+            top,
+            end,
+            middle,
+            scroll_prefix,
+            scroll_top,
+            center,
+            bottom,
+            yank_prefix,
+            yank_under,
+            peek,
+            all,
+            cell_path,
+            cell_path_string,
+            under,
+            view,
+            yank_schema,
+            clipboard,
+            write,
+            nuon,
+            ty,
+            markdown,
+            trail,
+            path,
+            transpose,
+            keys,
+            filter_shape,
+            filter,
+            clear_filter,
+            undo,
+            redo,
+            reload,
+            visual,
+            sort_ascending,
+            sort_descending,
+            move_down,
+            move_up,
+            delete,
+            parse,
+            toggle_wrap,
+            toggle_layout,
+            toggle_sort_keys,
+            toggle_cell_path,
+            expand,
+            command,
+            help,
+        ]
+        .contains(&key)
+    }
 }
 
 /// the layout of the application
@@ -138,6 +440,73 @@ pub enum Layout {
     Compact,
 }
 
+/// the alignment of the data of the rows, e.g. to right-align numbers
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum DataAlignment {
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+/// the format of the values in the editor
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum EditFormat {
+    /// edit values as NUON, which keeps all the Nushell types
+    #[default]
+    Nuon,
+    /// edit values as JSON, where file sizes, durations and dates lose their type
+    Json,
+    /// edit values as YAML, which loses the same types as JSON
+    Yaml,
+}
+
+/// what to give back when quitting the application
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum ReturnOnQuit {
+    /// return `null`, whatever changes have been made to the data
+    #[default]
+    Nothing,
+    /// return the data, along with the changes that have been made to it
+    MutatedValue,
+}
+
+/// the names of the types that can be given their own colors in `colors.types`
+pub const DATA_TYPES: &[&str] = &[
+    "binary",
+    "bool",
+    "cell-path",
+    "closure",
+    "date",
+    "duration",
+    "error",
+    "filesize",
+    "float",
+    "glob",
+    "int",
+    "list",
+    "nothing",
+    "range",
+    "record",
+    "string",
+];
+
+/// the environment variable that can hold the path to a standalone NUON config file
+pub const CONFIG_FILE_ENV_VAR: &str = "NU_PLUGIN_EXPLORE_CONFIG";
+
+/// the environment variable that disables the colors when set to a non-empty value, see
+/// <https://no-color.org>
+pub const NO_COLOR_ENV_VAR: &str = "NO_COLOR";
+
+/// the glyphs of the booleans, see [`Config::bool_glyphs`]
+#[derive(Clone, PartialEq, Debug)]
+pub struct BoolGlyphsConfig {
+    /// the glyph of `true`
+    pub yes: String,
+    /// the glyph of `false`
+    pub no: String,
+}
+
 /// the configuration of the whole application
 #[derive(Clone, PartialEq, Debug)]
 pub struct Config {
@@ -147,9 +516,99 @@ pub struct Config {
     pub layout: Layout,
     pub show_table_header: bool,
     pub margin: usize,
+    /// the number of rows to jump with `half_page_up` and `half_page_down`, half the height of
+    /// the frame if not set
+    pub scroll_amount: Option<usize>,
     pub number: bool,
     pub relativenumber: bool,
     pub show_hints: bool,
+    /// show a summary of the shapes of the current level next to the cell path
+    pub show_shape_summary: bool,
+    /// show an estimate of the size in memory of the value under the cursor in the status bar
+    pub show_size: bool,
+    /// print the number of cells that have been changed when leaving
+    pub announce_changes: bool,
+    /// remember the layout and the line numbers, as changed with commands, for the next session
+    ///
+    /// they come between the defaults and the actual config, which still has the final word
+    pub persist_ui_state: bool,
+    /// scroll through the data with the mouse wheel
+    pub mouse: bool,
+    /// the number of rows to scroll for each tick of the mouse wheel
+    pub mouse_scroll: usize,
+    /// the maximum number of characters shown in the preview of a cell
+    pub preview_max_length: usize,
+    /// the symbol at the end of the cells that have been truncated, either because of
+    /// [`Config::preview_max_length`] or [`Config::max_column_width`]
+    pub truncation_symbol: String,
+    /// the maximum number of changes that can be undone
+    pub undo_depth: usize,
+    /// only show lists of records as tables when all their rows have the same columns
+    pub strict_tables: bool,
+    /// allow the data to be changed, e.g. by editing cells, transposing or sorting
+    pub editable: bool,
+    /// compare the two records of a list such as `[$a, $b]` side by side at the top level, the
+    /// keys that differ being in `colors.diff`
+    pub diff: bool,
+    /// ask for a confirmation before applying an edit that changes the type of a cell, e.g. `123`
+    /// into `"123"`
+    pub confirm_type_change: bool,
+    /// peek the value under the cursor with `<enter>` in NORMAL mode, unless bound to something else
+    pub enter_peeks: bool,
+    /// the `strftime` format of the dates, RFC3339 if not set
+    pub date_format: Option<String>,
+    /// wrap the simple value shown at the bottom of the data, otherwise it is clipped
+    pub wrap_bottom_value: bool,
+    /// go from the last item of a list or a record to the first one when going down, and the
+    /// other way around when going up, otherwise the cursor stops at both ends
+    pub wrap_navigation: bool,
+    /// show the keys of the records sorted alphabetically, without changing the data, see
+    /// `keybindings.toggle_sort_keys`
+    pub view_sort_keys: bool,
+    /// show a scrollbar to the right of the data, when not all rows fit on screen
+    pub show_scrollbar: bool,
+    /// the representation of `null` values, to tell them apart from empty strings
+    pub null_placeholder: String,
+    /// show empty strings as `""`, to tell them apart from `null` values
+    pub show_empty_string_quotes: bool,
+    /// show the booleans as [`Config::glyphs`] instead of `true` and `false`, e.g. in tables of
+    /// flags
+    pub bool_glyphs: bool,
+    /// the glyphs of the booleans, when [`Config::bool_glyphs`] is set
+    pub glyphs: BoolGlyphsConfig,
+    /// the time between two ticks of the application, in milliseconds
+    pub tick_rate_ms: u64,
+    /// peek NUON strings with indentation, otherwise they fit on a single line
+    pub peek_nuon_pretty: bool,
+    /// peek the level the cursor is in with `keybindings.peeking.view`, otherwise the view of
+    /// the value under the cursor, i.e. without the items hidden by a filter
+    pub peek_view_is_parent: bool,
+    /// the maximum width of the columns of a table, which otherwise fit their widest cell
+    pub max_column_width: usize,
+    /// the number of columns of a table that stay on the left when scrolling through the other
+    /// ones with `keybindings.navigation.scroll_left` and
+    /// `keybindings.navigation.scroll_right`, e.g. to keep a `name` column in sight
+    pub pinned_columns: usize,
+    /// the cell path to start at, e.g. `["config", "plugins"]`, integers being list indices
+    pub start_path: Option<Vec<String>>,
+    /// preview nested lists and records inline, e.g. `{a: 1, b: 2}` instead of `{2 fields}`
+    pub inline_nested_preview: bool,
+    /// preview the first item of nested lists and records, e.g. `[2 items: a, …]`
+    pub preview_first_item: bool,
+    /// draw borders around the data, except in the compact layout which has none
+    pub show_borders: bool,
+    /// the type of the borders around the data, e.g. rounded
+    pub border_type: BorderType,
+    /// the alignment of the data in its column, or of the whole rows in the compact layout
+    pub data_alignment: DataAlignment,
+    /// right-align the numbers, e.g. ints and file sizes, whatever [`Config::data_alignment`] is
+    pub auto_align_numbers: bool,
+    /// show the cell path of the current level as the title of the data, on its top border
+    pub show_frame_title: bool,
+    /// the format of the values in the editor
+    pub edit_format: EditFormat,
+    /// what to give back when quitting, e.g. to use `explore` to edit data in a pipeline
+    pub return_on_quit: ReturnOnQuit,
 }
 
 impl Default for Config {
@@ -161,9 +620,51 @@ impl Default for Config {
             show_table_header: true,
             layout: Layout::Table,
             margin: 10,
+            scroll_amount: None,
             number: false,
             relativenumber: false,
             show_hints: true,
+            show_shape_summary: false,
+            show_size: false,
+            announce_changes: false,
+            persist_ui_state: false,
+            mouse: true,
+            mouse_scroll: 3,
+            preview_max_length: 64,
+            truncation_symbol: "…".into(),
+            undo_depth: 50,
+            strict_tables: true,
+            editable: true,
+            diff: false,
+            confirm_type_change: false,
+            enter_peeks: false,
+            date_format: None,
+            wrap_bottom_value: true,
+            wrap_navigation: false,
+            view_sort_keys: false,
+            show_scrollbar: true,
+            null_placeholder: "∅".into(),
+            show_empty_string_quotes: true,
+            bool_glyphs: false,
+            glyphs: BoolGlyphsConfig {
+                yes: "✓".into(),
+                no: "✗".into(),
+            },
+            tick_rate_ms: 250,
+            peek_nuon_pretty: false,
+            peek_view_is_parent: true,
+            max_column_width: 40,
+            pinned_columns: 0,
+            start_path: None,
+            inline_nested_preview: false,
+            preview_first_item: false,
+            show_borders: true,
+            border_type: BorderType::Plain,
+            data_alignment: DataAlignment::Left,
+            auto_align_numbers: false,
+            show_frame_title: false,
+            edit_format: EditFormat::Nuon,
+            return_on_quit: ReturnOnQuit::Nothing,
             colors: ColorConfig {
                 normal: TableRowColorConfig {
                     name: BgFgColorConfig {
@@ -227,6 +728,24 @@ impl Default for Config {
                         foreground: Color::Black,
                     },
                 },
+                scrollbar: BgFgColorConfig {
+                    background: Color::Reset,
+                    foreground: Color::DarkGray,
+                },
+                truncation: BgFgColorConfig {
+                    background: Color::Reset,
+                    foreground: Color::Yellow,
+                },
+                r#match: BgFgColorConfig {
+                    background: Color::Yellow,
+                    foreground: Color::Black,
+                },
+                diff: BgFgColorConfig {
+                    background: Color::Reset,
+                    foreground: Color::Red,
+                },
+                types: HashMap::new(),
+                enabled: true,
             },
             keybindings: KeyBindingsMap {
                 quit: KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE),
@@ -239,18 +758,71 @@ impl Default for Config {
                     right: KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE),
                     half_page_down: KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL),
                     half_page_up: KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL),
-                    goto_top: KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE),
                     goto_bottom: KeyEvent::new(KeyCode::Char('G'), KeyModifiers::NONE),
-                    goto_line: KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE),
+                    goto_line: KeyEvent::new(KeyCode::Char('G'), KeyModifiers::NONE),
+                    goto_root: KeyEvent::new(KeyCode::Char('H'), KeyModifiers::NONE),
+                    search: KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE),
+                    next_match: KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE),
+                    previous_match: KeyEvent::new(KeyCode::Char('N'), KeyModifiers::NONE),
+                    next_collection: KeyEvent::new(KeyCode::Char('}'), KeyModifiers::NONE),
+                    previous_collection: KeyEvent::new(KeyCode::Char('{'), KeyModifiers::NONE),
+                    scroll_left: KeyEvent::new(KeyCode::Char('<'), KeyModifiers::NONE),
+                    scroll_right: KeyEvent::new(KeyCode::Char('>'), KeyModifiers::NONE),
+                },
+                goto: GotoBindingsMap {
+                    prefix: KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE),
+                    top: KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE),
+                    end: KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE),
+                    middle: KeyEvent::new(KeyCode::Char('m'), KeyModifiers::NONE),
+                },
+                scroll: ScrollBindingsMap {
+                    prefix: KeyEvent::new(KeyCode::Char('z'), KeyModifiers::NONE),
+                    top: KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE),
+                    center: KeyEvent::new(KeyCode::Char('z'), KeyModifiers::NONE),
+                    bottom: KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE),
+                },
+                yank: YankBindingsMap {
+                    prefix: KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE),
+                    under: KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE),
                 },
                 peek: KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE),
                 peeking: PeekingBindingsMap {
                     all: KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE),
                     cell_path: KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE),
+                    cell_path_string: KeyEvent::new(KeyCode::Char('C'), KeyModifiers::NONE),
                     under: KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE),
                     view: KeyEvent::new(KeyCode::Char('v'), KeyModifiers::NONE),
+                    yank_schema: KeyEvent::new(KeyCode::Char('s'), KeyModifiers::NONE),
+                    clipboard: KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE),
+                    write: KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE),
+                    nuon: KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE),
+                    ty: KeyEvent::new(KeyCode::Char('T'), KeyModifiers::NONE),
+                    markdown: KeyEvent::new(KeyCode::Char('m'), KeyModifiers::NONE),
+                    trail: KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE),
+                    path: KeyEvent::new(KeyCode::Char('f'), KeyModifiers::NONE),
                 },
                 transpose: KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE),
+                keys: KeyEvent::new(KeyCode::Char('K'), KeyModifiers::NONE),
+                filter_shape: KeyEvent::new(KeyCode::Char('='), KeyModifiers::NONE),
+                filter: KeyEvent::new(KeyCode::Char('f'), KeyModifiers::NONE),
+                clear_filter: KeyEvent::new(KeyCode::Char('F'), KeyModifiers::NONE),
+                undo: KeyEvent::new(KeyCode::Char('u'), KeyModifiers::NONE),
+                redo: KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL),
+                reload: KeyEvent::new(KeyCode::Char('R'), KeyModifiers::NONE),
+                visual: KeyEvent::new(KeyCode::Char('V'), KeyModifiers::NONE),
+                sort_ascending: KeyEvent::new(KeyCode::Char('s'), KeyModifiers::NONE),
+                sort_descending: KeyEvent::new(KeyCode::Char('S'), KeyModifiers::NONE),
+                move_down: KeyEvent::new(KeyCode::Char('j'), KeyModifiers::CONTROL),
+                move_up: KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL),
+                delete: KeyEvent::new(KeyCode::Char('D'), KeyModifiers::NONE),
+                parse: KeyEvent::new(KeyCode::Char('P'), KeyModifiers::NONE),
+                toggle_wrap: KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE),
+                toggle_layout: KeyEvent::new(KeyCode::Char('L'), KeyModifiers::NONE),
+                toggle_sort_keys: KeyEvent::new(KeyCode::Char('O'), KeyModifiers::NONE),
+                toggle_cell_path: KeyEvent::new(KeyCode::Char('C'), KeyModifiers::NONE),
+                expand: KeyEvent::new(KeyCode::Char('o'), KeyModifiers::NONE),
+                command: KeyEvent::new(KeyCode::Char(':'), KeyModifiers::NONE),
+                help: KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE),
             },
         }
     }
@@ -262,7 +834,68 @@ impl Config {
     // `follow_cell_path(&value, &["colors", "line_numbers"])` is only found in the "colors" and
     // "line_numbers" branch of the parsing.
     pub fn from_value(value: &Value) -> Result<Self, LabeledError> {
-        let mut config = Config::default();
+        Config::from_layers(&[value])
+    }
+
+    /// load the config from the NUON file at `$env.NU_PLUGIN_EXPLORE_CONFIG`, if any, and then
+    /// from the *value*, which has priority over the file
+    ///
+    /// > **Note**  
+    /// > a file that cannot be read is ignored but a malformed one gives an error
+    pub fn load(value: &Value) -> Result<Self, LabeledError> {
+        let mut config = match std::env::var_os(CONFIG_FILE_ENV_VAR) {
+            Some(path) => Config::from_file_and_value(Path::new(&path), value)?,
+            None => Config::from_value(value)?,
+        };
+        if std::env::var_os(NO_COLOR_ENV_VAR).is_some_and(|val| !val.is_empty()) {
+            config.colors.enabled = false;
+        }
+
+        Ok(config)
+    }
+
+    /// same as [`Config::load`] but with an explicit *path* to the config file
+    pub fn from_file_and_value(path: &Path, value: &Value) -> Result<Self, LabeledError> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => {
+                let file_value = nuon::from_nuon(&contents, None).map_err(|err| {
+                    LabeledError::new("invalid config file").with_label(
+                        format!("`{}` is not valid NUON: {}", path.display(), err),
+                        Span::unknown(),
+                    )
+                })?;
+                Config::from_layers(&[&file_value, value])
+            }
+            Err(_) => Config::from_layers(&[value]),
+        }
+    }
+
+    /// merge the *layers* on top of the defaults, the last one having the final word
+    ///
+    /// > **Note**  
+    /// > when [`Config::persist_ui_state`] is set, the [`UiState`] of the last session, if any,
+    /// > goes between the defaults and the layers
+    fn from_layers(layers: &[&Value]) -> Result<Self, LabeledError> {
+        let merge = |base: Config| {
+            layers
+                .iter()
+                .try_fold(base, |config, layer| config.merge_value(layer))
+        };
+
+        let config = merge(Config::default())?;
+        if !config.persist_ui_state {
+            return Ok(config);
+        }
+
+        match UiState::load() {
+            Some(state) => merge(state.apply(Config::default())),
+            None => Ok(config),
+        }
+    }
+
+    /// update the config with the fields of the *value*, the other fields being left untouched
+    fn merge_value(self, value: &Value) -> Result<Self, LabeledError> {
+        let mut config = self;
 
         for column in value.columns() {
             match column.as_str() {
@@ -281,6 +914,26 @@ impl Config {
                         config.layout = val
                     }
                 }
+                "data_alignment" => {
+                    if let Some(val) = try_data_alignment(value, &["data_alignment"])? {
+                        config.data_alignment = val
+                    }
+                }
+                "auto_align_numbers" => {
+                    if let Some(val) = try_bool(value, &["auto_align_numbers"])? {
+                        config.auto_align_numbers = val
+                    }
+                }
+                "edit_format" => {
+                    if let Some(val) = try_edit_format(value, &["edit_format"])? {
+                        config.edit_format = val
+                    }
+                }
+                "return_on_quit" => {
+                    if let Some(val) = try_return_on_quit(value, &["return_on_quit"])? {
+                        config.return_on_quit = val
+                    }
+                }
                 "margin" => {
                     if let Some(val) = try_int(value, &["margin"])? {
                         if val < 0 {
@@ -289,6 +942,17 @@ impl Config {
                         config.margin = val as usize
                     }
                 }
+                "scroll_amount" => match follow_cell_path(value, &["scroll_amount"]) {
+                    Some(Value::Nothing { .. }) => config.scroll_amount = None,
+                    Some(Value::Int { val, .. }) if val > 0 => {
+                        config.scroll_amount = Some(val as usize)
+                    }
+                    Some(Value::Int { val, .. }) => {
+                        return Err(positive_integer(val, &["scroll_amount"], Span::unknown()))
+                    }
+                    Some(x) => return Err(invalid_type(&x, &["scroll_amount"], "int")),
+                    None => {}
+                },
                 "number" => {
                     if let Some(val) = try_bool(value, &["number"])? {
                         config.number = val
@@ -304,6 +968,238 @@ impl Config {
                         config.show_hints = val
                     }
                 }
+                "show_shape_summary" => {
+                    if let Some(val) = try_bool(value, &["show_shape_summary"])? {
+                        config.show_shape_summary = val
+                    }
+                }
+                "show_size" => {
+                    if let Some(val) = try_bool(value, &["show_size"])? {
+                        config.show_size = val
+                    }
+                }
+                "announce_changes" => {
+                    if let Some(val) = try_bool(value, &["announce_changes"])? {
+                        config.announce_changes = val
+                    }
+                }
+                "persist_ui_state" => {
+                    if let Some(val) = try_bool(value, &["persist_ui_state"])? {
+                        config.persist_ui_state = val
+                    }
+                }
+                "mouse" => {
+                    if let Some(val) = try_bool(value, &["mouse"])? {
+                        config.mouse = val
+                    }
+                }
+                "mouse_scroll" => {
+                    if let Some(val) = try_int(value, &["mouse_scroll"])? {
+                        if val < 0 {
+                            return Err(positive_integer(val, &["mouse_scroll"], Span::unknown()));
+                        }
+                        config.mouse_scroll = val as usize
+                    }
+                }
+                "inline_nested_preview" => {
+                    if let Some(val) = try_bool(value, &["inline_nested_preview"])? {
+                        config.inline_nested_preview = val
+                    }
+                }
+                "preview_first_item" => {
+                    if let Some(val) = try_bool(value, &["preview_first_item"])? {
+                        config.preview_first_item = val
+                    }
+                }
+                "show_borders" => {
+                    if let Some(val) = try_bool(value, &["show_borders"])? {
+                        config.show_borders = val
+                    }
+                }
+                "show_frame_title" => {
+                    if let Some(val) = try_bool(value, &["show_frame_title"])? {
+                        config.show_frame_title = val
+                    }
+                }
+                "border_type" => {
+                    if let Some(val) = try_border_type(value, &["border_type"])? {
+                        config.border_type = val
+                    }
+                }
+                "peek_nuon_pretty" => {
+                    if let Some(val) = try_bool(value, &["peek_nuon_pretty"])? {
+                        config.peek_nuon_pretty = val
+                    }
+                }
+                "peek_view_is_parent" => {
+                    if let Some(val) = try_bool(value, &["peek_view_is_parent"])? {
+                        config.peek_view_is_parent = val
+                    }
+                }
+                "tick_rate_ms" => {
+                    if let Some(val) = try_int(value, &["tick_rate_ms"])? {
+                        // NOTE: a tick rate of 0 would make the application spin
+                        if val <= 0 {
+                            return Err(positive_integer(val, &["tick_rate_ms"], Span::unknown()));
+                        }
+                        config.tick_rate_ms = val as u64
+                    }
+                }
+                "max_column_width" => {
+                    if let Some(val) = try_int(value, &["max_column_width"])? {
+                        if val <= 0 {
+                            return Err(positive_integer(
+                                val,
+                                &["max_column_width"],
+                                Span::unknown(),
+                            ));
+                        }
+                        config.max_column_width = val as usize
+                    }
+                }
+                "pinned_columns" => {
+                    if let Some(val) = try_int(value, &["pinned_columns"])? {
+                        if val < 0 {
+                            return Err(positive_integer(
+                                val,
+                                &["pinned_columns"],
+                                Span::unknown(),
+                            ));
+                        }
+                        config.pinned_columns = val as usize
+                    }
+                }
+                "preview_max_length" => {
+                    if let Some(val) = try_int(value, &["preview_max_length"])? {
+                        if val < 0 {
+                            return Err(positive_integer(
+                                val,
+                                &["preview_max_length"],
+                                Span::unknown(),
+                            ));
+                        }
+                        config.preview_max_length = val as usize
+                    }
+                }
+                "undo_depth" => {
+                    if let Some(val) = try_int(value, &["undo_depth"])? {
+                        if val < 0 {
+                            return Err(positive_integer(val, &["undo_depth"], Span::unknown()));
+                        }
+                        config.undo_depth = val as usize
+                    }
+                }
+                "strict_tables" => {
+                    if let Some(val) = try_bool(value, &["strict_tables"])? {
+                        config.strict_tables = val
+                    }
+                }
+                "editable" => {
+                    if let Some(val) = try_bool(value, &["editable"])? {
+                        config.editable = val
+                    }
+                }
+                "diff" => {
+                    if let Some(val) = try_bool(value, &["diff"])? {
+                        config.diff = val
+                    }
+                }
+                "confirm_type_change" => {
+                    if let Some(val) = try_bool(value, &["confirm_type_change"])? {
+                        config.confirm_type_change = val
+                    }
+                }
+                "enter_peeks" => {
+                    if let Some(val) = try_bool(value, &["enter_peeks"])? {
+                        config.enter_peeks = val
+                    }
+                }
+                "truncation_symbol" => {
+                    if let Some(val) = try_string(value, &["truncation_symbol"])? {
+                        config.truncation_symbol = val
+                    }
+                }
+                "null_placeholder" => {
+                    if let Some(val) = try_string(value, &["null_placeholder"])? {
+                        config.null_placeholder = val
+                    }
+                }
+                "show_empty_string_quotes" => {
+                    if let Some(val) = try_bool(value, &["show_empty_string_quotes"])? {
+                        config.show_empty_string_quotes = val
+                    }
+                }
+                "bool_glyphs" => {
+                    if let Some(val) = try_bool(value, &["bool_glyphs"])? {
+                        config.bool_glyphs = val
+                    }
+                }
+                "glyphs" => {
+                    let cell = follow_cell_path(value, &["glyphs"]).unwrap();
+                    let columns = match &cell {
+                        Value::Record { val: rec, .. } => rec.columns().collect::<Vec<_>>(),
+                        x => return Err(invalid_type(x, &["glyphs"], "record")),
+                    };
+
+                    for column in columns {
+                        match column.as_str() {
+                            "yes" => {
+                                if let Some(val) = try_string(value, &["glyphs", "yes"])? {
+                                    config.glyphs.yes = val
+                                }
+                            }
+                            "no" => {
+                                if let Some(val) = try_string(value, &["glyphs", "no"])? {
+                                    config.glyphs.no = val
+                                }
+                            }
+                            x => return Err(invalid_field(&["glyphs", x], cell.span())),
+                        }
+                    }
+                }
+                "show_scrollbar" => {
+                    if let Some(val) = try_bool(value, &["show_scrollbar"])? {
+                        config.show_scrollbar = val
+                    }
+                }
+                "wrap_bottom_value" => {
+                    if let Some(val) = try_bool(value, &["wrap_bottom_value"])? {
+                        config.wrap_bottom_value = val
+                    }
+                }
+                "wrap_navigation" => {
+                    if let Some(val) = try_bool(value, &["wrap_navigation"])? {
+                        config.wrap_navigation = val
+                    }
+                }
+                "view_sort_keys" => {
+                    if let Some(val) = try_bool(value, &["view_sort_keys"])? {
+                        config.view_sort_keys = val
+                    }
+                }
+                "date_format" => match follow_cell_path(value, &["date_format"]) {
+                    Some(Value::Nothing { .. }) => config.date_format = None,
+                    _ => {
+                        if let Some(val) = try_string(value, &["date_format"])? {
+                            config.date_format = Some(val)
+                        }
+                    }
+                },
+                "start_path" => match follow_cell_path(value, &["start_path"]) {
+                    Some(Value::Nothing { .. }) => config.start_path = None,
+                    Some(Value::List { vals, .. }) => {
+                        config.start_path = Some(
+                            vals.iter()
+                                .map(|v| match v {
+                                    Value::String { val, .. } => Ok(val.clone()),
+                                    x => Err(invalid_type(x, &["start_path"], "list<string>")),
+                                })
+                                .collect::<Result<Vec<_>, _>>()?,
+                        )
+                    }
+                    Some(x) => return Err(invalid_type(&x, &["start_path"], "list<string>")),
+                    None => {}
+                },
                 "colors" => {
                     let cell = follow_cell_path(value, &["colors"]).unwrap();
                     let columns = match &cell {
@@ -505,6 +1401,75 @@ impl Config {
                                     config.colors.warning = val
                                 }
                             }
+                            "enabled" => {
+                                if let Some(val) = try_bool(value, &["colors", "enabled"])? {
+                                    config.colors.enabled = val
+                                }
+                            }
+                            "scrollbar" => {
+                                if let Some(val) = try_fg_bg_colors(
+                                    value,
+                                    &["colors", "scrollbar"],
+                                    &config.colors.scrollbar,
+                                )? {
+                                    config.colors.scrollbar = val
+                                }
+                            }
+                            "truncation" => {
+                                if let Some(val) = try_fg_bg_colors(
+                                    value,
+                                    &["colors", "truncation"],
+                                    &config.colors.truncation,
+                                )? {
+                                    config.colors.truncation = val
+                                }
+                            }
+                            "match" => {
+                                if let Some(val) = try_fg_bg_colors(
+                                    value,
+                                    &["colors", "match"],
+                                    &config.colors.r#match,
+                                )? {
+                                    config.colors.r#match = val
+                                }
+                            }
+                            "diff" => {
+                                if let Some(val) = try_fg_bg_colors(
+                                    value,
+                                    &["colors", "diff"],
+                                    &config.colors.diff,
+                                )? {
+                                    config.colors.diff = val
+                                }
+                            }
+                            "types" => {
+                                let cell = follow_cell_path(value, &["colors", "types"]).unwrap();
+                                let columns = match &cell {
+                                    Value::Record { val: rec, .. } => {
+                                        rec.columns().collect::<Vec<_>>()
+                                    }
+                                    x => {
+                                        return Err(invalid_type(x, &["colors", "types"], "record"))
+                                    }
+                                };
+
+                                for column in columns {
+                                    if !DATA_TYPES.contains(&column.as_str()) {
+                                        return Err(invalid_field(
+                                            &["colors", "types", column],
+                                            cell.span(),
+                                        ));
+                                    }
+
+                                    if let Some(val) = try_fg_bg_colors(
+                                        value,
+                                        &["colors", "types", column],
+                                        &config.colors.normal.data,
+                                    )? {
+                                        config.colors.types.insert(column.clone(), val);
+                                    }
+                                }
+                            }
                             "line_numbers" => {
                                 let cell =
                                     follow_cell_path(value, &["colors", "line_numbers"]).unwrap();
@@ -644,14 +1609,6 @@ impl Config {
                                                 config.keybindings.navigation.half_page_down = val
                                             }
                                         }
-                                        "goto_top" => {
-                                            if let Some(val) = try_key(
-                                                value,
-                                                &["keybindings", "navigation", "goto_top"],
-                                            )? {
-                                                config.keybindings.navigation.goto_top = val
-                                            }
-                                        }
                                         "goto_bottom" => {
                                             if let Some(val) = try_key(
                                                 value,
@@ -668,6 +1625,86 @@ impl Config {
                                                 config.keybindings.navigation.goto_line = val
                                             }
                                         }
+                                        "goto_root" => {
+                                            if let Some(val) = try_key(
+                                                value,
+                                                &["keybindings", "navigation", "goto_root"],
+                                            )? {
+                                                config.keybindings.navigation.goto_root = val
+                                            }
+                                        }
+                                        "search" => {
+                                            if let Some(val) = try_key(
+                                                value,
+                                                &["keybindings", "navigation", "search"],
+                                            )? {
+                                                config.keybindings.navigation.search = val
+                                            }
+                                        }
+                                        "next_match" => {
+                                            if let Some(val) = try_key(
+                                                value,
+                                                &["keybindings", "navigation", "next_match"],
+                                            )? {
+                                                config.keybindings.navigation.next_match = val
+                                            }
+                                        }
+                                        "previous_match" => {
+                                            if let Some(val) = try_key(
+                                                value,
+                                                &["keybindings", "navigation", "previous_match"],
+                                            )? {
+                                                config.keybindings.navigation.previous_match = val
+                                            }
+                                        }
+                                        "next_collection" => {
+                                            if let Some(val) = try_key(
+                                                value,
+                                                &["keybindings", "navigation", "next_collection"],
+                                            )? {
+                                                config.keybindings.navigation.next_collection = val
+                                            }
+                                        }
+                                        "previous_collection" => {
+                                            if let Some(val) = try_key(
+                                                value,
+                                                &[
+                                                    "keybindings",
+                                                    "navigation",
+                                                    "previous_collection",
+                                                ],
+                                            )? {
+                                                config.keybindings.navigation.previous_collection =
+                                                    val
+                                            }
+                                        }
+                                        "scroll_left" => {
+                                            if let Some(val) = try_key(
+                                                value,
+                                                &["keybindings", "navigation", "scroll_left"],
+                                            )? {
+                                                config.keybindings.navigation.scroll_left = val
+                                            }
+                                        }
+                                        "scroll_right" => {
+                                            if let Some(val) = try_key(
+                                                value,
+                                                &["keybindings", "navigation", "scroll_right"],
+                                            )? {
+                                                config.keybindings.navigation.scroll_right = val
+                                            }
+                                        }
+                                        // NOTE: going to the top is now a motion after a prefix,
+                                        // which an old binding can't be turned into as is
+                                        "goto_top" => {
+                                            return Err(LabeledError::new("invalid config")
+                                                .with_label(
+                                                    "`$.keybindings.navigation.goto_top` has been \
+                                                     replaced by `$.keybindings.goto.top`, which \
+                                                     comes after `$.keybindings.goto.prefix`",
+                                                    cell.span(),
+                                                ));
+                                        }
                                         x => {
                                             return Err(invalid_field(
                                                 &["keybindings", "navigation", x],
@@ -682,9 +1719,9 @@ impl Config {
                                     config.keybindings.peek = val
                                 }
                             }
-                            "peeking" => {
+                            "goto" => {
                                 let cell =
-                                    follow_cell_path(value, &["keybindings", "peeking"]).unwrap();
+                                    follow_cell_path(value, &["keybindings", "goto"]).unwrap();
                                 let columns = match &cell {
                                     Value::Record { val: rec, .. } => {
                                         rec.columns().collect::<Vec<_>>()
@@ -692,7 +1729,7 @@ impl Config {
                                     x => {
                                         return Err(invalid_type(
                                             x,
-                                            &["keybindings", "peeking"],
+                                            &["keybindings", "goto"],
                                             "record",
                                         ))
                                     }
@@ -700,11 +1737,165 @@ impl Config {
 
                                 for column in columns {
                                     match column.as_str() {
-                                        "all" => {
+                                        "prefix" => {
                                             if let Some(val) =
-                                                try_key(value, &["keybindings", "peeking", "all"])?
+                                                try_key(value, &["keybindings", "goto", "prefix"])?
                                             {
-                                                config.keybindings.peeking.all = val
+                                                config.keybindings.goto.prefix = val
+                                            }
+                                        }
+                                        "top" => {
+                                            if let Some(val) =
+                                                try_key(value, &["keybindings", "goto", "top"])?
+                                            {
+                                                config.keybindings.goto.top = val
+                                            }
+                                        }
+                                        "end" => {
+                                            if let Some(val) =
+                                                try_key(value, &["keybindings", "goto", "end"])?
+                                            {
+                                                config.keybindings.goto.end = val
+                                            }
+                                        }
+                                        "middle" => {
+                                            if let Some(val) =
+                                                try_key(value, &["keybindings", "goto", "middle"])?
+                                            {
+                                                config.keybindings.goto.middle = val
+                                            }
+                                        }
+                                        x => {
+                                            return Err(invalid_field(
+                                                &["keybindings", "goto", x],
+                                                cell.span(),
+                                            ));
+                                        }
+                                    }
+                                }
+                            }
+                            "scroll" => {
+                                let cell =
+                                    follow_cell_path(value, &["keybindings", "scroll"]).unwrap();
+                                let columns = match &cell {
+                                    Value::Record { val: rec, .. } => {
+                                        rec.columns().collect::<Vec<_>>()
+                                    }
+                                    x => {
+                                        return Err(invalid_type(
+                                            x,
+                                            &["keybindings", "scroll"],
+                                            "record",
+                                        ))
+                                    }
+                                };
+
+                                for column in columns {
+                                    match column.as_str() {
+                                        "prefix" => {
+                                            if let Some(val) = try_key(
+                                                value,
+                                                &["keybindings", "scroll", "prefix"],
+                                            )? {
+                                                config.keybindings.scroll.prefix = val
+                                            }
+                                        }
+                                        "top" => {
+                                            if let Some(val) =
+                                                try_key(value, &["keybindings", "scroll", "top"])?
+                                            {
+                                                config.keybindings.scroll.top = val
+                                            }
+                                        }
+                                        "center" => {
+                                            if let Some(val) = try_key(
+                                                value,
+                                                &["keybindings", "scroll", "center"],
+                                            )? {
+                                                config.keybindings.scroll.center = val
+                                            }
+                                        }
+                                        "bottom" => {
+                                            if let Some(val) = try_key(
+                                                value,
+                                                &["keybindings", "scroll", "bottom"],
+                                            )? {
+                                                config.keybindings.scroll.bottom = val
+                                            }
+                                        }
+                                        x => {
+                                            return Err(invalid_field(
+                                                &["keybindings", "scroll", x],
+                                                cell.span(),
+                                            ));
+                                        }
+                                    }
+                                }
+                            }
+                            "yank" => {
+                                let cell =
+                                    follow_cell_path(value, &["keybindings", "yank"]).unwrap();
+                                let columns = match &cell {
+                                    Value::Record { val: rec, .. } => {
+                                        rec.columns().collect::<Vec<_>>()
+                                    }
+                                    x => {
+                                        return Err(invalid_type(
+                                            x,
+                                            &["keybindings", "yank"],
+                                            "record",
+                                        ))
+                                    }
+                                };
+
+                                for column in columns {
+                                    match column.as_str() {
+                                        "prefix" => {
+                                            if let Some(val) =
+                                                try_key(value, &["keybindings", "yank", "prefix"])?
+                                            {
+                                                config.keybindings.yank.prefix = val
+                                            }
+                                        }
+                                        "under" => {
+                                            if let Some(val) =
+                                                try_key(value, &["keybindings", "yank", "under"])?
+                                            {
+                                                config.keybindings.yank.under = val
+                                            }
+                                        }
+                                        x => {
+                                            return Err(invalid_field(
+                                                &["keybindings", "yank", x],
+                                                cell.span(),
+                                            ));
+                                        }
+                                    }
+                                }
+                            }
+                            "peeking" => {
+                                let cell =
+                                    follow_cell_path(value, &["keybindings", "peeking"]).unwrap();
+                                let columns = match &cell {
+                                    Value::Record { val: rec, .. } => {
+                                        rec.columns().collect::<Vec<_>>()
+                                    }
+                                    x => {
+                                        return Err(invalid_type(
+                                            x,
+                                            &["keybindings", "peeking"],
+                                            "record",
+                                        ))
+                                    }
+                                };
+
+                                for column in columns {
+                                    match column.as_str() {
+                                        "all" => {
+                                            if let Some(val) =
+                                                try_key(value, &["keybindings", "peeking", "all"])?
+                                            {
+                                                config.keybindings.peeking.all = val
                                             }
                                         }
                                         "cell_path" => {
@@ -730,6 +1921,75 @@ impl Config {
                                                 config.keybindings.peeking.view = val
                                             }
                                         }
+                                        "yank_schema" => {
+                                            if let Some(val) = try_key(
+                                                value,
+                                                &["keybindings", "peeking", "yank_schema"],
+                                            )? {
+                                                config.keybindings.peeking.yank_schema = val
+                                            }
+                                        }
+                                        "cell_path_string" => {
+                                            if let Some(val) = try_key(
+                                                value,
+                                                &["keybindings", "peeking", "cell_path_string"],
+                                            )? {
+                                                config.keybindings.peeking.cell_path_string = val
+                                            }
+                                        }
+                                        "type" => {
+                                            if let Some(val) =
+                                                try_key(value, &["keybindings", "peeking", "type"])?
+                                            {
+                                                config.keybindings.peeking.ty = val
+                                            }
+                                        }
+                                        "markdown" => {
+                                            if let Some(val) = try_key(
+                                                value,
+                                                &["keybindings", "peeking", "markdown"],
+                                            )? {
+                                                config.keybindings.peeking.markdown = val
+                                            }
+                                        }
+                                        "trail" => {
+                                            if let Some(val) = try_key(
+                                                value,
+                                                &["keybindings", "peeking", "trail"],
+                                            )? {
+                                                config.keybindings.peeking.trail = val
+                                            }
+                                        }
+                                        "path" => {
+                                            if let Some(val) =
+                                                try_key(value, &["keybindings", "peeking", "path"])?
+                                            {
+                                                config.keybindings.peeking.path = val
+                                            }
+                                        }
+                                        "clipboard" => {
+                                            if let Some(val) = try_key(
+                                                value,
+                                                &["keybindings", "peeking", "clipboard"],
+                                            )? {
+                                                config.keybindings.peeking.clipboard = val
+                                            }
+                                        }
+                                        "write" => {
+                                            if let Some(val) = try_key(
+                                                value,
+                                                &["keybindings", "peeking", "write"],
+                                            )? {
+                                                config.keybindings.peeking.write = val
+                                            }
+                                        }
+                                        "nuon" => {
+                                            if let Some(val) =
+                                                try_key(value, &["keybindings", "peeking", "nuon"])?
+                                            {
+                                                config.keybindings.peeking.nuon = val
+                                            }
+                                        }
                                         x => {
                                             return Err(invalid_field(
                                                 &["keybindings", "peeking", x],
@@ -740,10 +2000,128 @@ impl Config {
                                 }
                             }
                             "transpose" => {
-                                if let Some(val) = try_key(value, &["keybindings", "tranpose"])? {
+                                if let Some(val) = try_key(value, &["keybindings", "transpose"])? {
                                     config.keybindings.transpose = val
                                 }
                             }
+                            "keys" => {
+                                if let Some(val) = try_key(value, &["keybindings", "keys"])? {
+                                    config.keybindings.keys = val
+                                }
+                            }
+                            "filter_shape" => {
+                                if let Some(val) = try_key(value, &["keybindings", "filter_shape"])?
+                                {
+                                    config.keybindings.filter_shape = val
+                                }
+                            }
+                            "filter" => {
+                                if let Some(val) = try_key(value, &["keybindings", "filter"])? {
+                                    config.keybindings.filter = val
+                                }
+                            }
+                            "clear_filter" => {
+                                if let Some(val) = try_key(value, &["keybindings", "clear_filter"])?
+                                {
+                                    config.keybindings.clear_filter = val
+                                }
+                            }
+                            "undo" => {
+                                if let Some(val) = try_key(value, &["keybindings", "undo"])? {
+                                    config.keybindings.undo = val
+                                }
+                            }
+                            "redo" => {
+                                if let Some(val) = try_key(value, &["keybindings", "redo"])? {
+                                    config.keybindings.redo = val
+                                }
+                            }
+                            "reload" => {
+                                if let Some(val) = try_key(value, &["keybindings", "reload"])? {
+                                    config.keybindings.reload = val
+                                }
+                            }
+                            "visual" => {
+                                if let Some(val) = try_key(value, &["keybindings", "visual"])? {
+                                    config.keybindings.visual = val
+                                }
+                            }
+                            "sort_ascending" => {
+                                if let Some(val) =
+                                    try_key(value, &["keybindings", "sort_ascending"])?
+                                {
+                                    config.keybindings.sort_ascending = val
+                                }
+                            }
+                            "sort_descending" => {
+                                if let Some(val) =
+                                    try_key(value, &["keybindings", "sort_descending"])?
+                                {
+                                    config.keybindings.sort_descending = val
+                                }
+                            }
+                            "move_down" => {
+                                if let Some(val) = try_key(value, &["keybindings", "move_down"])? {
+                                    config.keybindings.move_down = val
+                                }
+                            }
+                            "move_up" => {
+                                if let Some(val) = try_key(value, &["keybindings", "move_up"])? {
+                                    config.keybindings.move_up = val
+                                }
+                            }
+                            "delete" => {
+                                if let Some(val) = try_key(value, &["keybindings", "delete"])? {
+                                    config.keybindings.delete = val
+                                }
+                            }
+                            "toggle_wrap" => {
+                                if let Some(val) = try_key(value, &["keybindings", "toggle_wrap"])?
+                                {
+                                    config.keybindings.toggle_wrap = val
+                                }
+                            }
+                            "toggle_layout" => {
+                                if let Some(val) =
+                                    try_key(value, &["keybindings", "toggle_layout"])?
+                                {
+                                    config.keybindings.toggle_layout = val
+                                }
+                            }
+                            "toggle_sort_keys" => {
+                                if let Some(val) =
+                                    try_key(value, &["keybindings", "toggle_sort_keys"])?
+                                {
+                                    config.keybindings.toggle_sort_keys = val
+                                }
+                            }
+                            "toggle_cell_path" => {
+                                if let Some(val) =
+                                    try_key(value, &["keybindings", "toggle_cell_path"])?
+                                {
+                                    config.keybindings.toggle_cell_path = val
+                                }
+                            }
+                            "parse" => {
+                                if let Some(val) = try_key(value, &["keybindings", "parse"])? {
+                                    config.keybindings.parse = val
+                                }
+                            }
+                            "expand" => {
+                                if let Some(val) = try_key(value, &["keybindings", "expand"])? {
+                                    config.keybindings.expand = val
+                                }
+                            }
+                            "command" => {
+                                if let Some(val) = try_key(value, &["keybindings", "command"])? {
+                                    config.keybindings.command = val
+                                }
+                            }
+                            "help" => {
+                                if let Some(val) = try_key(value, &["keybindings", "help"])? {
+                                    config.keybindings.help = val
+                                }
+                            }
                             x => return Err(invalid_field(&["keybindings", x], cell.span())),
                         }
                     }
@@ -761,10 +2139,11 @@ impl Config {
 mod tests {
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
     use nu_protocol::{record, Record, Value};
+    use ratatui::{style::Color, widgets::BorderType};
 
     use crate::handler::repr_key;
 
-    use super::Config;
+    use super::{BgFgColorConfig, Config, DataAlignment};
 
     #[test]
     fn keycode_representation() {
@@ -774,7 +2153,12 @@ mod tests {
             (KeyCode::Left, KeyModifiers::NONE, "←"),
             (KeyCode::Esc, KeyModifiers::NONE, "<esc>"),
             (KeyCode::Enter, KeyModifiers::NONE, "⏎"),
-            (KeyCode::Home, KeyModifiers::NONE, "??"),
+            (KeyCode::Home, KeyModifiers::NONE, "⇱"),
+            (KeyCode::Home, KeyModifiers::CONTROL, "<c-⇱>"),
+            (KeyCode::End, KeyModifiers::NONE, "⇲"),
+            (KeyCode::PageUp, KeyModifiers::NONE, "PgUp"),
+            (KeyCode::PageDown, KeyModifiers::NONE, "PgDn"),
+            (KeyCode::Insert, KeyModifiers::NONE, "??"),
         ] {
             assert_eq!(repr_key(&KeyEvent::new(key, modifiers)), expected);
         }
@@ -846,6 +2230,184 @@ mod tests {
         assert_eq!(Config::from_value(&value), Ok(expected));
     }
 
+    #[test]
+    fn parse_transpose_keybinding() {
+        let value = Value::test_record(record! {
+            "keybindings" => Value::test_record(record!{
+                "transpose" => Value::test_string("x")
+            }),
+        });
+
+        let mut expected = Config::default();
+        expected.keybindings.transpose = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(Config::from_value(&value), Ok(expected));
+
+        let value = Value::test_record(record! {
+            "keybindings" => Value::test_record(record!{
+                "transpose" => Value::test_string("t")
+            }),
+        });
+        assert_eq!(Config::from_value(&value), Ok(Config::default()));
+    }
+
+    #[test]
+    fn parse_goto_bindings() {
+        // NOTE: the motions only come after the prefix, so they can be the same as `goto_line`
+        let value = Value::test_record(record! {
+            "keybindings" => Value::test_record(record!{
+                "navigation" => Value::test_record(record!{
+                    "goto_line" => Value::test_string("g"),
+                }),
+                "goto" => Value::test_record(record!{
+                    "prefix" => Value::test_string("z"),
+                    "end" => Value::test_string("G"),
+                }),
+            }),
+        });
+        let mut expected = Config::default();
+        expected.keybindings.navigation.goto_line =
+            KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE);
+        expected.keybindings.goto.prefix = KeyEvent::new(KeyCode::Char('z'), KeyModifiers::NONE);
+        expected.keybindings.goto.end = KeyEvent::new(KeyCode::Char('G'), KeyModifiers::NONE);
+        assert_eq!(Config::from_value(&value), Ok(expected));
+
+        let value = Value::test_record(record! {
+            "keybindings" => Value::test_record(record!{
+                "goto" => Value::test_record(record!{ "nope" => Value::test_string("x") }),
+            }),
+        });
+        assert!(Config::from_value(&value).is_err());
+
+        // NOTE: the old binding points to the new one
+        let value = Value::test_record(record! {
+            "keybindings" => Value::test_record(record!{
+                "navigation" => Value::test_record(record!{
+                    "goto_top" => Value::test_string("g"),
+                }),
+            }),
+        });
+        let err = Config::from_value(&value).unwrap_err();
+        assert!(
+            err.labels[0].text.contains("`$.keybindings.goto.top`"),
+            "{:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn parse_border_type() {
+        for (border_type, expected) in [
+            ("plain", BorderType::Plain),
+            ("rounded", BorderType::Rounded),
+            ("double", BorderType::Double),
+        ] {
+            let value = Value::test_record(record! {
+                "border_type" => Value::test_string(border_type),
+            });
+            assert_eq!(Config::from_value(&value).unwrap().border_type, expected);
+        }
+
+        let value = Value::test_record(record! {
+            "border_type" => Value::test_string("dotted"),
+        });
+        assert!(Config::from_value(&value).is_err());
+    }
+
+    #[test]
+    fn parse_data_alignment() {
+        for (alignment, expected) in [
+            ("left", DataAlignment::Left),
+            ("right", DataAlignment::Right),
+            ("center", DataAlignment::Center),
+        ] {
+            let value = Value::test_record(record! {
+                "data_alignment" => Value::test_string(alignment),
+            });
+            assert_eq!(Config::from_value(&value).unwrap().data_alignment, expected);
+        }
+
+        let value = Value::test_record(record! {
+            "data_alignment" => Value::test_string("justify"),
+        });
+        assert!(Config::from_value(&value).is_err());
+    }
+
+    #[test]
+    fn parse_type_colors() {
+        let value = Value::test_record(record! {
+            "colors" => Value::test_record(record! {
+                "types" => Value::test_record(record! {
+                    "int" => Value::test_record(record! {
+                        "foreground" => Value::test_string("red"),
+                    }),
+                }),
+            }),
+        });
+        let config = Config::from_value(&value).unwrap();
+        assert_eq!(
+            config.colors.types.get("int"),
+            Some(&BgFgColorConfig {
+                foreground: Color::Red,
+                ..Config::default().colors.normal.data
+            })
+        );
+        assert_eq!(config.colors.types.get("string"), None);
+
+        let value = Value::test_record(record! {
+            "colors" => Value::test_record(record! {
+                "types" => Value::test_record(record! {
+                    "integer" => Value::test_record(record! {}),
+                }),
+            }),
+        });
+        assert!(Config::from_value(&value).is_err());
+    }
+
+    #[test]
+    fn parse_tick_rate() {
+        let value = Value::test_record(record! {
+            "tick_rate_ms" => Value::test_int(100),
+        });
+        assert_eq!(Config::from_value(&value).unwrap().tick_rate_ms, 100);
+
+        for val in [0, -1] {
+            let value = Value::test_record(record! {
+                "tick_rate_ms" => Value::test_int(val),
+            });
+            assert!(Config::from_value(&value).is_err());
+        }
+    }
+
+    #[test]
+    fn parse_scroll_amount() {
+        let value = Value::test_record(record! {
+            "scroll_amount" => Value::test_int(5),
+        });
+        assert_eq!(Config::from_value(&value).unwrap().scroll_amount, Some(5));
+
+        let value = Value::test_record(record! {
+            "scroll_amount" => Value::test_nothing(),
+        });
+        assert_eq!(Config::from_value(&value).unwrap().scroll_amount, None);
+
+        for val in [0, -1] {
+            let value = Value::test_record(record! {
+                "scroll_amount" => Value::test_int(val),
+            });
+            assert!(Config::from_value(&value).is_err());
+        }
+    }
+
+    #[test]
+    fn parse_wrap_bottom_value() {
+        for wrap in [true, false] {
+            let value = Value::test_record(record! {
+                "wrap_bottom_value" => Value::test_bool(wrap),
+            });
+            assert_eq!(Config::from_value(&value).unwrap().wrap_bottom_value, wrap);
+        }
+    }
+
     #[test]
     fn same_as_default() {
         assert_eq!(
@@ -856,4 +2418,40 @@ mod tests {
             .unwrap()
         )
     }
+
+    #[test]
+    fn load_from_file() {
+        let dir = std::env::temp_dir().join(format!("nu_plugin_explore-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let file = dir.join("config.nuon");
+        std::fs::write(&file, "{ margin: 3, number: true }").unwrap();
+
+        let config = Config::from_file_and_value(&file, &Value::test_record(record! {})).unwrap();
+        assert_eq!(config.margin, 3);
+        assert!(config.number);
+
+        // the value has priority over the file
+        let config = Config::from_file_and_value(
+            &file,
+            &Value::test_record(record! { "margin" => Value::test_int(5) }),
+        )
+        .unwrap();
+        assert_eq!(config.margin, 5);
+        assert!(config.number);
+
+        // a missing file is ignored
+        let config =
+            Config::from_file_and_value(&dir.join("missing.nuon"), &Value::test_record(record! {}))
+                .unwrap();
+        assert_eq!(config, Config::default());
+
+        // a malformed file is an error
+        std::fs::write(&file, "{ margin: ").unwrap();
+        assert!(Config::from_file_and_value(&file, &Value::test_record(record! {})).is_err());
+        std::fs::write(&file, "{ margin: true }").unwrap();
+        assert!(Config::from_file_and_value(&file, &Value::test_record(record! {})).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
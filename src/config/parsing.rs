@@ -1,12 +1,15 @@
 //! utilities to parse a [`Value`](https://docs.rs/nu-protocol/0.83.1/nu_protocol/enum.Value.html)
 //! into a configuration
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use ratatui::style::{Color, Modifier};
+use ratatui::{
+    style::{Color, Modifier},
+    widgets::BorderType,
+};
 
 use nu_protocol::LabeledError;
 use nu_protocol::{ast::PathMember, Span, Value};
 
-use super::{BgFgColorConfig, Layout};
+use super::{BgFgColorConfig, DataAlignment, EditFormat, Layout, ReturnOnQuit};
 
 /// return an *invalid field* error
 ///
@@ -105,35 +108,68 @@ pub fn try_int(value: &Value, cell_path: &[&str]) -> Result<Option<i64>, Labeled
     }
 }
 
-/// try to parse an ANSI modifier in the *value* at the given *cell path*
+/// try to parse ANSI modifiers in the *value* at the given *cell path*
+///
+/// the modifiers are separated by spaces, e.g. `"bold underline"`, and combined together.
 pub fn try_modifier(value: &Value, cell_path: &[&str]) -> Result<Option<Modifier>, LabeledError> {
     match follow_cell_path(value, cell_path) {
         Some(Value::Nothing { .. }) => Ok(Some(Modifier::empty())),
-        Some(Value::String { val, .. }) => match val.as_str() {
-            "" => Ok(Some(Modifier::empty())),
-            "bold" => Ok(Some(Modifier::BOLD)),
-            "italic" => Ok(Some(Modifier::ITALIC)),
-            "underline" => Ok(Some(Modifier::UNDERLINED)),
-            "blink" => Ok(Some(Modifier::SLOW_BLINK)),
-            x => Err(LabeledError::new(
-                "invalid config").with_label(
-                format!(
-                    r#"`$.{}` should be the empty string, one of [italic, bold, underline, blink] or null, found {}"#,
-                    cell_path.join("."),
-                    x
-                ),
-                value.span()
-            )),
-        },
+        Some(Value::String { val, .. }) => {
+            let mut modifier = Modifier::empty();
+            for token in val.split_whitespace() {
+                modifier |= match token {
+                    "bold" => Modifier::BOLD,
+                    "italic" => Modifier::ITALIC,
+                    "underline" => Modifier::UNDERLINED,
+                    "blink" => Modifier::SLOW_BLINK,
+                    "reversed" => Modifier::REVERSED,
+                    "dim" => Modifier::DIM,
+                    "crossed_out" => Modifier::CROSSED_OUT,
+                    x => return Err(LabeledError::new(
+                        "invalid config").with_label(
+                        format!(
+                            r#"`$.{}` should be the empty string, a space-separated list of [italic, bold, underline, blink, reversed, dim, crossed_out] or null, found {}"#,
+                            cell_path.join("."),
+                            x
+                        ),
+                        value.span()
+                    )),
+                }
+            }
+
+            Ok(Some(modifier))
+        }
         Some(x) => Err(invalid_type(&x, cell_path, "string or null")),
         _ => Ok(None),
     }
 }
 
+/// parse the `rrggbb` part of a `#rrggbb` hex color
+fn parse_hex_color(hex: &str) -> Option<Color> {
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+
+    Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
 /// try to parse a color in the *value* at the given *cell path*
 fn try_color(value: &Value, cell_path: &[&str]) -> Result<Option<Color>, LabeledError> {
     match follow_cell_path(value, cell_path) {
-        Some(Value::String { val, .. }) => match val.as_str() {
+        Some(Value::String { val, internal_span }) => match val.as_str() {
+            x if x.starts_with('#') => match parse_hex_color(&x[1..]) {
+                Some(color) => Ok(Some(color)),
+                None => Err(LabeledError::new("invalid config").with_label(
+                    format!(
+                        r#"`$.{}` should be a hex color of the form #rrggbb, found {}"#,
+                        cell_path.join("."),
+                        x
+                    ),
+                    internal_span,
+                )),
+            },
             "reset" => Ok(Some(Color::Reset)),
             "black" => Ok(Some(Color::Black)),
             "red" => Ok(Some(Color::Red)),
@@ -253,6 +289,41 @@ pub fn try_fg_bg_colors(
     Ok(Some(colors))
 }
 
+/// parse a key inside `<...>`, e.g. `<c-x>`, `<a-x>`, `<c-a-x>` or `<f1>`
+///
+/// the modifiers, `c` for control and `a` for alt in any case, come before the key, which is
+/// either a character or a function key from `f1` to `f12`, all separated by `-`.
+fn parse_chord(chord: &str) -> Option<KeyEvent> {
+    let chord = chord.strip_prefix('<')?.strip_suffix('>')?;
+    // NOTE: the key itself can be a `-`, e.g. `<c-->`
+    let (modifiers, key) = match chord.strip_suffix("--") {
+        Some(modifiers) => (modifiers, "-"),
+        None => chord.rsplit_once('-').unwrap_or(("", chord)),
+    };
+
+    let modifiers =
+        modifiers
+            .split('-')
+            .filter(|m| !m.is_empty())
+            .try_fold(KeyModifiers::NONE, |acc, m| match m {
+                "c" | "C" => Some(acc | KeyModifiers::CONTROL),
+                "a" | "A" => Some(acc | KeyModifiers::ALT),
+                _ => None,
+            })?;
+
+    let mut chars = key.chars();
+    let code = match (chars.next()?, chars.as_str()) {
+        (c, "") => KeyCode::Char(c),
+        ('f' | 'F', n) => match n.parse::<u8>() {
+            Ok(n @ 1..=12) => KeyCode::F(n),
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    Some(KeyEvent::new(code, modifiers))
+}
+
 /// try to parse a key in the *value* at the given *cell path*
 pub fn try_key(value: &Value, cell_path: &[&str]) -> Result<Option<KeyEvent>, LabeledError> {
     match follow_cell_path(value, cell_path) {
@@ -263,36 +334,23 @@ pub fn try_key(value: &Value, cell_path: &[&str]) -> Result<Option<KeyEvent>, La
             "right" => Ok(Some(KeyEvent::new(KeyCode::Right, KeyModifiers::NONE))),
             "escape" => Ok(Some(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))),
             x => {
-                if x.len() != 1 {
-                    if x.len() == 5
-                        && (x.starts_with("<c-") || x.starts_with("<C-"))
-                        && x.ends_with('>')
-                    {
-                        #[allow(clippy::iter_nth_zero)]
-                        return Ok(Some(KeyEvent::new(
-                            // NOTE: this `unwrap` cannot fail because the length of `x` is `5`
-                            KeyCode::Char(x.to_string().chars().nth(3).unwrap()),
-                            KeyModifiers::CONTROL,
-                        )));
-                    }
+                let mut chars = x.chars();
+                if let (Some(c), None) = (chars.next(), chars.next()) {
+                    return Ok(Some(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)));
+                }
 
-                    return Err(LabeledError::new(
+                match parse_chord(x) {
+                    Some(key) => Ok(Some(key)),
+                    None => Err(LabeledError::new(
                         "invalid config")
                         .with_label(format!(
-                            r#"`$.{}` should be a character, possibly inside '<c-...>' or '<C-...>', or one of [up, down, left, right, escape] , found {}"#,
+                            r#"`$.{}` should be a character, possibly inside '<c-...>' or '<a-...>', a function key such as '<f1>', or one of [up, down, left, right, escape] , found {}"#,
                             cell_path.join("."),
                             x
                         ),
                         value.span()
-                    ));
+                    )),
                 }
-
-                #[allow(clippy::iter_nth_zero)]
-                Ok(Some(KeyEvent::new(
-                    // NOTE: this `unwrap` cannot fail because the length of `x` is `1`
-                    KeyCode::Char(x.to_string().chars().nth(0).unwrap()),
-                    KeyModifiers::NONE,
-                )))
             }
         },
         Some(x) => Err(invalid_type(&x, cell_path, "string")),
@@ -320,6 +378,101 @@ pub fn try_layout(value: &Value, cell_path: &[&str]) -> Result<Option<Layout>, L
     }
 }
 
+/// try to parse a border type in the *value* at the given *cell path*
+pub fn try_border_type(
+    value: &Value,
+    cell_path: &[&str],
+) -> Result<Option<BorderType>, LabeledError> {
+    match follow_cell_path(value, cell_path) {
+        Some(Value::String { val, .. }) => match val.as_str() {
+            "plain" => Ok(Some(BorderType::Plain)),
+            "rounded" => Ok(Some(BorderType::Rounded)),
+            "double" => Ok(Some(BorderType::Double)),
+            x => Err(LabeledError::new("invalid config").with_label(
+                format!(
+                    r#"`$.{}` should be one of [plain, rounded, double] , found {}"#,
+                    cell_path.join("."),
+                    x
+                ),
+                value.span(),
+            )),
+        },
+        Some(x) => Err(invalid_type(&x, cell_path, "string")),
+        _ => Ok(None),
+    }
+}
+
+/// try to parse an edit format in the *value* at the given *cell path*
+pub fn try_edit_format(
+    value: &Value,
+    cell_path: &[&str],
+) -> Result<Option<EditFormat>, LabeledError> {
+    match follow_cell_path(value, cell_path) {
+        Some(Value::String { val, .. }) => match val.as_str() {
+            "nuon" => Ok(Some(EditFormat::Nuon)),
+            "json" => Ok(Some(EditFormat::Json)),
+            "yaml" => Ok(Some(EditFormat::Yaml)),
+            x => Err(LabeledError::new("invalid config").with_label(
+                format!(
+                    r#"`$.{}` should be one of [nuon, json, yaml] , found {}"#,
+                    cell_path.join("."),
+                    x
+                ),
+                value.span(),
+            )),
+        },
+        Some(x) => Err(invalid_type(&x, cell_path, "string")),
+        _ => Ok(None),
+    }
+}
+
+/// try to parse an alignment of the data in the *value* at the given *cell path*
+pub fn try_data_alignment(
+    value: &Value,
+    cell_path: &[&str],
+) -> Result<Option<DataAlignment>, LabeledError> {
+    match follow_cell_path(value, cell_path) {
+        Some(Value::String { val, .. }) => match val.as_str() {
+            "left" => Ok(Some(DataAlignment::Left)),
+            "right" => Ok(Some(DataAlignment::Right)),
+            "center" => Ok(Some(DataAlignment::Center)),
+            x => Err(LabeledError::new("invalid config").with_label(
+                format!(
+                    r#"`$.{}` should be one of [left, right, center] , found {}"#,
+                    cell_path.join("."),
+                    x
+                ),
+                value.span(),
+            )),
+        },
+        Some(x) => Err(invalid_type(&x, cell_path, "string")),
+        _ => Ok(None),
+    }
+}
+
+/// try to parse what to return on quit in the *value* at the given *cell path*
+pub fn try_return_on_quit(
+    value: &Value,
+    cell_path: &[&str],
+) -> Result<Option<ReturnOnQuit>, LabeledError> {
+    match follow_cell_path(value, cell_path) {
+        Some(Value::String { val, .. }) => match val.as_str() {
+            "nothing" => Ok(Some(ReturnOnQuit::Nothing)),
+            "mutated_value" => Ok(Some(ReturnOnQuit::MutatedValue)),
+            x => Err(LabeledError::new("invalid config").with_label(
+                format!(
+                    r#"`$.{}` should be one of [nothing, mutated_value] , found {}"#,
+                    cell_path.join("."),
+                    x
+                ),
+                value.span(),
+            )),
+        },
+        Some(x) => Err(invalid_type(&x, cell_path, "string")),
+        _ => Ok(None),
+    }
+}
+
 /// follow a cell path into a Value, giving the resulting Value if it exists
 ///
 /// # Example
@@ -479,8 +632,15 @@ mod tests {
         test_tried_error(
             try_key(&Value::test_string("enter"), &[]),
             "",
-            "should be a character, possibly inside '<c-...>' or '<C-...>', or one of [up, down, left, right, escape] , found enter",
+            "should be a character, possibly inside '<c-...>' or '<a-...>', a function key such as '<f1>', or one of [up, down, left, right, escape] , found enter",
         );
+        for invalid in ["<x-a>", "<c-ab>", "<f13>", "<f0>", "<c->", "<>", "<c-x"] {
+            assert!(
+                try_key(&Value::test_string(invalid), &[]).is_err(),
+                "{} should not be a valid key",
+                invalid
+            );
+        }
 
         let cases = vec![
             ("up", KeyEvent::new(KeyCode::Up, KeyModifiers::NONE)),
@@ -496,6 +656,35 @@ mod tests {
                 "<C-x>",
                 KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL),
             ),
+            (
+                "<a-x>",
+                KeyEvent::new(KeyCode::Char('x'), KeyModifiers::ALT),
+            ),
+            (
+                "<c-a-x>",
+                KeyEvent::new(
+                    KeyCode::Char('x'),
+                    KeyModifiers::CONTROL | KeyModifiers::ALT,
+                ),
+            ),
+            (
+                "<c-A-x>",
+                KeyEvent::new(
+                    KeyCode::Char('x'),
+                    KeyModifiers::CONTROL | KeyModifiers::ALT,
+                ),
+            ),
+            ("<f1>", KeyEvent::new(KeyCode::F(1), KeyModifiers::NONE)),
+            ("<F12>", KeyEvent::new(KeyCode::F(12), KeyModifiers::NONE)),
+            (
+                "<c-f5>",
+                KeyEvent::new(KeyCode::F(5), KeyModifiers::CONTROL),
+            ),
+            (
+                "<c-->",
+                KeyEvent::new(KeyCode::Char('-'), KeyModifiers::CONTROL),
+            ),
+            ("é", KeyEvent::new(KeyCode::Char('é'), KeyModifiers::NONE)),
         ];
 
         for (input, expected) in cases {
@@ -546,7 +735,12 @@ mod tests {
         test_tried_error(
             try_modifier(&Value::test_string("x"), &[]),
             "",
-            "should be the empty string, one of [italic, bold, underline, blink] or null, found x",
+            "should be the empty string, a space-separated list of [italic, bold, underline, blink, reversed, dim, crossed_out] or null, found x",
+        );
+        test_tried_error(
+            try_modifier(&Value::test_string("bold x italic"), &[]),
+            "",
+            "should be the empty string, a space-separated list of [italic, bold, underline, blink, reversed, dim, crossed_out] or null, found x",
         );
 
         assert_eq!(
@@ -560,6 +754,11 @@ mod tests {
             ("bold", Modifier::BOLD),
             ("underline", Modifier::UNDERLINED),
             ("blink", Modifier::SLOW_BLINK),
+            ("reversed", Modifier::REVERSED),
+            ("dim", Modifier::DIM),
+            ("crossed_out", Modifier::CROSSED_OUT),
+            ("bold underline", Modifier::BOLD | Modifier::UNDERLINED),
+            ("  italic   dim ", Modifier::ITALIC | Modifier::DIM),
         ];
 
         for (input, expected) in cases {
@@ -610,6 +809,16 @@ mod tests {
             "",
             "should be a u8, a list of three u8s or one of [black, red, green, yellow, blue, magenta, cyan, gray, darkgray, lightred, lightgreen, lightyellow, lightblue, lightmagenta, lightcyan, white] , found x",
         );
+        test_tried_error(
+            try_color(&Value::test_string("#12"), &[]),
+            "",
+            "should be a hex color of the form #rrggbb, found #12",
+        );
+        test_tried_error(
+            try_color(&Value::test_string("#12345g"), &[]),
+            "",
+            "should be a hex color of the form #rrggbb, found #12345g",
+        );
 
         let cases = vec![
             (Value::test_string("black"), Color::Black),
@@ -617,6 +826,9 @@ mod tests {
             (Value::test_string("green"), Color::Green),
             (Value::test_string("blue"), Color::Blue),
             (Value::test_int(123), Color::Rgb(123, 123, 123)),
+            (Value::test_string("#000000"), Color::Rgb(0, 0, 0)),
+            (Value::test_string("#FFFFFF"), Color::Rgb(255, 255, 255)),
+            (Value::test_string("#ff8800"), Color::Rgb(255, 136, 0)),
             (
                 Value::test_list(vec![
                     Value::test_int(1),
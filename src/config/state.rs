@@ -0,0 +1,118 @@
+//! the state of the UI, persisted from one session to the next, see [`Config::persist_ui_state`]
+use std::path::PathBuf;
+
+use nu_protocol::{record, Span, Value};
+use nuon::{from_nuon, to_nuon, ToStyle};
+
+use super::{Config, Layout};
+
+/// the parts of the [`Config`] that can be changed while exploring, e.g. with `:layout compact`
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct UiState {
+    pub(crate) layout: Layout,
+    pub(crate) number: bool,
+    pub(crate) relativenumber: bool,
+}
+
+impl UiState {
+    pub(crate) fn from_config(config: &Config) -> Self {
+        Self {
+            layout: config.layout.clone(),
+            number: config.number,
+            relativenumber: config.relativenumber,
+        }
+    }
+
+    /// the file of the state, in the config directory of the platform, e.g.
+    /// `~/.config/nu_plugin_explore/state.nuon` on Linux
+    fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("nu_plugin_explore").join("state.nuon"))
+    }
+
+    /// the state as a record, with the same fields as in the [`Config`]
+    fn to_value(&self) -> Value {
+        let layout = match self.layout {
+            Layout::Table => "table",
+            Layout::Compact => "compact",
+        };
+
+        Value::record(
+            record! {
+                "layout" => Value::string(layout, Span::unknown()),
+                "number" => Value::bool(self.number, Span::unknown()),
+                "relativenumber" => Value::bool(self.relativenumber, Span::unknown()),
+            },
+            Span::unknown(),
+        )
+    }
+
+    pub(crate) fn to_nuon(&self) -> Result<String, String> {
+        to_nuon(&self.to_value(), ToStyle::Raw, None).map_err(|err| err.to_string())
+    }
+
+    /// parse a state written with [`UiState::to_nuon`]
+    ///
+    /// > :bulb: **Note**  
+    /// > the state is parsed as a [`Config`], so that it's validated in the same way
+    pub(crate) fn from_nuon(nuon: &str) -> Result<Self, String> {
+        let value = from_nuon(nuon, None).map_err(|err| err.to_string())?;
+        let config = Config::default()
+            .merge_value(&value)
+            .map_err(|err| err.to_string())?;
+
+        Ok(Self::from_config(&config))
+    }
+
+    /// the state of the last session, if any
+    ///
+    /// > :bulb: **Note**  
+    /// > a state that cannot be read or parsed is ignored, it will be overwritten on the next quit
+    pub(crate) fn load() -> Option<Self> {
+        let contents = std::fs::read_to_string(Self::path()?).ok()?;
+        Self::from_nuon(&contents).ok()
+    }
+
+    /// write the state for the next session
+    pub(crate) fn save(&self) -> Result<(), String> {
+        let path = Self::path().ok_or("no config directory on this platform")?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+        }
+        std::fs::write(&path, self.to_nuon()?).map_err(|err| err.to_string())
+    }
+
+    /// apply the state on top of the *config*
+    pub(crate) fn apply(&self, config: Config) -> Config {
+        Config {
+            layout: self.layout.clone(),
+            number: self.number,
+            relativenumber: self.relativenumber,
+            ..config
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UiState;
+    use crate::config::Layout;
+
+    #[test]
+    fn round_trip() {
+        let state = UiState {
+            layout: Layout::Compact,
+            number: true,
+            relativenumber: false,
+        };
+
+        let nuon = state.to_nuon().unwrap();
+        assert_eq!(
+            nuon,
+            "{layout: compact, number: true, relativenumber: false}"
+        );
+        assert_eq!(UiState::from_nuon(&nuon), Ok(state));
+
+        assert!(UiState::from_nuon("{layout: grid}").is_err());
+        assert!(UiState::from_nuon("{layout: ").is_err());
+    }
+}
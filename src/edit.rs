@@ -1,4 +1,4 @@
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     prelude::Rect,
     style::Style,
@@ -6,16 +6,37 @@ use ratatui::{
     Frame,
 };
 
-use nu_protocol::{Span, Value};
+use nu_protocol::{Span, Type, Value};
 use nuon::{from_nuon, to_nuon, ToStyle};
 
-use crate::config::Config;
+use crate::{
+    config::{Config, EditFormat},
+    nu::{
+        json::{from_json, to_json},
+        serialize::{from_yaml, to_yaml},
+    },
+    ui::styled,
+};
 
 #[derive(Default, Clone)]
 pub struct Editor {
     buffer: String,
     cursor_position: (usize, usize),
     width: usize,
+    format: EditFormat,
+    /// the error of the last attempt to parse the buffer, shown below it until the next key
+    error: Option<String>,
+    /// the type of the value being edited, when a change of type needs to be confirmed, see
+    /// [`Editor::confirm_type_change`]
+    original_type: Option<Type>,
+    /// the value parsed from the buffer, waiting for the change of type to be confirmed
+    confirmation: Option<Value>,
+}
+
+/// tell whether editing a value of type *original* into *edited* changes its type, e.g. `123`
+/// into `"123"`
+fn changes_type(original: &Type, edited: &Value) -> bool {
+    original != &edited.get_type()
 }
 
 #[derive(Debug, PartialEq)]
@@ -33,62 +54,135 @@ impl Editor {
         self.width = width - 2;
     }
 
-    pub(super) fn from_value(value: &Value) -> Self {
-        Self {
-            // NOTE: `value` should be a valid [`Value`] and thus the conversion should never fail
-            buffer: to_nuon(value, ToStyle::Raw, None).unwrap(),
+    pub(super) fn from_value(value: &Value, format: EditFormat) -> Result<Self, String> {
+        let buffer = match format {
+            EditFormat::Nuon => to_nuon(value, ToStyle::Raw, None)
+                .map_err(|err| format!("could not convert to NUON: {}", err))?,
+            EditFormat::Json => {
+                to_json(value).map_err(|err| format!("could not convert to JSON: {}", err))?
+            }
+            EditFormat::Yaml => {
+                to_yaml(value).map_err(|err| format!("could not convert to YAML: {}", err))?
+            }
+        };
+
+        Ok(Self {
+            buffer,
             cursor_position: (0, 0),
             width: 0,
+            format,
+            error: None,
+            original_type: None,
+            confirmation: None,
+        })
+    }
+
+    /// ask for a confirmation, with `y`, before giving back a value whose type is not the one of
+    /// *original*, see [`changes_type`]
+    pub(super) fn confirm_type_change(&mut self, original: &Value) {
+        self.original_type = Some(original.get_type());
+    }
+
+    /// the line to show below the buffer, if any, i.e. an error or a change of type to confirm
+    fn message(&self) -> Option<String> {
+        match (&self.error, &self.confirmation, &self.original_type) {
+            (Some(error), _, _) => Some(format!("Err: {}", error)),
+            (None, Some(value), Some(ty)) => Some(format!(
+                "Warn: {} becomes {}, y to confirm",
+                ty,
+                value.get_type()
+            )),
+            _ => None,
         }
     }
 
+    fn parse_buffer(&self) -> Result<Value, String> {
+        match self.format {
+            EditFormat::Nuon => from_nuon(&self.buffer, Some(Span::unknown()))
+                .map_err(|err| format!("could not convert back from NUON: {}", err)),
+            EditFormat::Json => from_json(&self.buffer, Span::unknown())
+                .map_err(|err| format!("could not convert back from JSON: {}", err)),
+            EditFormat::Yaml => from_yaml(&self.buffer, Span::unknown())
+                .map_err(|err| format!("could not convert back from YAML: {}", err)),
+        }
+    }
+
+    /// the rows of the buffer on screen, as the index of their first `char` and their number of
+    /// `char`s, the lines being cut at the explicit newlines and wrapped at the width
+    ///
+    /// > :bulb: **Note**  
+    /// > a line filling its last row is followed by an empty one, for the cursor to go at its end
+    fn rows(&self) -> Vec<(usize, usize)> {
+        let width = self.width.max(1);
+
+        let mut rows = vec![];
+        let mut start = 0;
+        for line in self.buffer.split('\n') {
+            let len = line.chars().count();
+            for k in 0..=len / width {
+                rows.push((start + k * width, width.min(len - k * width)));
+            }
+            start += len + 1;
+        }
+
+        rows
+    }
+
+    /// the index of the `char` under the cursor in the buffer
     fn position(&self) -> usize {
         let (x, y) = self.cursor_position;
-        y * self.width + x
+        match self.rows().get(y) {
+            Some((start, _)) => start + x,
+            None => self.buffer.chars().count(),
+        }
     }
 
-    fn move_cursor_left(&mut self) {
-        let position = self
-            .position()
-            .saturating_sub(1)
-            .clamp(0, self.buffer.len());
+    /// put the cursor on the `char` at *position* in the buffer, clamped to its end
+    fn set_position(&mut self, position: usize) {
+        let position = position.min(self.buffer.chars().count());
+
+        // NOTE: the end of a wrapped row is the start of the next one, on the same line
+        let rows = self.rows();
+        let y = rows
+            .iter()
+            .rposition(|(start, _)| *start <= position)
+            .unwrap_or(0);
+        self.cursor_position = (position - rows[y].0, y);
+    }
 
-        self.cursor_position = (position % self.width, position / self.width);
+    fn move_cursor_left(&mut self) {
+        self.set_position(self.position().saturating_sub(1));
     }
 
     fn move_cursor_right(&mut self) {
-        let position = self
-            .position()
-            .saturating_add(1)
-            .clamp(0, self.buffer.len());
-
-        self.cursor_position = (position % self.width, position / self.width);
+        self.set_position(self.position().saturating_add(1));
     }
 
     fn move_cursor_up(&mut self) {
         let (x, y) = self.cursor_position;
-        let y = y.saturating_sub(1).clamp(0, self.buffer.len() / self.width);
+        let y = y.saturating_sub(1);
 
-        self.cursor_position = (x, y);
+        self.cursor_position = (x.min(self.rows()[y].1), y);
     }
 
     fn move_cursor_down(&mut self) {
         let (x, y) = self.cursor_position;
-        let y = y.saturating_add(1).clamp(0, self.buffer.len() / self.width);
-
-        self.cursor_position = (x, y);
+        let rows = self.rows();
+        let y = y.saturating_add(1).min(rows.len() - 1);
 
-        if self.position() > self.buffer.len() {
-            self.cursor_position = (
-                self.buffer.len() % self.width,
-                self.buffer.len() / self.width,
-            );
-        }
+        self.cursor_position = (x.min(rows[y].1), y);
     }
 
     fn enter_char(&mut self, c: char) {
-        self.buffer.insert(self.position(), c);
-        self.move_cursor_right();
+        // NOTE: the position is in `char`s whereas `insert` works on bytes
+        let position = self.position();
+        let index = self
+            .buffer
+            .char_indices()
+            .nth(position)
+            .map_or(self.buffer.len(), |(i, _)| i);
+        self.buffer.insert(index, c);
+        self.set_position(position + 1);
     }
 
     fn delete_char(&mut self, offset: i32) {
@@ -103,21 +197,48 @@ impl Editor {
             .collect();
     }
 
+    // NOTE: the cursor is put back from its position in the buffer because removing a newline
+    // changes the rows
     fn delete_char_before_cursor(&mut self) {
-        let is_not_cursor_leftmost = self.position() != 0;
+        let position = self.position();
+        let is_not_cursor_leftmost = position != 0;
 
         if is_not_cursor_leftmost {
             self.delete_char(-1);
-            self.move_cursor_left();
+            self.set_position(position - 1);
         }
     }
 
     fn delete_char_under_cursor(&mut self) {
+        let position = self.position();
         self.delete_char(0);
+        self.set_position(position);
     }
 
-    pub(super) fn handle_key(&mut self, key: &KeyCode) -> Result<EditorTransition, String> {
-        match key {
+    /// handle a *key* typed in the editor
+    ///
+    /// > :bulb: **Note**  
+    /// > - a buffer that can't be parsed is kept as is, with the error shown below it, for the user
+    /// >   to fix it or give up with `<esc>`
+    /// > - a change of type, if it needs to be confirmed, is only applied with `y`, any other key
+    /// >   keeping the editor open
+    /// > - `<enter>` with `<shift>` or `<alt>` inserts a newline instead of giving the value back,
+    /// >   the former being only seen by the terminals supporting it
+    pub(super) fn handle_key(&mut self, key: &KeyEvent) -> EditorTransition {
+        self.error = None;
+
+        if let Some(value) = self.confirmation.take() {
+            return match key.code {
+                KeyCode::Char('y') => EditorTransition::Value(value),
+                _ => EditorTransition::Continue,
+            };
+        }
+
+        let newline = key
+            .modifiers
+            .intersects(KeyModifiers::SHIFT | KeyModifiers::ALT);
+        match &key.code {
+            KeyCode::Enter if newline => self.enter_char('\n'),
             KeyCode::Left => self.move_cursor_left(),
             KeyCode::Right => self.move_cursor_right(),
             KeyCode::Up => self.move_cursor_up(),
@@ -125,44 +246,56 @@ impl Editor {
             KeyCode::Char(c) => self.enter_char(*c),
             KeyCode::Backspace => self.delete_char_before_cursor(),
             KeyCode::Delete => self.delete_char_under_cursor(),
-            KeyCode::Enter => match from_nuon(&self.buffer, Some(Span::unknown())) {
-                Ok(val) => return Ok(EditorTransition::Value(val)),
-                Err(err) => return Err(format!("could not convert back from NUON: {}", err)),
+            KeyCode::Enter => match (self.parse_buffer(), &self.original_type) {
+                (Ok(value), Some(ty)) if changes_type(ty, &value) => {
+                    self.confirmation = Some(value)
+                }
+                (Ok(value), _) => return EditorTransition::Value(value),
+                (Err(err), _) => self.error = Some(err),
             },
-            KeyCode::Esc => return Ok(EditorTransition::Quit),
+            KeyCode::Esc => return EditorTransition::Quit,
             _ => {}
         }
 
-        Ok(EditorTransition::Continue)
+        EditorTransition::Continue
     }
 
     pub(super) fn render(&self, frame: &mut Frame, config: &Config) {
-        let title = "Editor";
+        let title = match self.format {
+            EditFormat::Nuon => "Editor (NUON)",
+            EditFormat::Json => "Editor (JSON)",
+            EditFormat::Yaml => "Editor (YAML)",
+        };
 
         let block = Paragraph::new(self.buffer.as_str())
-            .style(
-                Style::default()
-                    .fg(config.colors.editor.buffer.foreground)
-                    .bg(config.colors.editor.buffer.background),
-            )
+            .style(styled(
+                Style::default(),
+                config.colors.editor.buffer.foreground,
+                config.colors.editor.buffer.background,
+                config.colors.enabled,
+            ))
             .block(
-                Block::default().borders(Borders::ALL).title(title).style(
-                    Style::default()
-                        .fg(config.colors.editor.frame.foreground)
-                        .bg(config.colors.editor.frame.background),
-                ),
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .style(styled(
+                        Style::default(),
+                        config.colors.editor.frame.foreground,
+                        config.colors.editor.frame.background,
+                        config.colors.enabled,
+                    )),
             );
 
-        let height = if self.buffer.is_empty() {
-            1
-        } else if (self.buffer.len() % self.width) == 0 {
-            self.buffer.len() / self.width
-        } else {
-            self.buffer.len() / self.width + 1
-        } as u16;
+        let height = self.rows().len() as u16;
+        // NOTE: the message, if any, takes one more line below the frame
+        let message = self.message();
+        let message_height = message.is_some() as u16;
         let area = Rect {
-            x: (frame.size().width - (self.width as u16 + 2)) / 2,
-            y: frame.size().height - (height + 2) - 2,
+            x: frame.size().width.saturating_sub(self.width as u16 + 2) / 2,
+            y: frame
+                .size()
+                .height
+                .saturating_sub(height + 2 + 2 + message_height),
             width: self.width as u16 + 2,
             height: height + 2,
         };
@@ -170,6 +303,24 @@ impl Editor {
         frame.render_widget(Clear, area); //this clears out the background
         frame.render_widget(block.wrap(Wrap { trim: false }), area);
 
+        if let Some(message) = message {
+            let message_area = Rect {
+                y: area.y + area.height,
+                height: 1,
+                ..area
+            };
+            frame.render_widget(Clear, message_area);
+            frame.render_widget(
+                Paragraph::new(message).style(styled(
+                    Style::default(),
+                    config.colors.warning.foreground,
+                    config.colors.warning.background,
+                    config.colors.enabled,
+                )),
+                message_area,
+            );
+        }
+
         let (x, y) = self.cursor_position;
         frame.set_cursor(area.x + 1 + (x as u16), area.y + 1 + (y as u16))
     }
@@ -177,10 +328,12 @@ impl Editor {
 
 #[cfg(test)]
 mod tests {
-    use crossterm::event::KeyCode;
-    use nu_protocol::Value;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use nu_protocol::{record, Type, Value};
+    use ratatui::{backend::TestBackend, Terminal};
 
-    use super::{Editor, EditorTransition};
+    use super::{changes_type, Editor, EditorTransition};
+    use crate::config::{Config, EditFormat};
 
     #[test]
     fn edit_cells() {
@@ -196,180 +349,313 @@ mod tests {
             (
                 KeyCode::Enter,
                 "",
-                Ok(EditorTransition::Value(Value::test_string(""))),
-            ),
-            (KeyCode::Right, "", Ok(EditorTransition::Continue)),
-            (KeyCode::Char('a'), "a", Ok(EditorTransition::Continue)),
-            (KeyCode::Char('b'), "ab", Ok(EditorTransition::Continue)),
-            (KeyCode::Char('c'), "abc", Ok(EditorTransition::Continue)),
-            (KeyCode::Char('d'), "abcd", Ok(EditorTransition::Continue)),
-            (KeyCode::Char('e'), "abcde", Ok(EditorTransition::Continue)),
-            (KeyCode::Left, "abcde", Ok(EditorTransition::Continue)),
-            (KeyCode::Char('f'), "abcdfe", Ok(EditorTransition::Continue)),
-            (KeyCode::Left, "abcdfe", Ok(EditorTransition::Continue)),
-            (KeyCode::Left, "abcdfe", Ok(EditorTransition::Continue)),
-            (
-                KeyCode::Char('g'),
-                "abcgdfe",
-                Ok(EditorTransition::Continue),
-            ),
-            (KeyCode::Right, "abcgdfe", Ok(EditorTransition::Continue)),
-            (KeyCode::Right, "abcgdfe", Ok(EditorTransition::Continue)),
-            (KeyCode::Right, "abcgdfe", Ok(EditorTransition::Continue)),
-            (KeyCode::Up, "abcgdfe", Ok(EditorTransition::Continue)),
-            (KeyCode::Down, "abcgdfe", Ok(EditorTransition::Continue)),
-            (
-                KeyCode::Char('h'),
-                "abcgdfeh",
-                Ok(EditorTransition::Continue),
-            ),
-            (
-                KeyCode::Char('i'),
-                "abcgdfehi",
-                Ok(EditorTransition::Continue),
-            ),
-            (
-                KeyCode::Char('j'),
-                "abcgdfehij",
-                Ok(EditorTransition::Continue),
+                EditorTransition::Value(Value::test_string("")),
             ),
+            (KeyCode::Right, "", EditorTransition::Continue),
+            (KeyCode::Char('a'), "a", EditorTransition::Continue),
+            (KeyCode::Char('b'), "ab", EditorTransition::Continue),
+            (KeyCode::Char('c'), "abc", EditorTransition::Continue),
+            (KeyCode::Char('d'), "abcd", EditorTransition::Continue),
+            (KeyCode::Char('e'), "abcde", EditorTransition::Continue),
+            (KeyCode::Left, "abcde", EditorTransition::Continue),
+            (KeyCode::Char('f'), "abcdfe", EditorTransition::Continue),
+            (KeyCode::Left, "abcdfe", EditorTransition::Continue),
+            (KeyCode::Left, "abcdfe", EditorTransition::Continue),
+            (KeyCode::Char('g'), "abcgdfe", EditorTransition::Continue),
+            (KeyCode::Right, "abcgdfe", EditorTransition::Continue),
+            (KeyCode::Right, "abcgdfe", EditorTransition::Continue),
+            (KeyCode::Right, "abcgdfe", EditorTransition::Continue),
+            (KeyCode::Up, "abcgdfe", EditorTransition::Continue),
+            (KeyCode::Down, "abcgdfe", EditorTransition::Continue),
+            (KeyCode::Char('h'), "abcgdfeh", EditorTransition::Continue),
+            (KeyCode::Char('i'), "abcgdfehi", EditorTransition::Continue),
+            (KeyCode::Char('j'), "abcgdfehij", EditorTransition::Continue),
             (
                 KeyCode::Char('k'),
                 "abcgdfehijk",
-                Ok(EditorTransition::Continue),
+                EditorTransition::Continue,
             ),
             (
                 KeyCode::Char('l'),
                 "abcgdfehijkl",
-                Ok(EditorTransition::Continue),
+                EditorTransition::Continue,
             ),
-            (KeyCode::Up, "abcgdfehijkl", Ok(EditorTransition::Continue)),
+            (KeyCode::Up, "abcgdfehijkl", EditorTransition::Continue),
             (
                 KeyCode::Char('m'),
                 "abmcgdfehijkl",
-                Ok(EditorTransition::Continue),
-            ),
-            (
-                KeyCode::Down,
-                "abmcgdfehijkl",
-                Ok(EditorTransition::Continue),
-            ),
-            (
-                KeyCode::Left,
-                "abmcgdfehijkl",
-                Ok(EditorTransition::Continue),
+                EditorTransition::Continue,
             ),
+            (KeyCode::Down, "abmcgdfehijkl", EditorTransition::Continue),
+            (KeyCode::Left, "abmcgdfehijkl", EditorTransition::Continue),
             (
                 KeyCode::Char('n'),
                 "abmcgdfehijknl",
-                Ok(EditorTransition::Continue),
-            ),
-            (
-                KeyCode::Left,
-                "abmcgdfehijknl",
-                Ok(EditorTransition::Continue),
-            ),
-            (
-                KeyCode::Left,
-                "abmcgdfehijknl",
-                Ok(EditorTransition::Continue),
-            ),
-            (
-                KeyCode::Left,
-                "abmcgdfehijknl",
-                Ok(EditorTransition::Continue),
-            ),
-            (
-                KeyCode::Left,
-                "abmcgdfehijknl",
-                Ok(EditorTransition::Continue),
-            ),
-            (
-                KeyCode::Left,
-                "abmcgdfehijknl",
-                Ok(EditorTransition::Continue),
+                EditorTransition::Continue,
             ),
+            (KeyCode::Left, "abmcgdfehijknl", EditorTransition::Continue),
+            (KeyCode::Left, "abmcgdfehijknl", EditorTransition::Continue),
+            (KeyCode::Left, "abmcgdfehijknl", EditorTransition::Continue),
+            (KeyCode::Left, "abmcgdfehijknl", EditorTransition::Continue),
+            (KeyCode::Left, "abmcgdfehijknl", EditorTransition::Continue),
             (
                 KeyCode::Char('o'),
                 "abmcgdfeohijknl",
-                Ok(EditorTransition::Continue),
+                EditorTransition::Continue,
             ),
             (
                 KeyCode::Right,
                 "abmcgdfeohijknl",
-                Ok(EditorTransition::Continue),
+                EditorTransition::Continue,
             ),
             (
                 KeyCode::Right,
                 "abmcgdfeohijknl",
-                Ok(EditorTransition::Continue),
+                EditorTransition::Continue,
             ),
             (
                 KeyCode::Enter,
                 "abmcgdfeohijknl",
-                Ok(EditorTransition::Value(Value::test_string(
-                    "abmcgdfeohijknl",
-                ))),
+                EditorTransition::Value(Value::test_string("abmcgdfeohijknl")),
             ),
             (
                 KeyCode::Right,
                 "abmcgdfeohijknl",
-                Ok(EditorTransition::Continue),
+                EditorTransition::Continue,
             ),
             (
                 KeyCode::Right,
                 "abmcgdfeohijknl",
-                Ok(EditorTransition::Continue),
+                EditorTransition::Continue,
             ),
             (
                 KeyCode::Char('p'),
                 "abmcgdfeohijkpnl",
-                Ok(EditorTransition::Continue),
+                EditorTransition::Continue,
             ),
             (
                 KeyCode::Backspace,
                 "abmcgdfeohijknl",
-                Ok(EditorTransition::Continue),
+                EditorTransition::Continue,
             ),
             (
                 KeyCode::Backspace,
                 "abmcgdfeohijnl",
-                Ok(EditorTransition::Continue),
+                EditorTransition::Continue,
             ),
             (
                 KeyCode::Backspace,
                 "abmcgdfeohinl",
-                Ok(EditorTransition::Continue),
-            ),
-            (KeyCode::Up, "abmcgdfeohinl", Ok(EditorTransition::Continue)),
-            (
-                KeyCode::Delete,
-                "amcgdfeohinl",
-                Ok(EditorTransition::Continue),
-            ),
-            (
-                KeyCode::Delete,
-                "acgdfeohinl",
-                Ok(EditorTransition::Continue),
+                EditorTransition::Continue,
             ),
-            (
-                KeyCode::Delete,
-                "agdfeohinl",
-                Ok(EditorTransition::Continue),
-            ),
-            (KeyCode::Esc, "agdfeohinl", Ok(EditorTransition::Quit)),
+            (KeyCode::Up, "abmcgdfeohinl", EditorTransition::Continue),
+            (KeyCode::Delete, "amcgdfeohinl", EditorTransition::Continue),
+            (KeyCode::Delete, "acgdfeohinl", EditorTransition::Continue),
+            (KeyCode::Delete, "agdfeohinl", EditorTransition::Continue),
+            (KeyCode::Esc, "agdfeohinl", EditorTransition::Quit),
             (
                 KeyCode::Enter,
                 "agdfeohinl",
-                Ok(EditorTransition::Value(Value::test_string("agdfeohinl"))),
+                EditorTransition::Value(Value::test_string("agdfeohinl")),
             ),
         ];
 
         for (key, expected_buffer, expected) in strokes {
-            let result = editor.handle_key(&key);
+            let result = editor.handle_key(&key.into());
 
             assert_eq!(result, expected);
             assert_eq!(editor.buffer, format!(r#""{}""#, expected_buffer));
         }
     }
+
+    #[test]
+    fn edit_multiple_lines() {
+        let mut editor = Editor::from_value(&Value::test_string("ab"), EditFormat::Nuon).unwrap();
+        editor.set_width(10 + 2);
+        let alt_enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT);
+        let shift_enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::SHIFT);
+
+        // NOTE: the buffer is `"ab"`, with the quotes
+        let strokes = vec![
+            (KeyCode::Right.into(), "\"ab\"", (1, 0)),
+            (KeyCode::Right.into(), "\"ab\"", (2, 0)),
+            (alt_enter, "\"a\nb\"", (0, 1)),
+            (KeyCode::Up.into(), "\"a\nb\"", (0, 0)),
+            (KeyCode::End.into(), "\"a\nb\"", (0, 0)),
+            (KeyCode::Right.into(), "\"a\nb\"", (1, 0)),
+            (KeyCode::Right.into(), "\"a\nb\"", (2, 0)),
+            // the newline is crossed like any other character
+            (KeyCode::Right.into(), "\"a\nb\"", (0, 1)),
+            (KeyCode::Left.into(), "\"a\nb\"", (2, 0)),
+            (KeyCode::Down.into(), "\"a\nb\"", (2, 1)),
+            (KeyCode::Down.into(), "\"a\nb\"", (2, 1)),
+            (shift_enter, "\"a\nb\"\n", (0, 2)),
+            (KeyCode::Up.into(), "\"a\nb\"\n", (0, 1)),
+            (KeyCode::Backspace.into(), "\"ab\"\n", (2, 0)),
+            (KeyCode::Delete.into(), "\"a\"\n", (2, 0)),
+        ];
+        for (key, buffer, cursor) in strokes {
+            assert_eq!(
+                editor.handle_key(&key),
+                EditorTransition::Continue,
+                "{:?}",
+                key
+            );
+            assert_eq!(editor.buffer, buffer, "after {:?}", key);
+            assert_eq!(editor.cursor_position, cursor, "after {:?}", key);
+        }
+
+        // the lines are also wrapped at the width of the editor
+        editor.buffer = format!("{}\nb", "a".repeat(12));
+        assert_eq!(editor.rows(), vec![(0, 10), (10, 2), (13, 1)]);
+        editor.cursor_position = (1, 1);
+        editor.handle_key(&KeyCode::Down.into());
+        assert_eq!(editor.cursor_position, (1, 2));
+        editor.handle_key(&KeyCode::Left.into());
+        editor.handle_key(&KeyCode::Left.into());
+        assert_eq!(editor.cursor_position, (2, 1));
+
+        editor.buffer = "\"a\nb\"".into();
+        assert_eq!(
+            editor.handle_key(&KeyCode::Enter.into()),
+            EditorTransition::Value(Value::test_string("a\nb"))
+        );
+    }
+
+    #[test]
+    fn round_trip_through_formats() {
+        let value = Value::test_record(record! {
+            "name" => Value::test_string("explore"),
+            "version" => Value::test_int(1),
+            "tags" => Value::test_list(vec![Value::test_string("tui"), Value::test_bool(true)]),
+        });
+
+        for (format, buffer) in [
+            (
+                EditFormat::Nuon,
+                r#"{name: explore, version: 1, tags: [tui, true]}"#,
+            ),
+            (
+                EditFormat::Json,
+                r#"{"name":"explore","version":1,"tags":["tui",true]}"#,
+            ),
+            (
+                EditFormat::Yaml,
+                "name: explore\nversion: 1\ntags:\n- tui\n- true\n",
+            ),
+        ] {
+            let mut editor = Editor::from_value(&value, format).unwrap();
+            editor.set_width(80 + 2);
+
+            assert_eq!(editor.buffer, buffer, "{:?}", format);
+            assert_eq!(
+                editor.handle_key(&KeyCode::Enter.into()),
+                EditorTransition::Value(value.clone()),
+                "{:?}",
+                format
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_json_buffer() {
+        let mut editor = Editor::from_value(&Value::test_int(1), EditFormat::Json).unwrap();
+        editor.set_width(10 + 2);
+        editor.buffer = "{a: 1}".into();
+
+        assert_eq!(
+            editor.handle_key(&KeyCode::Enter.into()),
+            EditorTransition::Continue
+        );
+        assert!(
+            matches!(&editor.error, Some(err) if err.starts_with("could not convert back from JSON")),
+            "{:?}",
+            editor.error
+        );
+    }
+
+    #[test]
+    fn keep_an_invalid_buffer() {
+        let mut editor = Editor::from_value(&Value::test_int(1), EditFormat::Nuon).unwrap();
+        editor.set_width(10 + 2);
+        editor.buffer = "[1, 2".into();
+        editor.cursor_position = (5, 0);
+
+        assert_eq!(
+            editor.handle_key(&KeyCode::Enter.into()),
+            EditorTransition::Continue
+        );
+        assert_eq!(editor.buffer, "[1, 2", "the buffer should be kept");
+        assert!(editor.error.is_some());
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+        terminal
+            .draw(|frame| editor.render(frame, &Config::default()))
+            .unwrap();
+        let line = (0..40)
+            .map(|x| terminal.backend().buffer().get(x, 7).symbol())
+            .collect::<String>();
+        assert!(line.contains("Err: "), "{:?}", line);
+
+        // NOTE: the error is forgotten as soon as the buffer is edited
+        assert_eq!(
+            editor.handle_key(&KeyCode::Char(']').into()),
+            EditorTransition::Continue
+        );
+        assert_eq!(editor.error, None);
+
+        assert_eq!(
+            editor.handle_key(&KeyCode::Enter.into()),
+            EditorTransition::Value(Value::test_list(vec![
+                Value::test_int(1),
+                Value::test_int(2)
+            ]))
+        );
+    }
+
+    #[test]
+    fn type_changes() {
+        assert!(changes_type(&Type::Int, &Value::test_string("123")));
+        assert!(changes_type(&Type::Int, &Value::test_float(1.0)));
+        assert!(!changes_type(&Type::Int, &Value::test_int(123)));
+        assert!(!changes_type(&Type::String, &Value::test_string("")));
+    }
+
+    #[test]
+    fn confirm_a_change_of_type() {
+        let original = Value::test_int(123);
+        let mut editor = Editor::from_value(&original, EditFormat::Nuon).unwrap();
+        editor.confirm_type_change(&original);
+        editor.set_width(10 + 2);
+        editor.buffer = r#""123""#.into();
+
+        assert_eq!(
+            editor.handle_key(&KeyCode::Enter.into()),
+            EditorTransition::Continue
+        );
+        assert!(editor
+            .message()
+            .is_some_and(|m| m.starts_with("Warn: int becomes string")));
+
+        // NOTE: declining keeps the editor open, with the buffer untouched
+        assert_eq!(
+            editor.handle_key(&KeyCode::Char('n').into()),
+            EditorTransition::Continue
+        );
+        assert_eq!(editor.buffer, r#""123""#);
+        assert_eq!(editor.message(), None);
+
+        editor.handle_key(&KeyCode::Enter.into());
+        assert_eq!(
+            editor.handle_key(&KeyCode::Char('y').into()),
+            EditorTransition::Value(Value::test_string("123"))
+        );
+
+        // the same type needs no confirmation
+        editor.buffer = "456".into();
+        assert_eq!(
+            editor.handle_key(&KeyCode::Enter.into()),
+            EditorTransition::Value(Value::test_int(456))
+        );
+    }
 }
@@ -0,0 +1,197 @@
+//! a scrollable overlay to show the errors that don't fit at the bottom of the frame, e.g. the
+//! multi-line ones
+use crossterm::event::KeyEvent;
+use ratatui::{
+    prelude::Rect,
+    style::Style,
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::{config::Config, handler::repr_key, ui::styled};
+
+#[derive(Debug, Clone)]
+pub(crate) struct ErrorView {
+    message: String,
+    /// the number of wrapped lines scrolled past the top of the overlay
+    scroll: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum ErrorTransition {
+    Continue,
+    Quit,
+}
+
+/// wrap the lines of *message* to at most *width* characters
+///
+/// > :bulb: **Note**  
+/// > the lines are cut on `char` boundaries, not on words, and the empty lines are kept
+fn wrap(message: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+
+    message
+        .lines()
+        .flat_map(|line| {
+            let chars = line.chars().collect::<Vec<char>>();
+            if chars.is_empty() {
+                return vec![String::new()];
+            }
+            chars
+                .chunks(width)
+                .map(|chunk| chunk.iter().collect())
+                .collect()
+        })
+        .collect()
+}
+
+impl ErrorView {
+    pub(crate) fn new(message: &str) -> Self {
+        Self {
+            message: message.into(),
+            scroll: 0,
+        }
+    }
+
+    pub(crate) fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// tell whether the error is too long to be shown at the bottom of a frame of the given
+    /// *width*, after the `Err: ` prefix, and needs the overlay
+    pub(crate) fn is_long(&self, width: u16) -> bool {
+        self.message.contains('\n') || self.message.chars().count() + 5 > width as usize
+    }
+
+    /// the centered area of the overlay in a frame of the given *size*
+    fn area(&self, size: Rect) -> Rect {
+        let width = size.width.saturating_sub(4);
+        let lines = wrap(&self.message, width.saturating_sub(2) as usize).len() as u16;
+        let height = (lines + 2).min(size.height.saturating_sub(4));
+
+        Rect {
+            x: size.width.saturating_sub(width) / 2,
+            y: size.height.saturating_sub(height) / 2,
+            width,
+            height,
+        }
+    }
+
+    /// the maximum scroll in a frame of the given *size*, i.e. when the last line is at the bottom
+    /// of the overlay
+    fn max_scroll(&self, size: Rect) -> usize {
+        let area = self.area(size);
+        let lines = wrap(&self.message, area.width.saturating_sub(2) as usize).len();
+
+        lines.saturating_sub(area.height.saturating_sub(2) as usize)
+    }
+
+    /// scroll the error with [`crate::config::NavigationBindingsMap::down`] and
+    /// [`crate::config::NavigationBindingsMap::up`], any other key dismissing it
+    ///
+    /// > :bulb: **Note**  
+    /// > an error short enough to be at the bottom of the frame of the given *size* is always
+    /// > dismissed, as before
+    pub(crate) fn handle_key(
+        &mut self,
+        key: &KeyEvent,
+        config: &Config,
+        size: Rect,
+    ) -> ErrorTransition {
+        if !self.is_long(size.width) {
+            return ErrorTransition::Quit;
+        }
+
+        if key == &config.keybindings.navigation.down {
+            self.scroll = (self.scroll + 1).min(self.max_scroll(size));
+        } else if key == &config.keybindings.navigation.up {
+            self.scroll = self.scroll.saturating_sub(1);
+        } else {
+            return ErrorTransition::Quit;
+        }
+
+        ErrorTransition::Continue
+    }
+
+    pub(crate) fn render(&self, frame: &mut Frame, config: &Config) {
+        let area = self.area(frame.size());
+        let lines = wrap(&self.message, area.width.saturating_sub(2) as usize)
+            .into_iter()
+            .skip(self.scroll)
+            .map(Line::from)
+            .collect::<Vec<Line>>();
+
+        let title = format!(
+            "Err ({}{} to scroll, any other key to continue)",
+            repr_key(&config.keybindings.navigation.down),
+            repr_key(&config.keybindings.navigation.up),
+        );
+        let paragraph = Paragraph::new(lines)
+            .style(styled(
+                Style::default(),
+                config.colors.warning.foreground,
+                config.colors.warning.background,
+                config.colors.enabled,
+            ))
+            .block(Block::default().borders(Borders::ALL).title(title));
+
+        frame.render_widget(Clear, area); //this clears out the background
+        frame.render_widget(paragraph, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::prelude::Rect;
+
+    use super::{wrap, ErrorTransition, ErrorView};
+    use crate::config::Config;
+
+    #[test]
+    fn wrap_messages() {
+        assert_eq!(wrap("abcdef", 4), vec!["abcd", "ef"]);
+        assert_eq!(wrap("ab\n\ncd", 4), vec!["ab", "", "cd"]);
+        assert_eq!(wrap("éèàù", 2), vec!["éè", "àù"]);
+        assert_eq!(wrap("abc", 0), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn scroll_long_errors() {
+        let config = Config::default();
+        let kmap = &config.keybindings;
+        let other = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
+        // NOTE: the overlay is 6 lines high inside a 10-line frame, i.e. 4 lines of message
+        let size = Rect::new(0, 0, 20, 10);
+
+        // a short error is dismissed by any key, even the ones to scroll
+        let mut view = ErrorView::new("short");
+        assert_eq!(
+            view.handle_key(&kmap.navigation.down, &config, size),
+            ErrorTransition::Quit
+        );
+
+        let message = (0..6).map(|i| i.to_string()).collect::<Vec<_>>().join("\n");
+        let mut view = ErrorView::new(&message);
+        assert_eq!(view.max_scroll(size), 2);
+
+        let strokes = vec![
+            (&kmap.navigation.up, 0, ErrorTransition::Continue),
+            (&kmap.navigation.down, 1, ErrorTransition::Continue),
+            (&kmap.navigation.down, 2, ErrorTransition::Continue),
+            (&kmap.navigation.down, 2, ErrorTransition::Continue),
+            (&kmap.navigation.up, 1, ErrorTransition::Continue),
+            (&other, 1, ErrorTransition::Quit),
+        ];
+        for (key, scroll, expected) in strokes {
+            assert_eq!(view.handle_key(key, &config, size), expected);
+            assert_eq!(view.scroll, scroll, "after {:?}", key);
+        }
+
+        // a single line that is too long for the frame is wrapped
+        let view = ErrorView::new(&"a".repeat(100));
+        assert!(view.is_long(size.width));
+        assert_eq!(view.max_scroll(size), 100 / 14 + 1 - 4);
+    }
+}
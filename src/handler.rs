@@ -1,15 +1,27 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::path::Path;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 
 use nu_protocol::{
     ast::{CellPath, PathMember},
     ShellError, Span, Value,
 };
+use nuon::{from_nuon, to_nuon, ToStyle};
 
 use crate::{
-    app::{App, Mode},
+    app::{App, Filter, Mode, RowsCache},
+    clipboard::copy_to_clipboard,
+    command::write_nuon,
+    config::{KeyBindingsMap, Layout, ReturnOnQuit},
     edit::EditorTransition,
     navigation::Direction,
-    nu::value::transpose,
+    nu::{
+        json::from_json,
+        strings::{stat_path, SpecialString},
+        value::{filter_rows, is_table, schema, sort, swap, table_columns, transpose, Table},
+    },
+    prompt::{Prompt, PromptTransition},
+    ui::{format_cell_path, repr_table, to_markdown, ReprOptions, ScrollAnchor},
 };
 
 /// the result of a state transition
@@ -29,7 +41,51 @@ impl TransitionResult {
     }
 }
 
+/// tell whether the *key* changes the data in NORMAL mode, e.g. to refuse it when the data is
+/// read-only or when the keys of a record are being explored
+///
+/// > :bulb: **Note**  
+/// > undo and redo are not part of it, they don't change the data as it was given
+fn is_mutating(bindings: &KeyBindingsMap, key: &KeyEvent) -> bool {
+    [
+        bindings.insert,
+        bindings.transpose,
+        bindings.sort_ascending,
+        bindings.sort_descending,
+        bindings.move_down,
+        bindings.move_up,
+        bindings.delete,
+        bindings.parse,
+    ]
+    .contains(key)
+}
+
 impl App {
+    /// quit the application, giving back the data if [`crate::config::Config::return_on_quit`] asks for it
+    pub(crate) fn quit(&self) -> TransitionResult {
+        match self.config.return_on_quit {
+            ReturnOnQuit::Nothing => TransitionResult::Quit,
+            ReturnOnQuit::MutatedValue => TransitionResult::Return(self.value.clone()),
+        }
+    }
+
+    /// Handles the mouse events and updates the state of [`App`].
+    ///
+    /// > :bulb: **Note**  
+    /// > the mouse wheel only scrolls through the data in NORMAL mode
+    pub fn handle_mouse_events(&mut self, mouse_event: MouseEvent) {
+        if !self.config.mouse || self.mode != Mode::Normal {
+            return;
+        }
+
+        let rows = self.config.mouse_scroll;
+        match mouse_event.kind {
+            MouseEventKind::ScrollDown => self.go_up_or_down_in_data(Direction::Down(rows)),
+            MouseEventKind::ScrollUp => self.go_up_or_down_in_data(Direction::Up(rows)),
+            _ => {}
+        }
+    }
+
     /// Handles the key events and updates the state of [`App`].
     #[allow(clippy::collapsible_if)]
     pub fn handle_key_events(
@@ -37,6 +93,23 @@ impl App {
         key_event: KeyEvent,
         half_page: usize,
     ) -> Result<TransitionResult, ShellError> {
+        // NOTE: the flash message, the tour and the summary of the root only last until the next
+        // key press, which still does its job
+        self.flash = None;
+        self.tour = false;
+        self.root_summary = None;
+
+        // NOTE: `<c-c>` always aborts, whatever the mode, to restore the terminal, unless it's
+        // bound to something else by the user
+        let abort = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        if key_event == abort && !self.config.keybindings.is_bound(&abort) {
+            return Ok(TransitionResult::Quit);
+        }
+        // NOTE: the prefix to jump to only grows with consecutive letters, see below
+        let jump_prefix = std::mem::take(&mut self.jump_prefix);
+        // NOTE: the half page is still the one of the frame for everything else, e.g. `zz`
+        let scroll_amount = self.config.scroll_amount.unwrap_or(half_page);
+
         let config = &self.config;
 
         match self.mode {
@@ -57,24 +130,164 @@ impl App {
                         _ => unreachable!(),
                     });
                     return Ok(TransitionResult::Continue);
+                } else if matches!(
+                    key_event.code,
+                    KeyCode::Home | KeyCode::End | KeyCode::PageUp | KeyCode::PageDown
+                ) {
+                    // NOTE: the usual navigation keys work regardless of the bindings
+                    match (key_event.code, key_event.modifiers) {
+                        (KeyCode::Home, KeyModifiers::CONTROL) => self.go_to_root(),
+                        (KeyCode::Home, _) => self.go_up_or_down_in_data(Direction::Top),
+                        (KeyCode::End, _) => self.go_up_or_down_in_data(Direction::Bottom),
+                        (KeyCode::PageUp, _) => {
+                            self.go_half_page_up_or_down(Direction::Up(scroll_amount), half_page)
+                        }
+                        _ => {
+                            self.go_half_page_up_or_down(Direction::Down(scroll_amount), half_page)
+                        }
+                    }
+                    return Ok(TransitionResult::Continue);
                 } else if key_event == config.keybindings.navigation.half_page_down {
-                    // TODO: add a margin to the bottom
-                    self.go_up_or_down_in_data(Direction::Down(half_page));
+                    self.go_half_page_up_or_down(Direction::Down(scroll_amount), half_page);
                     return Ok(TransitionResult::Continue);
                 } else if key_event == config.keybindings.navigation.half_page_up {
-                    // TODO: add a margin to the top
-                    self.go_up_or_down_in_data(Direction::Up(half_page));
+                    self.go_half_page_up_or_down(Direction::Up(scroll_amount), half_page);
                     return Ok(TransitionResult::Continue);
                 } else if key_event == config.keybindings.navigation.goto_bottom {
                     self.go_up_or_down_in_data(Direction::Bottom);
                     return Ok(TransitionResult::Continue);
-                } else if key_event == config.keybindings.navigation.goto_top {
-                    self.go_up_or_down_in_data(Direction::Top);
+                } else if key_event == config.keybindings.goto.prefix {
+                    self.mode = Mode::Goto(None);
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.scroll.prefix {
+                    self.mode = Mode::Scroll;
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.yank.prefix {
+                    self.mode = Mode::Yank;
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.navigation.search {
+                    self.mode = Mode::Searching;
+                    self.search.clear();
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.navigation.next_match {
+                    self.go_to_search_match(Direction::Down(1));
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.navigation.previous_match {
+                    self.go_to_search_match(Direction::Up(1));
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.navigation.next_collection {
+                    self.go_to_collection(Direction::Down(1));
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.navigation.previous_collection {
+                    self.go_to_collection(Direction::Up(1));
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.command {
+                    self.mode = Mode::Command;
+                    self.command.clear();
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.help {
+                    self.mode = Mode::Help;
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.visual {
+                    let mut path = self.position.clone();
+                    let has_rows = path.members.pop().is_some()
+                        && match self.view_at(&path) {
+                            Value::List { vals, .. } => !vals.is_empty(),
+                            Value::Record { val: rec, .. } => !rec.is_empty(),
+                            _ => false,
+                        };
+
+                    if has_rows {
+                        self.mode = Mode::Visual(self.selected_row());
+                    } else {
+                        self.flash = Some(
+                            "only the rows of a non-empty list or record can be selected".into(),
+                        );
+                    }
                     return Ok(TransitionResult::Continue);
                 } else if key_event == config.keybindings.quit {
-                    return Ok(TransitionResult::Quit);
+                    return Ok(self.quit());
+                } else if !config.editable && is_mutating(&config.keybindings, &key_event) {
+                    self.flash = Some("the data is read-only".into());
+                    return Ok(TransitionResult::Continue);
+                } else if self.keys.is_some()
+                    && (is_mutating(&config.keybindings, &key_event)
+                        || key_event == config.keybindings.undo
+                        || key_event == config.keybindings.redo)
+                {
+                    self.flash = Some("the keys of a record can't be changed".into());
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.reload {
+                    self.reload();
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.toggle_layout {
+                    self.config.layout = match self.config.layout {
+                        Layout::Table => Layout::Compact,
+                        Layout::Compact => Layout::Table,
+                    };
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.toggle_sort_keys {
+                    // NOTE: the cursor stays on its key, which might now be shown elsewhere
+                    self.config.view_sort_keys = !self.config.view_sort_keys;
+                    self.rows_cache = RowsCache::default();
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.toggle_cell_path {
+                    // NOTE: the data frame changes height, the rendering tops being clamped again
+                    // on the next draw, see `ui::scroll_top`
+                    self.config.show_cell_path = !self.config.show_cell_path;
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.expand {
+                    let mut path = self.position.clone();
+                    path.members.pop();
+
+                    if matches!(
+                        is_table(&self.view_at(&path), !config.strict_tables),
+                        Table::IsValid
+                    ) {
+                        return Ok(TransitionResult::Error(
+                            "the rows of a table can't be expanded".into(),
+                        ));
+                    }
+                    if !matches!(
+                        self.value_under_cursor(None),
+                        Value::List { .. } | Value::Record { .. }
+                    ) {
+                        return Ok(TransitionResult::Error(
+                            "only lists and records can be expanded".into(),
+                        ));
+                    }
+
+                    self.toggle_expansion(&self.position.clone());
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.parse {
+                    // NOTE: the strings that are not JSON are parsed as NUON, e.g. `{a: 1}`
+                    let parsed = match self.value_under_cursor(None) {
+                        Value::String { val, .. } => {
+                            from_json(&val, Span::unknown()).or_else(|_| {
+                                from_nuon(&val, Some(Span::unknown()))
+                                    .map_err(|err| err.to_string())
+                            })
+                        }
+                        _ => {
+                            return Ok(TransitionResult::Error("only strings can be parsed".into()))
+                        }
+                    };
+                    return match parsed {
+                        Ok(value) => Ok(TransitionResult::Mutate(value, self.position.clone())),
+                        Err(err) => Ok(TransitionResult::Error(format!(
+                            "could not parse the string: {}",
+                            err
+                        ))),
+                    };
+                } else if key_event == config.keybindings.keys {
+                    if let Err(err) = self.enter_keys_view() {
+                        return Ok(TransitionResult::Error(err));
+                    }
+                    return Ok(TransitionResult::Continue);
                 } else if key_event == config.keybindings.insert {
-                    self.enter_editor();
+                    if let Err(err) = self.enter_editor() {
+                        return Ok(TransitionResult::Error(err));
+                    }
                     return Ok(TransitionResult::Continue);
                 } else if key_event == config.keybindings.peek {
                     self.mode = Mode::Peeking;
@@ -91,6 +304,36 @@ impl App {
                 } else if key_event == config.keybindings.navigation.left {
                     self.go_back_in_data();
                     return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.navigation.goto_root {
+                    self.go_to_root();
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.navigation.scroll_left
+                    || key_event == config.keybindings.navigation.scroll_right
+                {
+                    let mut path = self.position.clone();
+                    path.members.pop();
+
+                    let view = self.view_at(&path);
+                    let nb_columns = match (&view, is_table(&view, !config.strict_tables)) {
+                        (Value::List { vals, .. }, Table::IsValid) => {
+                            table_columns(vals.iter().map(|v| v.as_record().unwrap())).len()
+                        }
+                        _ => {
+                            return Ok(TransitionResult::Error(
+                                "only the columns of a table can be scrolled".into(),
+                            ))
+                        }
+                    };
+
+                    // NOTE: at least one column that is not pinned stays visible
+                    let max = nb_columns.saturating_sub(config.pinned_columns + 1);
+                    self.horizontal_offset =
+                        if key_event == config.keybindings.navigation.scroll_left {
+                            self.horizontal_offset.min(max).saturating_sub(1)
+                        } else {
+                            (self.horizontal_offset + 1).min(max)
+                        };
+                    return Ok(TransitionResult::Continue);
                 } else if key_event == config.keybindings.transpose {
                     let mut path = self.position.clone();
                     path.members.pop();
@@ -122,7 +365,224 @@ impl App {
                         return Ok(TransitionResult::Mutate(transpose, path));
                     }
 
+                    // NOTE: pressing the key without anything happening would be confusing
+                    return Ok(TransitionResult::Error(format!(
+                        "only tables and records can be transposed, found {}",
+                        view.get_type()
+                    )));
+                } else if key_event == config.keybindings.sort_ascending
+                    || key_event == config.keybindings.sort_descending
+                {
+                    let descending = key_event == config.keybindings.sort_descending;
+
+                    let mut path = self.position.clone();
+                    let current = path.members.pop();
+
+                    // NOTE: from inside a row of a table, the whole table is sorted by the column
+                    // under the cursor
+                    let mut table_path = path.clone();
+                    if let (Some(PathMember::Int { .. }), Some(PathMember::String { val, .. })) =
+                        (table_path.members.pop(), &current)
+                    {
+                        let table = self.value_under_cursor(Some(table_path.clone()));
+                        if matches!(is_table(&table, !config.strict_tables), Table::IsValid) {
+                            return Ok(TransitionResult::Mutate(
+                                sort(&table, Some(val), descending),
+                                table_path,
+                            ));
+                        }
+                    }
+
+                    let view = self.value_under_cursor(Some(path.clone()));
+                    let sorted = match &view {
+                        Value::Record { .. } => sort(&view, None, descending),
+                        Value::List { vals, .. } => match is_table(&view, !config.strict_tables) {
+                            Table::IsValid => {
+                                // NOTE: a valid table always has a first row with columns
+                                let column = vals[0].as_record().unwrap().columns().next().cloned();
+                                sort(&view, column.as_deref(), descending)
+                            }
+                            _ => sort(&view, None, descending),
+                        },
+                        _ => {
+                            return Ok(TransitionResult::Error(
+                                "only lists and records can be sorted".into(),
+                            ))
+                        }
+                    };
+
+                    // NOTE: the sorted value is always given back because two records with the
+                    // same fields in a different order are equal
+                    return Ok(TransitionResult::Mutate(sorted, path));
+                } else if key_event == config.keybindings.move_down
+                    || key_event == config.keybindings.move_up
+                {
+                    let mut path = self.position.clone();
+                    let current = path.members.pop();
+
+                    // NOTE: in a filtered list, the row is swapped with the next visible one
+                    let view = self.value_under_cursor(Some(path.clone()));
+                    let (rows, index) = match (&current, &view) {
+                        (Some(PathMember::Int { val, .. }), Value::List { vals, .. }) => {
+                            (self.visible_indices(&path, vals), *val)
+                        }
+                        (Some(PathMember::String { .. }), Value::Record { .. })
+                            if config.view_sort_keys =>
+                        {
+                            return Ok(TransitionResult::Error(
+                                "the keys can't be moved while they are shown sorted".into(),
+                            ))
+                        }
+                        (Some(PathMember::String { val, .. }), Value::Record { val: rec, .. }) => (
+                            (0..rec.len()).collect(),
+                            rec.columns().position(|col| col == val).unwrap_or(0),
+                        ),
+                        _ => {
+                            return Ok(TransitionResult::Error(
+                                "only the rows of a list or a record can be moved".into(),
+                            ))
+                        }
+                    };
+
+                    let neighbour = match rows.iter().position(|&i| i == index) {
+                        Some(k) if key_event == config.keybindings.move_down => rows.get(k + 1),
+                        Some(k) if k > 0 => rows.get(k - 1),
+                        _ => None,
+                    };
+                    // NOTE: the first row can't go up and the last one can't go down
+                    let Some(&neighbour) = neighbour else {
+                        return Ok(TransitionResult::Continue);
+                    };
+
+                    // NOTE: in a record, the cursor is on the key, which follows the field already
+                    if let Some(PathMember::Int { val, .. }) = self.position.members.last_mut() {
+                        *val = neighbour;
+                    }
+                    return Ok(TransitionResult::Mutate(
+                        swap(&view, index, neighbour),
+                        path,
+                    ));
+                } else if key_event == config.keybindings.delete {
+                    let mut path = self.position.clone();
+                    let current = path.members.pop();
+
+                    // NOTE: the cursor goes to the next row, or the previous one after the last row
+                    let (value, member) =
+                        match (current, self.value_under_cursor(Some(path.clone()))) {
+                            (
+                                Some(PathMember::Int {
+                                    val,
+                                    optional: false,
+                                    ..
+                                }),
+                                Value::List { mut vals, .. },
+                            ) if val < vals.len() => {
+                                vals.remove(val);
+                                let visible = self.visible_indices(&path, &vals);
+                                let next = visible
+                                    .iter()
+                                    .find(|&&i| i >= val)
+                                    .or(visible.last())
+                                    .copied();
+                                let member = PathMember::Int {
+                                    val: next.unwrap_or(0),
+                                    span: Span::unknown(),
+                                    optional: next.is_none(),
+                                };
+                                (Value::list(vals, Span::unknown()), member)
+                            }
+                            (
+                                Some(PathMember::String {
+                                    val,
+                                    optional: false,
+                                    ..
+                                }),
+                                Value::Record { val: rec, .. },
+                            ) if rec.contains(&val) => {
+                                let cols = self.columns_in_view(&rec);
+                                let index = cols.iter().position(|col| col == &val).unwrap_or(0);
+                                let mut rec = rec.into_owned();
+                                rec.remove(&val);
+                                let cols = self.columns_in_view(&rec);
+                                let next = cols.get(index).or(cols.last()).cloned();
+                                let member = PathMember::String {
+                                    val: next.clone().unwrap_or_default(),
+                                    span: Span::unknown(),
+                                    optional: next.is_none(),
+                                };
+                                (Value::record(rec, Span::unknown()), member)
+                            }
+                            _ => {
+                                return Ok(TransitionResult::Error(
+                                    "only the rows of a non-empty list or record can be deleted"
+                                        .into(),
+                                ))
+                            }
+                        };
+
+                    // NOTE: app.position.members should never be empty by construction
+                    *self.position.members.last_mut().unwrap() = member;
+                    return Ok(TransitionResult::Mutate(value, path));
+                } else if key_event == config.keybindings.filter_shape {
+                    let mut path = self.position.clone();
+                    path.members.pop();
+
+                    if !matches!(
+                        self.value_under_cursor(Some(path.clone())),
+                        Value::List { .. }
+                    ) {
+                        return Ok(TransitionResult::Error(
+                            "only the items of a list can be filtered".into(),
+                        ));
+                    }
+
+                    let shape = self.value_at(&self.position).get_type();
+                    self.filter = Some((path, Filter::Shape(shape)));
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.filter {
+                    let mut path = self.position.clone();
+                    path.members.pop();
+
+                    if !matches!(
+                        self.value_under_cursor(Some(path.clone())),
+                        Value::List { .. }
+                    ) {
+                        return Ok(TransitionResult::Error(
+                            "only the items of a list can be filtered".into(),
+                        ));
+                    }
+
+                    self.mode = Mode::Filtering;
+                    self.prompt = Prompt::new("Filter");
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.clear_filter {
+                    self.filter = None;
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.undo {
+                    if !self.undo() {
+                        self.flash = Some("nothing to undo".into());
+                    }
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.redo {
+                    if !self.redo() {
+                        self.flash = Some("nothing to redo".into());
+                    }
                     return Ok(TransitionResult::Continue);
+                } else if config.enter_peeks && key_event.code == KeyCode::Enter {
+                    // NOTE: the actual bindings, checked above, have priority
+                    return Ok(TransitionResult::Return(self.value_at(&self.position)));
+                } else if let (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) =
+                    (key_event.code, key_event.modifiers)
+                {
+                    // NOTE: the letters that are not bound to any action jump to the first row
+                    // starting with them, digits being always a count for WAITING mode
+                    if c.is_alphabetic() {
+                        self.jump_prefix = jump_prefix;
+                        self.jump_prefix.push(c);
+                        self.jump_prefix_ticks = 0;
+                        self.go_to_prefix_match();
+                        return Ok(TransitionResult::Continue);
+                    }
                 }
             }
             Mode::Waiting(n) => {
@@ -160,53 +620,348 @@ impl App {
                     return Ok(TransitionResult::Continue);
                 }
             }
+            Mode::Visual(anchor) => {
+                if key_event == config.keybindings.quit {
+                    return Ok(self.quit());
+                } else if key_event == config.keybindings.normal {
+                    self.mode = Mode::Normal;
+                } else if key_event == config.keybindings.peek {
+                    // NOTE: the selection always exists in VISUAL mode
+                    let (start, end) = self.visual_selection().unwrap();
+                    let mut path = self.position.clone();
+                    path.members.pop();
+
+                    let selection = match self.view_at(&path) {
+                        Value::List { vals, .. } => {
+                            Value::list(vals[start..=end].to_vec(), Span::unknown())
+                        }
+                        Value::Record { val: rec, .. } => Value::record(
+                            rec.iter()
+                                .skip(start)
+                                .take(end - start + 1)
+                                .map(|(col, val)| (col.clone(), val.clone()))
+                                .collect(),
+                            Span::unknown(),
+                        ),
+                        _ => unreachable!("only lists and records can be selected"),
+                    };
+
+                    self.mode = Mode::Normal;
+                    return Ok(TransitionResult::Return(selection));
+                } else if key_event == config.keybindings.navigation.down {
+                    self.go_up_or_down_in_data(Direction::Down(1));
+                } else if key_event == config.keybindings.navigation.up {
+                    self.go_up_or_down_in_data(Direction::Up(1));
+                } else if key_event == config.keybindings.navigation.half_page_down {
+                    self.go_up_or_down_in_data(Direction::Down(scroll_amount));
+                } else if key_event == config.keybindings.navigation.half_page_up {
+                    self.go_up_or_down_in_data(Direction::Up(scroll_amount));
+                } else if key_event == config.keybindings.navigation.goto_bottom {
+                    self.go_up_or_down_in_data(Direction::Bottom);
+                } else if key_event == config.keybindings.goto.prefix {
+                    self.mode = Mode::Goto(Some(anchor));
+                }
+                return Ok(TransitionResult::Continue);
+            }
+            Mode::Goto(anchor) => {
+                // NOTE: any other key cancels the motion
+                self.mode = match anchor {
+                    Some(anchor) => Mode::Visual(anchor),
+                    None => Mode::Normal,
+                };
+
+                if key_event == config.keybindings.goto.top {
+                    self.go_up_or_down_in_data(Direction::Top);
+                } else if key_event == config.keybindings.goto.end {
+                    self.go_up_or_down_in_data(Direction::Bottom);
+                } else if key_event == config.keybindings.goto.middle {
+                    self.go_to_middle_of_frame(half_page);
+                }
+                return Ok(TransitionResult::Continue);
+            }
+            Mode::Scroll => {
+                // NOTE: any other key cancels the command
+                self.mode = Mode::Normal;
+
+                let anchor = if key_event == config.keybindings.scroll.top {
+                    Some(ScrollAnchor::Top)
+                } else if key_event == config.keybindings.scroll.center {
+                    Some(ScrollAnchor::Center)
+                } else if key_event == config.keybindings.scroll.bottom {
+                    Some(ScrollAnchor::Bottom)
+                } else {
+                    None
+                };
+                if let Some(anchor) = anchor {
+                    self.scroll_to_cursor(anchor, half_page);
+                }
+                return Ok(TransitionResult::Continue);
+            }
+            Mode::Yank => {
+                // NOTE: any other key cancels the command
+                self.mode = Mode::Normal;
+
+                if key_event == config.keybindings.yank.under {
+                    return Ok(TransitionResult::Return(self.value_at(&self.position)));
+                }
+                return Ok(TransitionResult::Continue);
+            }
+            Mode::Help => {
+                // NOTE: the help is clamped to its last row when rendered, see `help::render`
+                if key_event == config.keybindings.navigation.down {
+                    self.help_top = self.help_top.saturating_add(1);
+                } else if key_event == config.keybindings.navigation.up {
+                    self.help_top = self.help_top.saturating_sub(1);
+                } else {
+                    // NOTE: any other key closes the help, without doing anything else
+                    self.mode = Mode::Normal;
+                    self.help_top = 0;
+                }
+                return Ok(TransitionResult::Continue);
+            }
+            Mode::Searching => {
+                if key_event == config.keybindings.normal {
+                    self.mode = Mode::Normal;
+                    self.search.clear();
+                    return Ok(TransitionResult::Continue);
+                }
+
+                match key_event.code {
+                    KeyCode::Enter => {
+                        self.mode = Mode::Normal;
+                        self.go_to_search_match(Direction::Top);
+                    }
+                    KeyCode::Backspace => {
+                        self.search.pop();
+                    }
+                    KeyCode::Char(c) => self.search.push(c),
+                    _ => {}
+                }
+                return Ok(TransitionResult::Continue);
+            }
+            Mode::Command => {
+                if key_event == config.keybindings.normal {
+                    self.mode = Mode::Normal;
+                    return Ok(TransitionResult::Continue);
+                }
+
+                match key_event.code {
+                    KeyCode::Enter => {
+                        self.mode = Mode::Normal;
+                        let command = std::mem::take(&mut self.command);
+                        return Ok(self.run_command(&command));
+                    }
+                    KeyCode::Backspace => {
+                        // NOTE: like in Vim, erasing the empty command line goes back to NORMAL
+                        if self.command.pop().is_none() {
+                            self.mode = Mode::Normal;
+                        }
+                    }
+                    KeyCode::Char(c) => self.command.push(c),
+                    _ => {}
+                }
+                return Ok(TransitionResult::Continue);
+            }
             Mode::Insert => {
                 if key_event == config.keybindings.normal {
                     self.mode = Mode::Normal;
                     return Ok(TransitionResult::Continue);
                 }
 
-                match self.editor.handle_key(&key_event.code) {
-                    Ok(EditorTransition::Value(v)) => {
+                // NOTE: a buffer that can't be parsed keeps the editor open, see `Editor::handle_key`
+                match self.editor.handle_key(&key_event) {
+                    EditorTransition::Value(v) => {
                         self.mode = Mode::Normal;
                         return Ok(TransitionResult::Mutate(v, self.position.clone()));
                     }
-                    Ok(EditorTransition::Quit) => {
+                    EditorTransition::Quit => {
                         self.mode = Mode::Normal;
                         return Ok(TransitionResult::Continue);
                     }
-                    Ok(EditorTransition::Continue) => return Ok(TransitionResult::Continue),
-                    Err(err) => return Ok(TransitionResult::Error(err)),
+                    EditorTransition::Continue => return Ok(TransitionResult::Continue),
                 }
             }
             Mode::Peeking => {
                 if key_event == config.keybindings.quit {
-                    return Ok(TransitionResult::Quit);
+                    return Ok(self.quit());
                 } else if key_event == config.keybindings.normal {
                     self.mode = Mode::Normal;
                     return Ok(TransitionResult::Continue);
                 } else if key_event == config.keybindings.peeking.all {
                     return Ok(TransitionResult::Return(self.value.clone()));
                 } else if key_event == config.keybindings.peeking.view {
-                    self.position.members.pop();
-                    return Ok(TransitionResult::Return(self.value_under_cursor(None)));
+                    // NOTE: the items hidden by the filter, if any, are not part of the view
+                    let mut path = self.position.clone();
+                    if config.peek_view_is_parent {
+                        path.members.pop();
+                    }
+                    return Ok(TransitionResult::Return(self.view_at(&path)));
                 } else if key_event == config.keybindings.peeking.under {
-                    return Ok(TransitionResult::Return(self.value_under_cursor(None)));
+                    return Ok(TransitionResult::Return(self.value_at(&self.position)));
                 } else if key_event == config.keybindings.peeking.cell_path {
                     return Ok(TransitionResult::Return(Value::cell_path(
                         self.position.clone(),
                         Span::unknown(),
                     )));
+                } else if key_event == config.keybindings.peeking.cell_path_string {
+                    return Ok(TransitionResult::Return(Value::string(
+                        format_cell_path(&self.position.members),
+                        Span::unknown(),
+                    )));
+                } else if key_event == config.keybindings.peeking.nuon {
+                    let style = if config.peek_nuon_pretty {
+                        ToStyle::Spaces(4)
+                    } else {
+                        ToStyle::Raw
+                    };
+                    return match to_nuon(&self.value_at(&self.position), style, None) {
+                        Ok(nuon) => Ok(TransitionResult::Return(Value::string(
+                            nuon,
+                            Span::unknown(),
+                        ))),
+                        Err(err) => Ok(TransitionResult::Error(format!(
+                            "could not convert to NUON: {}",
+                            err
+                        ))),
+                    };
+                } else if key_event == config.keybindings.peeking.markdown {
+                    let mut path = self.position.clone();
+                    path.members.pop();
+
+                    // NOTE: the table is the one seen by the user, i.e. with the filter applied
+                    let view = self.view_at(&path);
+                    if !matches!(is_table(&view, !config.strict_tables), Table::IsValid) {
+                        return Ok(TransitionResult::Error(
+                            "only tables can be peeked as Markdown".into(),
+                        ));
+                    }
+
+                    // NOTE: a valid table is always a list of records
+                    let recs = view
+                        .as_list()
+                        .unwrap()
+                        .iter()
+                        .map(|v| v.as_record().unwrap().clone())
+                        .collect::<Vec<_>>();
+                    // NOTE: the peeked text is the full data, not what fits on screen
+                    let (columns, _, rows) = repr_table(&recs, ReprOptions::export());
+
+                    return Ok(TransitionResult::Return(Value::string(
+                        to_markdown(&columns, &rows),
+                        Span::unknown(),
+                    )));
+                } else if key_event == config.keybindings.peeking.trail {
+                    // NOTE: the first value is the whole data and the last one is under the cursor
+                    let members = &self.position.members;
+                    let trail = (0..=members.len())
+                        .map(|i| {
+                            self.value_at(&CellPath {
+                                members: members[..i].to_vec(),
+                            })
+                        })
+                        .collect();
+                    return Ok(TransitionResult::Return(Value::list(
+                        trail,
+                        Span::unknown(),
+                    )));
+                } else if key_event == config.keybindings.peeking.path {
+                    return match self.value_at(&self.position) {
+                        Value::String { val, .. }
+                            if SpecialString::parse(&val) == Some(SpecialString::Path) =>
+                        {
+                            Ok(TransitionResult::Return(stat_path(&val)))
+                        }
+                        _ => Ok(TransitionResult::Error(
+                            "only paths can have their metadata peeked".into(),
+                        )),
+                    };
+                } else if key_event == config.keybindings.peeking.yank_schema {
+                    return match copy_to_clipboard(&schema(&self.value_at(&self.position))) {
+                        Ok(()) => {
+                            self.mode = Mode::Normal;
+                            self.flash = Some("copied the schema to the clipboard".into());
+                            Ok(TransitionResult::Continue)
+                        }
+                        Err(err) => Ok(TransitionResult::Error(err)),
+                    };
+                } else if key_event == config.keybindings.peeking.ty {
+                    return Ok(TransitionResult::Return(Value::string(
+                        self.value_at(&self.position).get_type().to_string(),
+                        Span::unknown(),
+                    )));
+                } else if key_event == config.keybindings.peeking.clipboard {
+                    return match copy_to_clipboard(&self.value_at(&self.position)) {
+                        Ok(()) => {
+                            self.mode = Mode::Normal;
+                            self.flash = Some("copied to the clipboard".into());
+                            Ok(TransitionResult::Continue)
+                        }
+                        Err(err) => Ok(TransitionResult::Error(err)),
+                    };
+                } else if key_event == config.keybindings.peeking.write {
+                    self.mode = Mode::Writing;
+                    self.prompt = Prompt::new("Write to");
+                    return Ok(TransitionResult::Continue);
                 }
             }
+            Mode::Writing => match self.prompt.handle_key(&key_event.code) {
+                PromptTransition::Continue => return Ok(TransitionResult::Continue),
+                PromptTransition::Quit => {
+                    self.mode = Mode::Normal;
+                    return Ok(TransitionResult::Continue);
+                }
+                PromptTransition::Submit(path) => {
+                    // NOTE: on error, the path is kept to be fixed
+                    return match write_nuon(&self.value_at(&self.position), Path::new(&path)) {
+                        Ok(()) => {
+                            self.mode = Mode::Normal;
+                            self.flash = Some(format!("written to '{}'", path));
+                            Ok(TransitionResult::Continue)
+                        }
+                        Err(err) => Ok(TransitionResult::Error(err)),
+                    };
+                }
+            },
+            Mode::Filtering => match self.prompt.handle_key(&key_event.code) {
+                PromptTransition::Continue => return Ok(TransitionResult::Continue),
+                PromptTransition::Quit => {
+                    self.mode = Mode::Normal;
+                    return Ok(TransitionResult::Continue);
+                }
+                PromptTransition::Submit(query) => {
+                    self.mode = Mode::Normal;
+
+                    let mut path = self.position.clone();
+                    path.members.pop();
+
+                    // NOTE: a filter hiding all the items would leave the cursor nowhere
+                    let rows = filter_rows(&self.value_under_cursor(Some(path.clone())), &query);
+                    if rows.as_list().is_ok_and(|vals| vals.is_empty()) {
+                        self.flash = Some(format!("no row matching '{}'", query));
+                        return Ok(TransitionResult::Continue);
+                    }
+
+                    self.filter = Some((path, Filter::Query(query)));
+                    // NOTE: the item under the cursor might be hidden, in which case the cursor
+                    // goes to the first visible one
+                    self.go_up_or_down_in_data(Direction::At(self.selected_row()));
+                    return Ok(TransitionResult::Continue);
+                }
+            },
             Mode::Bottom => {
                 if key_event == config.keybindings.quit {
-                    return Ok(TransitionResult::Quit);
+                    return Ok(self.quit());
                 } else if key_event == config.keybindings.navigation.left {
                     self.mode = Mode::Normal;
                     return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.navigation.goto_root {
+                    self.go_to_root();
+                    return Ok(TransitionResult::Continue);
+                } else if key_event == config.keybindings.toggle_wrap {
+                    self.config.wrap_bottom_value = !self.config.wrap_bottom_value;
+                    return Ok(TransitionResult::Continue);
                 } else if key_event == config.keybindings.peek {
-                    return Ok(TransitionResult::Return(self.value_under_cursor(None)));
+                    return Ok(TransitionResult::Return(self.value_at(&self.position)));
                 }
             }
         }
@@ -227,28 +982,48 @@ pub fn repr_key(key: &KeyEvent) -> String {
         KeyCode::Enter => char::from_u32(0x23ce).unwrap().into(),
         KeyCode::Backspace => char::from_u32(0x232b).unwrap().into(),
         KeyCode::Delete => char::from_u32(0x2326).unwrap().into(),
+        KeyCode::Home => char::from_u32(0x21f1).unwrap().into(),
+        KeyCode::End => char::from_u32(0x21f2).unwrap().into(),
+        KeyCode::PageUp => "PgUp".into(),
+        KeyCode::PageDown => "PgDn".into(),
+        KeyCode::F(n) => format!("f{}", n),
         _ => "??".into(),
     };
 
-    match key.modifiers {
-        KeyModifiers::NONE => code,
-        KeyModifiers::CONTROL => format!("<c-{}>", code),
-        _ => "??".into(),
+    // NOTE: the shift is already in the case of the characters
+    let modifiers = key.modifiers - KeyModifiers::SHIFT;
+    if !(modifiers - KeyModifiers::CONTROL - KeyModifiers::ALT).is_empty() {
+        return "??".into();
+    }
+
+    let mut prefix = String::new();
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        prefix.push_str("c-");
+    }
+    if modifiers.contains(KeyModifiers::ALT) {
+        prefix.push_str("a-");
+    }
+
+    match (prefix.is_empty(), key.code) {
+        (true, KeyCode::F(_)) => format!("<{}>", code),
+        (true, _) => code,
+        (false, _) => format!("<{}{}>", prefix, code),
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crossterm::event::KeyEvent;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
     use nu_protocol::{
-        ast::{CellPath, PathMember},
-        record, Span, Value,
+        ast::{CellPath, PathMember, RangeInclusion},
+        record, Range, Span, Value,
     };
 
     use super::{repr_key, App, TransitionResult};
     use crate::{
-        app::Mode,
-        config::Config,
+        app::{Mode, JUMP_PREFIX_TIMEOUT_MS},
+        config::{Config, Layout, ReturnOnQuit},
+        navigation::Direction,
         nu::cell_path::{to_path_member_vec, PM},
     };
 
@@ -356,6 +1131,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn abort_with_ctrl_c() {
+        let abort = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+
+        let mut app = App::from_value(test_value());
+        app.editor.set_width(10);
+        app.handle_key_events(app.config.keybindings.insert, 0)
+            .unwrap();
+        assert_eq!(app.mode, Mode::Insert);
+        assert_eq!(
+            app.handle_key_events(abort, 0).unwrap(),
+            TransitionResult::Quit
+        );
+
+        // NOTE: `<c-c>` is left to the user when they bind it to something
+        let mut app = App::from_value(test_value());
+        assert!(!app.config.keybindings.is_bound(&abort));
+        app.config.keybindings.peek = abort;
+        assert!(app.config.keybindings.is_bound(&abort));
+        app.handle_key_events(abort, 0).unwrap();
+        assert_eq!(app.mode, Mode::Peeking);
+    }
+
     fn repr_path_member_vec(members: &[PathMember]) -> String {
         format!(
             "$.{}",
@@ -461,19 +1259,85 @@ mod tests {
         }
     }
 
-    fn run_peeking_scenario(
-        transitions: Vec<(KeyEvent, bool, Option<Value>)>,
-        config: Config,
-        value: Value,
-    ) {
-        let mut app = App::from_value(value).with_config(config);
-
-        for (key, exit, expected) in transitions {
-            let mode = app.mode.clone();
-
-            let result = app.handle_key_events(key, 0).unwrap();
+    #[test]
+    fn read_only_data() {
+        let value = test_value();
+        let mut app = App::from_value(value.clone());
+        app.config.editable = false;
+        let kmap = app.config.clone().keybindings;
 
-            if exit {
+        for key in [
+            kmap.insert,
+            kmap.transpose,
+            kmap.sort_ascending,
+            kmap.sort_descending,
+        ] {
+            assert_eq!(
+                app.handle_key_events(key, 0).unwrap(),
+                TransitionResult::Continue,
+                "expected no change after pressing {}",
+                repr_key(&key)
+            );
+            assert_eq!(app.mode, Mode::Normal);
+            assert!(app.flash.is_some());
+        }
+        assert_eq!(app.value, value);
+    }
+
+    #[test]
+    fn go_back_to_the_root() {
+        let mut app = App::from_value(test_value());
+        let nav = app.config.clone().keybindings.navigation;
+
+        let transitions = vec![
+            (nav.goto_root, vec![PM::S("l")], false),
+            (nav.right, vec![PM::S("l"), PM::I(0)], false),
+            (nav.down, vec![PM::S("l"), PM::I(1)], false),
+            (nav.goto_root, vec![PM::S("l")], false),
+            (nav.down, vec![PM::S("r")], false),
+            (nav.right, vec![PM::S("r"), PM::S("a")], false),
+            (nav.right, vec![PM::S("r"), PM::S("a")], true),
+            (nav.goto_root, vec![PM::S("r")], false),
+            (nav.down, vec![PM::S("s")], false),
+            (nav.right, vec![PM::S("s")], true),
+            (nav.goto_root, vec![PM::S("s")], false),
+        ];
+
+        for (key, cell_path, bottom) in transitions {
+            let expected = to_path_member_vec(&cell_path);
+            app.handle_key_events(key, 0).unwrap();
+
+            assert_eq!(
+                app.is_at_bottom(),
+                bottom,
+                "unexpected bottom state after pressing {}",
+                repr_key(&key)
+            );
+            assert_eq!(
+                app.position.members,
+                expected,
+                "expected to be at {:?}, found {:?}",
+                repr_path_member_vec(&expected),
+                repr_path_member_vec(&app.position.members)
+            );
+            assert!(app.rendering_tops.len() <= app.position.members.len() + 1);
+        }
+        assert_eq!(app.rendering_tops.len(), 1);
+    }
+
+    fn run_peeking_scenario(
+        transitions: Vec<(KeyEvent, bool, Option<Value>)>,
+        config: Config,
+        value: Value,
+    ) {
+        let mut app = App::from_value(value).with_config(config);
+
+        for (key, exit, expected) in transitions {
+            let mode = app.mode.clone();
+
+            let result = app.handle_key_events(key, 0).unwrap();
+
+            if exit {
                 assert!(
                     result.is_quit(),
                     "expected to peek some data after pressing {} in {} mode",
@@ -604,6 +1468,352 @@ mod tests {
         run_peeking_scenario(peek_at_the_bottom, config.clone(), value);
     }
 
+    #[test]
+    fn peek_on_enter() {
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+
+        let mut app = App::from_value(test_value());
+        app.handle_key_events(app.config.keybindings.navigation.down, 0)
+            .unwrap();
+        assert_eq!(
+            app.handle_key_events(enter, 0).unwrap(),
+            TransitionResult::Continue
+        );
+
+        app.config.enter_peeks = true;
+        assert_eq!(
+            app.handle_key_events(enter, 0).unwrap(),
+            TransitionResult::Return(app.value_under_cursor(None))
+        );
+
+        // NOTE: <enter> only peeks in NORMAL mode
+        app.handle_key_events(KeyEvent::new(KeyCode::Char('1'), KeyModifiers::NONE), 0)
+            .unwrap();
+        assert_eq!(
+            app.handle_key_events(enter, 0).unwrap(),
+            TransitionResult::Continue
+        );
+        assert_eq!(app.mode, Mode::Waiting(1));
+    }
+
+    #[test]
+    fn peek_markdown() {
+        let mut app = App::from_value(Value::test_list(vec![
+            Value::test_record(
+                record! { "a" => Value::test_int(1), "b" => Value::test_string("x") },
+            ),
+            Value::test_record(
+                record! { "a" => Value::test_int(2), "b" => Value::test_string("y|z") },
+            ),
+        ]));
+        let kmap = app.config.keybindings.clone();
+
+        app.handle_key_events(kmap.peek, 0).unwrap();
+        assert_eq!(
+            app.handle_key_events(kmap.peeking.markdown, 0).unwrap(),
+            TransitionResult::Return(Value::test_string(
+                "| a | b |\n| --- | --- |\n| 1 | x |\n| 2 | y\\|z |"
+            ))
+        );
+
+        // the cells are neither truncated nor replaced by placeholders
+        let long = "x".repeat(100);
+        let mut app = App::from_value(Value::test_list(vec![Value::test_record(record! {
+            "a" => Value::test_string(&long),
+            "b" => Value::test_nothing(),
+            "c" => Value::test_string(""),
+        })]));
+        app.config.preview_max_length = 10;
+
+        app.handle_key_events(kmap.peek, 0).unwrap();
+        assert_eq!(
+            app.handle_key_events(kmap.peeking.markdown, 0).unwrap(),
+            TransitionResult::Return(Value::test_string(format!(
+                "| a | b | c |\n| --- | --- | --- |\n| {} |  |  |",
+                long
+            )))
+        );
+
+        // only tables can be peeked as Markdown
+        let mut app = App::from_value(test_value());
+        app.handle_key_events(kmap.peek, 0).unwrap();
+        assert!(matches!(
+            app.handle_key_events(kmap.peeking.markdown, 0).unwrap(),
+            TransitionResult::Error(_)
+        ));
+    }
+
+    #[test]
+    fn peek_path() {
+        let mut app = App::from_value(Value::test_list(vec![
+            Value::test_string("foo"),
+            Value::test_string("/some/missing/path"),
+        ]));
+        let kmap = app.config.keybindings.clone();
+
+        app.handle_key_events(kmap.peek, 0).unwrap();
+        assert!(matches!(
+            app.handle_key_events(kmap.peeking.path, 0).unwrap(),
+            TransitionResult::Error(_)
+        ));
+
+        for key in [kmap.normal, kmap.navigation.down, kmap.peek] {
+            app.handle_key_events(key, 0).unwrap();
+        }
+        assert_eq!(
+            app.handle_key_events(kmap.peeking.path, 0).unwrap(),
+            TransitionResult::Return(Value::test_record(record! {
+                "path" => Value::test_string("/some/missing/path"),
+                "exists" => Value::test_bool(false),
+                "is_dir" => Value::test_bool(false),
+                "size" => Value::test_nothing(),
+            }))
+        );
+    }
+
+    #[test]
+    fn peek_trail() {
+        let inner = Value::test_record(record! { "b" => Value::test_int(1) });
+        let list = Value::test_list(vec![Value::test_int(0), inner.clone()]);
+        let value = Value::test_record(record! { "a" => list.clone() });
+        let mut app = App::from_value(value.clone());
+        let kmap = app.config.keybindings.clone();
+
+        for key in [
+            kmap.navigation.right,
+            kmap.navigation.down,
+            kmap.navigation.right,
+            kmap.peek,
+        ] {
+            app.handle_key_events(key, 0).unwrap();
+        }
+        assert_eq!(
+            app.handle_key_events(kmap.peeking.trail, 0).unwrap(),
+            TransitionResult::Return(Value::test_list(vec![
+                value,
+                list,
+                inner,
+                Value::test_int(1)
+            ]))
+        );
+    }
+
+    #[test]
+    fn peek_view() {
+        let inner = Value::test_list(vec![Value::test_int(1), Value::test_int(2)]);
+        let value = Value::test_list(vec![
+            Value::test_string("a"),
+            inner.clone(),
+            Value::test_string("c"),
+        ]);
+
+        for (is_parent, expected) in [(true, value.clone()), (false, inner)] {
+            let mut app = App::from_value(value.clone());
+            app.config.peek_view_is_parent = is_parent;
+            let kmap = app.config.keybindings.clone();
+
+            app.handle_key_events(kmap.navigation.down, 0).unwrap();
+            app.handle_key_events(kmap.peek, 0).unwrap();
+            assert_eq!(
+                app.handle_key_events(kmap.peeking.view, 0).unwrap(),
+                TransitionResult::Return(expected),
+                "peek_view_is_parent: {}",
+                is_parent
+            );
+        }
+    }
+
+    #[test]
+    fn peek_cell_path_string() {
+        let mut app = App::from_value(Value::test_record(record! {
+            "a" => Value::test_list(vec![
+                Value::test_record(record! { "b" => Value::test_int(1) }),
+                Value::test_record(record! { "b" => Value::test_int(2), "c" => Value::test_int(3) }),
+            ]),
+        }));
+        let kmap = app.config.keybindings.clone();
+
+        for key in [
+            kmap.navigation.right,
+            kmap.navigation.down,
+            kmap.navigation.right,
+            kmap.navigation.down,
+            kmap.peek,
+        ] {
+            app.handle_key_events(key, 0).unwrap();
+        } // on $.a.1.c
+
+        assert_eq!(
+            app.handle_key_events(kmap.peeking.cell_path_string, 0)
+                .unwrap(),
+            TransitionResult::Return(Value::test_string("$.a.1.c"))
+        );
+    }
+
+    #[test]
+    fn peek_nuon() {
+        for pretty in [false, true] {
+            let mut app = App::from_value(test_value());
+            app.config.peek_nuon_pretty = pretty;
+            let config = app.config.clone();
+
+            app.handle_key_events(config.keybindings.navigation.down, 0)
+                .unwrap(); // on {r: {a: 1, b: 2}}
+            app.handle_key_events(config.keybindings.peek, 0).unwrap();
+            let result = app
+                .handle_key_events(config.keybindings.peeking.nuon, 0)
+                .unwrap();
+
+            let nuon = match result {
+                TransitionResult::Return(Value::String { val, .. }) => val,
+                x => panic!("expected a NUON string to be peeked, found {:?}", x),
+            };
+            assert_eq!(nuon.contains('\n'), pretty);
+            assert_eq!(
+                nuon::from_nuon(&nuon, None).unwrap(),
+                Value::test_record(record! {
+                    "a" => Value::test_int(1),
+                    "b" => Value::test_int(2),
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn peek_type() {
+        let mut app = App::from_value(test_value());
+        let config = app.config.clone();
+
+        for _ in 0..3 {
+            app.handle_key_events(config.keybindings.navigation.down, 0)
+                .unwrap();
+        } // on {i: 123}
+        app.handle_key_events(config.keybindings.peek, 0).unwrap();
+
+        assert_eq!(
+            app.handle_key_events(config.keybindings.peeking.ty, 0)
+                .unwrap(),
+            TransitionResult::Return(Value::test_string("int"))
+        );
+    }
+
+    #[test]
+    fn return_on_quit() {
+        for (return_on_quit, expected) in [
+            (ReturnOnQuit::Nothing, TransitionResult::Quit),
+            (
+                ReturnOnQuit::MutatedValue,
+                TransitionResult::Return(Value::test_list(vec![Value::test_int(2)])),
+            ),
+        ] {
+            let mut app = App::from_value(Value::test_list(vec![Value::test_int(1)]));
+            app.config.return_on_quit = return_on_quit;
+            let kmap = app.config.keybindings.clone();
+
+            let position = app.position.clone();
+            app.mutate(&Value::test_int(2), &position, position.clone());
+
+            assert_eq!(app.handle_key_events(kmap.quit, 0).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn show_and_close_the_help() {
+        let mut app = App::from_value(test_value());
+        let config = app.config.clone();
+
+        app.handle_key_events(config.keybindings.help, 0).unwrap();
+        assert_eq!(app.mode, Mode::Help);
+
+        // NOTE: the help scrolls instead of closing
+        for key in [
+            config.keybindings.navigation.down,
+            config.keybindings.navigation.down,
+            config.keybindings.navigation.up,
+        ] {
+            app.handle_key_events(key, 0).unwrap();
+            assert_eq!(app.mode, Mode::Help);
+        }
+        assert_eq!(app.help_top, 1);
+
+        // NOTE: quitting is just closing the help here
+        assert_eq!(
+            app.handle_key_events(config.keybindings.quit, 0).unwrap(),
+            TransitionResult::Continue
+        );
+        assert_eq!(app.mode, Mode::Normal);
+        assert_eq!(app.help_top, 0);
+    }
+
+    #[test]
+    fn explore_the_keys_of_a_record() {
+        let value = Value::test_record(record!(
+            "a" => Value::test_int(1),
+            "b" => Value::test_int(2),
+            "c" => Value::test_int(3),
+        ));
+        let mut app = App::from_value(value.clone());
+        let kmap = app.config.clone().keybindings;
+
+        app.handle_key_events(kmap.navigation.down, 0).unwrap();
+        app.handle_key_events(kmap.keys, 0).unwrap();
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(1)]));
+        assert_eq!(app.value_under_cursor(None), Value::test_string("b"));
+
+        // NOTE: the keys can't be changed
+        app.handle_key_events(kmap.transpose, 0).unwrap();
+        assert!(app.flash.is_some());
+
+        app.handle_key_events(kmap.peek, 0).unwrap();
+        assert_eq!(
+            app.handle_key_events(kmap.peeking.view, 0).unwrap(),
+            TransitionResult::Return(Value::test_list(vec![
+                Value::test_string("a"),
+                Value::test_string("b"),
+                Value::test_string("c"),
+            ]))
+        );
+        assert_eq!(app.value, value, "the data should not be modified");
+
+        let mut app = App::from_value(value.clone());
+        for key in [kmap.keys, kmap.navigation.down, kmap.navigation.down] {
+            app.handle_key_events(key, 0).unwrap();
+        }
+        app.handle_key_events(kmap.navigation.left, 0).unwrap();
+        assert_eq!(app.keys, None);
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("c")]));
+        assert_eq!(app.value_under_cursor(None), Value::test_int(3));
+
+        // only the keys of a record can be explored
+        let mut app = App::from_value(Value::test_list(vec![Value::test_int(1)]));
+        assert!(matches!(
+            app.handle_key_events(kmap.keys, 0).unwrap(),
+            TransitionResult::Error(_)
+        ));
+    }
+
+    #[test]
+    fn transpose_nothing() {
+        for value in [
+            Value::test_int(1),
+            Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+        ] {
+            let mut app = App::from_value(value.clone());
+            let position = app.position.clone();
+
+            let result = app
+                .handle_key_events(app.config.keybindings.transpose, 0)
+                .unwrap();
+            assert!(
+                matches!(&result, TransitionResult::Error(err) if err.contains("transposed")),
+                "{:?}",
+                result
+            );
+            assert_eq!(app.position, position);
+            assert_eq!(app.value, value);
+        }
+    }
+
     #[test]
     fn transpose_the_data() {
         let mut app = App::from_value(Value::test_record(record!(
@@ -644,4 +1854,1149 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn sort_the_data() {
+        let mut app = App::from_value(Value::test_record(record!(
+            "b" => Value::test_list(vec![
+                Value::test_int(3),
+                Value::test_int(1),
+                Value::test_int(2),
+            ]),
+            "a" => Value::test_list(vec![
+                Value::test_record(record! { "x" => Value::test_int(2), "y" => Value::test_int(1) }),
+                Value::test_record(record! { "x" => Value::test_int(1), "y" => Value::test_int(2) }),
+            ]),
+        )));
+        let kmap = app.config.clone().keybindings;
+
+        let transitions = vec![
+            (kmap.sort_ascending, vec![PM::S("b")]),
+            (kmap.navigation.right, vec![PM::S("b"), PM::I(0)]),
+            (kmap.sort_descending, vec![PM::S("b"), PM::I(0)]),
+            (kmap.navigation.left, vec![PM::S("b")]),
+            (kmap.navigation.up, vec![PM::S("a")]),
+            (kmap.navigation.right, vec![PM::S("a"), PM::I(0)]),
+            (kmap.sort_ascending, vec![PM::S("a"), PM::I(0)]),
+            (
+                kmap.navigation.right,
+                vec![PM::S("a"), PM::I(0), PM::S("x")],
+            ),
+            (kmap.navigation.down, vec![PM::S("a"), PM::I(0), PM::S("y")]),
+            (kmap.sort_ascending, vec![PM::S("a"), PM::I(0), PM::S("y")]),
+        ];
+
+        for (key, cell_path) in transitions {
+            let expected = to_path_member_vec(&cell_path);
+            if let TransitionResult::Mutate(cell, path) = app.handle_key_events(key, 0).unwrap() {
+                app.value = crate::nu::value::mutate_value_cell(&app.value, &path, &cell).unwrap()
+            }
+
+            assert_eq!(
+                app.position.members,
+                expected,
+                "expected to be at {:?}, found {:?}",
+                repr_path_member_vec(&expected),
+                repr_path_member_vec(&app.position.members)
+            );
+        }
+
+        assert_eq!(
+            app.value,
+            Value::test_record(record!(
+                "a" => Value::test_list(vec![
+                    Value::test_record(record! { "x" => Value::test_int(2), "y" => Value::test_int(1) }),
+                    Value::test_record(record! { "x" => Value::test_int(1), "y" => Value::test_int(2) }),
+                ]),
+                "b" => Value::test_list(vec![
+                    Value::test_int(3),
+                    Value::test_int(2),
+                    Value::test_int(1),
+                ]),
+            ))
+        );
+        assert_eq!(
+            app.value.as_record().unwrap().columns().collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+    }
+
+    #[test]
+    fn filter_by_shape() {
+        let mut app = App::from_value(Value::test_list(vec![
+            Value::test_string("a"),
+            Value::test_record(record! { "a" => Value::test_int(1) }),
+            Value::test_string("b"),
+            Value::test_record(record! { "b" => Value::test_int(2) }),
+        ]));
+        let kmap = app.config.clone().keybindings;
+        let root = CellPath { members: vec![] };
+
+        app.handle_key_events(kmap.filter_shape, 0).unwrap();
+        assert_eq!(
+            app.view_at(&root),
+            Value::test_list(vec![Value::test_string("a"), Value::test_string("b")]),
+            "the records should be hidden after filtering by the shape of a string"
+        );
+
+        let transitions = vec![
+            (kmap.navigation.down, vec![PM::I(2)]),
+            (kmap.navigation.down, vec![PM::I(2)]),
+            (kmap.navigation.up, vec![PM::I(0)]),
+            (kmap.clear_filter, vec![PM::I(0)]),
+            (kmap.navigation.down, vec![PM::I(1)]),
+        ];
+
+        for (key, cell_path) in transitions {
+            let expected = to_path_member_vec(&cell_path);
+            app.handle_key_events(key, 0).unwrap();
+
+            assert_eq!(
+                app.position.members,
+                expected,
+                "expected to be at {:?}, found {:?}",
+                repr_path_member_vec(&expected),
+                repr_path_member_vec(&app.position.members)
+            );
+        }
+
+        assert_eq!(app.view_at(&root), app.value);
+    }
+
+    #[test]
+    fn filter_by_query() {
+        let value = Value::test_list(vec![
+            Value::test_record(record! { "name" => Value::test_string("foo") }),
+            Value::test_record(record! { "name" => Value::test_string("bar") }),
+            Value::test_record(record! { "name" => Value::test_string("baz") }),
+        ]);
+        let mut app = App::from_value(value.clone());
+        let kmap = app.config.clone().keybindings;
+        let root = CellPath { members: vec![] };
+
+        let type_query = |app: &mut App, query: &str| {
+            app.handle_key_events(kmap.filter, 0).unwrap();
+            assert_eq!(app.mode, Mode::Filtering);
+            for c in query.chars() {
+                app.handle_key_events(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE), 0)
+                    .unwrap();
+            }
+            app.handle_key_events(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), 0)
+                .unwrap();
+            assert_eq!(app.mode, Mode::Normal);
+        };
+
+        // the cursor leaves the hidden row under it
+        type_query(&mut app, "ba");
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(1)]));
+        assert_eq!(
+            app.view_at(&root),
+            Value::test_list(vec![
+                Value::test_record(record! { "name" => Value::test_string("bar") }),
+                Value::test_record(record! { "name" => Value::test_string("baz") }),
+            ])
+        );
+        assert_eq!(app.value, value, "the data should not be modified");
+
+        // a query matching nothing does not change the filter
+        type_query(&mut app, "nope");
+        assert_eq!(app.flash, Some("no row matching 'nope'".into()));
+        assert_eq!(app.view_at(&root).as_list().unwrap().len(), 2);
+
+        app.handle_key_events(kmap.peek, 0).unwrap();
+        let mut peek_view = app.clone();
+        assert_eq!(
+            peek_view.handle_key_events(kmap.peeking.view, 0).unwrap(),
+            TransitionResult::Return(app.view_at(&root))
+        );
+        assert_eq!(
+            app.handle_key_events(kmap.peeking.all, 0).unwrap(),
+            TransitionResult::Return(value)
+        );
+
+        app.handle_key_events(kmap.normal, 0).unwrap();
+        app.handle_key_events(kmap.clear_filter, 0).unwrap();
+        assert_eq!(app.view_at(&root), app.value);
+
+        let mut app = App::from_value(Value::test_record(record! { "a" => Value::test_int(1) }));
+        assert!(matches!(
+            app.handle_key_events(kmap.filter, 0).unwrap(),
+            TransitionResult::Error(_)
+        ));
+        assert_eq!(app.mode, Mode::Normal);
+    }
+
+    #[test]
+    fn jump_between_collections() {
+        let mut app = App::from_value(Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_record(record! { "x" => Value::test_int(2) }),
+            "c" => Value::test_string("foo"),
+            "d" => Value::test_list(vec![]),
+            "e" => Value::test_bool(true),
+        }));
+        let nav = app.config.keybindings.navigation.clone();
+
+        let strokes = vec![
+            (nav.next_collection, "b"),
+            (nav.next_collection, "d"),
+            // NOTE: the jumps wrap around
+            (nav.next_collection, "b"),
+            (nav.previous_collection, "d"),
+            (nav.down, "e"),
+            (nav.previous_collection, "d"),
+            (nav.previous_collection, "b"),
+        ];
+        for (key, expected) in strokes {
+            app.handle_key_events(key, 0).unwrap();
+            assert_eq!(
+                app.position.members,
+                to_path_member_vec(&[PM::S(expected)]),
+                "after {:?}",
+                key
+            );
+        }
+
+        let mut app = App::from_value(Value::test_list(vec![Value::test_int(1)]));
+        app.handle_key_events(nav.next_collection, 0).unwrap();
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(0)]));
+        assert!(app.flash.is_some());
+    }
+
+    #[test]
+    fn search_the_data() {
+        let mut app = App::from_value(Value::test_record(record! {
+            "foo" => Value::test_int(1),
+            "bar" => Value::test_string("baz"),
+            "qux" => Value::test_string("foobar"),
+            "r" => Value::test_record(record! { "foo" => Value::test_int(2) }),
+        }));
+        let nav = app.config.clone().keybindings.navigation;
+        let normal = app.config.keybindings.normal;
+
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let char = |c: char| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let backspace = KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE);
+
+        let transitions = vec![
+            (nav.down, vec![PM::S("bar")], false),
+            // an empty query is a no-op
+            (nav.search, vec![PM::S("bar")], false),
+            (enter, vec![PM::S("bar")], false),
+            // a query that matches nothing does not move the cursor
+            (nav.search, vec![PM::S("bar")], false),
+            (char('y'), vec![PM::S("bar")], false),
+            (enter, vec![PM::S("bar")], true),
+            // the first match is in the name of the first row
+            (nav.search, vec![PM::S("bar")], false),
+            (char('f'), vec![PM::S("bar")], false),
+            (char('x'), vec![PM::S("bar")], false),
+            (backspace, vec![PM::S("bar")], false),
+            (char('o'), vec![PM::S("bar")], false),
+            (enter, vec![PM::S("foo")], false),
+            // the data is searched as well
+            (nav.next_match, vec![PM::S("qux")], false),
+            (nav.next_match, vec![PM::S("foo")], false),
+            (nav.previous_match, vec![PM::S("qux")], false),
+            (nav.previous_match, vec![PM::S("foo")], false),
+            // escaping does not move the cursor
+            (nav.search, vec![PM::S("foo")], false),
+            (char('b'), vec![PM::S("foo")], false),
+            (normal, vec![PM::S("foo")], false),
+        ];
+
+        for (key, cell_path, flash) in transitions {
+            let expected = to_path_member_vec(&cell_path);
+            app.handle_key_events(key, 0).unwrap();
+
+            assert_eq!(
+                app.position.members,
+                expected,
+                "expected to be at {:?}, found {:?}",
+                repr_path_member_vec(&expected),
+                repr_path_member_vec(&app.position.members)
+            );
+            assert_eq!(
+                app.flash.is_some(),
+                flash,
+                "unexpected flash message after pressing {}: {:?}",
+                repr_key(&key),
+                app.flash
+            );
+        }
+        assert_eq!(app.mode, Mode::Normal);
+
+        // the data is searched as it is shown, e.g. with the placeholder of null values
+        let mut app = App::from_value(Value::test_list(vec![
+            Value::test_int(1),
+            Value::test_nothing(),
+        ]));
+        app.config.null_placeholder = "nil".into();
+        for key in [nav.search, char('n'), char('i'), char('l'), enter] {
+            app.handle_key_events(key, 0).unwrap();
+        }
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(1)]));
+    }
+
+    #[test]
+    fn type_a_command() {
+        let mut app = App::from_value(test_value());
+        let config = app.config.clone();
+
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let char = |c: char| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let backspace = KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE);
+
+        // erasing the empty command goes back to NORMAL mode
+        app.handle_key_events(config.keybindings.command, 0)
+            .unwrap();
+        assert_eq!(app.mode, Mode::Command);
+        app.handle_key_events(backspace, 0).unwrap();
+        assert_eq!(app.mode, Mode::Normal);
+
+        app.handle_key_events(config.keybindings.command, 0)
+            .unwrap();
+        for c in "layout compactx".chars() {
+            app.handle_key_events(char(c), 0).unwrap();
+        }
+        app.handle_key_events(backspace, 0).unwrap();
+        assert_eq!(app.command, "layout compact");
+        assert_eq!(
+            app.handle_key_events(enter, 0).unwrap(),
+            TransitionResult::Continue
+        );
+        assert_eq!(app.mode, Mode::Normal);
+        assert_eq!(app.config.layout, Layout::Compact);
+
+        app.handle_key_events(config.keybindings.command, 0)
+            .unwrap();
+        app.handle_key_events(char('x'), 0).unwrap();
+        assert!(matches!(
+            app.handle_key_events(enter, 0).unwrap(),
+            TransitionResult::Error(_)
+        ));
+
+        app.handle_key_events(config.keybindings.command, 0)
+            .unwrap();
+        app.handle_key_events(char('q'), 0).unwrap();
+        assert!(app.handle_key_events(enter, 0).unwrap().is_quit());
+    }
+
+    #[test]
+    fn toggle_line_numbers() {
+        let mut app = App::from_value(test_value());
+        let config = app.config.clone();
+
+        let run = |app: &mut App, line: &str| {
+            app.handle_key_events(config.keybindings.command, 0)
+                .unwrap();
+            for c in line.chars() {
+                app.handle_key_events(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE), 0)
+                    .unwrap();
+            }
+            app.handle_key_events(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), 0)
+                .unwrap();
+            (app.config.number, app.config.relativenumber)
+        };
+
+        app.config.number = false;
+        app.config.relativenumber = false;
+
+        assert_eq!(run(&mut app, "set number"), (true, false));
+        assert_eq!(run(&mut app, "set number"), (true, false));
+        // both give hybrid numbers
+        assert_eq!(run(&mut app, "set relativenumber!"), (true, true));
+        assert_eq!(run(&mut app, "set nonumber"), (false, true));
+        assert_eq!(run(&mut app, "set rnu!"), (false, false));
+        assert_eq!(run(&mut app, "set nu!"), (true, false));
+    }
+
+    #[test]
+    fn jump_to_a_prefix() {
+        let mut app = App::from_value(Value::test_record(record! {
+            "apple" => Value::test_int(1),
+            "banana" => Value::test_int(2),
+            "berry" => Value::test_int(3),
+            "cherry" => Value::test_int(4),
+        }));
+        let nav = app.config.clone().keybindings.navigation;
+        let char = |c: char| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+
+        let transitions = vec![
+            (char('b'), "b", vec![PM::S("banana")]),
+            (char('e'), "be", vec![PM::S("berry")]),
+            // nothing starts with the prefix
+            (char('x'), "bex", vec![PM::S("berry")]),
+            // a bound key forgets the prefix
+            (nav.up, "", vec![PM::S("banana")]),
+            (char('c'), "c", vec![PM::S("cherry")]),
+            (char('a'), "ca", vec![PM::S("cherry")]),
+        ];
+
+        for (key, prefix, expected) in transitions {
+            app.handle_key_events(key, 0).unwrap();
+            assert_eq!(app.jump_prefix, prefix, "after {}", repr_key(&key));
+            assert_eq!(
+                app.position.members,
+                to_path_member_vec(&expected),
+                "after {}",
+                repr_key(&key)
+            );
+        }
+
+        // the prefix is forgotten after a while
+        for _ in 0..JUMP_PREFIX_TIMEOUT_MS / app.config.tick_rate_ms {
+            assert_eq!(app.jump_prefix, "ca");
+            app.tick();
+        }
+        assert_eq!(app.jump_prefix, "");
+        app.handle_key_events(char('a'), 0).unwrap();
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("apple")]));
+
+        // the rows are compared as they are shown, e.g. with the placeholder of null values
+        let mut app = App::from_value(Value::test_list(vec![
+            Value::test_int(1),
+            Value::test_nothing(),
+        ]));
+        app.config.null_placeholder = "absent".into();
+        app.handle_key_events(char('a'), 0).unwrap();
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(1)]));
+    }
+
+    #[test]
+    fn represent_keys() {
+        #[rustfmt::skip]
+        let cases = vec![
+            (KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE), "x"),
+            (KeyEvent::new(KeyCode::Char('X'), KeyModifiers::SHIFT), "X"),
+            (KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL), "<c-d>"),
+            (KeyEvent::new(KeyCode::Char('x'), KeyModifiers::ALT), "<a-x>"),
+            (KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL | KeyModifiers::ALT), "<c-a-x>"),
+            (KeyEvent::new(KeyCode::F(1), KeyModifiers::NONE), "<f1>"),
+            (KeyEvent::new(KeyCode::F(5), KeyModifiers::CONTROL), "<c-f5>"),
+            (KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE), "<esc>"),
+            (KeyEvent::new(KeyCode::Char('x'), KeyModifiers::SUPER), "??"),
+        ];
+
+        for (key, expected) in cases {
+            assert_eq!(repr_key(&key), expected);
+        }
+    }
+
+    #[test]
+    fn navigate_with_the_usual_keys() {
+        let mut app = App::from_value(Value::test_record(record! {
+            "l" => Value::test_list((0..10).map(Value::test_int).collect()),
+        }));
+        app.go_deeper_in_data();
+        let key = |code: KeyCode| KeyEvent::new(code, KeyModifiers::NONE);
+
+        let transitions = vec![
+            (key(KeyCode::End), vec![PM::S("l"), PM::I(9)]),
+            (key(KeyCode::Home), vec![PM::S("l"), PM::I(0)]),
+            (key(KeyCode::PageDown), vec![PM::S("l"), PM::I(3)]),
+            (key(KeyCode::PageDown), vec![PM::S("l"), PM::I(6)]),
+            (key(KeyCode::PageUp), vec![PM::S("l"), PM::I(3)]),
+            (
+                KeyEvent::new(KeyCode::Home, KeyModifiers::CONTROL),
+                vec![PM::S("l")],
+            ),
+        ];
+
+        for (key, expected) in transitions {
+            app.handle_key_events(key, 3).unwrap();
+            assert_eq!(
+                app.position.members,
+                to_path_member_vec(&expected),
+                "after {}",
+                repr_key(&key)
+            );
+        }
+    }
+
+    #[test]
+    fn goto_motions() {
+        let mut app = App::from_value(Value::test_list((0..10).map(Value::test_int).collect()));
+        let kmap = app.config.clone().keybindings;
+        let char = |c: char| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+
+        let transitions = vec![
+            (vec![char('g'), char('e')], vec![PM::I(9)], Mode::Normal),
+            (vec![char('g'), char('g')], vec![PM::I(0)], Mode::Normal),
+            // the middle of the 6 rows of the frame
+            (vec![char('g'), char('m')], vec![PM::I(2)], Mode::Normal),
+            (vec![char('g')], vec![PM::I(2)], Mode::Goto(None)),
+            // any other key cancels the motion
+            (vec![char('x')], vec![PM::I(2)], Mode::Normal),
+            // the selection is kept in VISUAL mode
+            (
+                vec![kmap.visual, char('g'), char('e')],
+                vec![PM::I(9)],
+                Mode::Visual(2),
+            ),
+            (vec![char('g'), char('g')], vec![PM::I(0)], Mode::Visual(2)),
+        ];
+
+        for (keys, expected, mode) in transitions {
+            for key in &keys {
+                app.handle_key_events(*key, 3).unwrap();
+            }
+            let keys = keys.iter().map(repr_key).collect::<String>();
+            assert_eq!(
+                app.position.members,
+                to_path_member_vec(&expected),
+                "after {}",
+                keys
+            );
+            assert_eq!(app.mode, mode, "after {}", keys);
+        }
+    }
+
+    #[test]
+    fn scroll_around_the_cursor() {
+        let mut app = App::from_value(Value::test_list((0..100).map(Value::test_int).collect()));
+        app.config.margin = 2;
+        app.go_up_or_down_in_data(Direction::At(50));
+        let char = |c: char| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+
+        // NOTE: the frame is 2 * 10 rows high
+        #[rustfmt::skip]
+        let cases = vec![
+            (char('t'), 48),
+            (char('z'), 41),
+            (char('b'), 33),
+        ];
+
+        for (key, expected) in cases {
+            app.handle_key_events(char('z'), 10).unwrap();
+            assert_eq!(app.mode, Mode::Scroll);
+            app.handle_key_events(key, 10).unwrap();
+
+            assert_eq!(app.mode, Mode::Normal);
+            assert_eq!(
+                app.rendering_tops,
+                vec![expected],
+                "after z{}",
+                repr_key(&key)
+            );
+            assert_eq!(app.selected_row(), 50, "the cursor should not move");
+        }
+
+        // any other key cancels the command
+        app.handle_key_events(char('z'), 10).unwrap();
+        app.handle_key_events(char('x'), 10).unwrap();
+        assert_eq!(app.mode, Mode::Normal);
+        assert_eq!(app.rendering_tops, vec![33]);
+    }
+
+    #[test]
+    fn scroll_by_a_fixed_amount() {
+        let mut app = App::from_value(Value::test_list((0..100).map(Value::test_int).collect()));
+        let kmap = app.config.keybindings.clone();
+
+        app.handle_key_events(kmap.navigation.half_page_down, 10)
+            .unwrap();
+        assert_eq!(app.selected_row(), 10, "half of the frame by default");
+
+        app.config.scroll_amount = Some(3);
+        app.handle_key_events(kmap.navigation.half_page_down, 10)
+            .unwrap();
+        assert_eq!(app.selected_row(), 13);
+        app.handle_key_events(kmap.navigation.half_page_up, 10)
+            .unwrap();
+        app.handle_key_events(kmap.navigation.half_page_up, 10)
+            .unwrap();
+        assert_eq!(app.selected_row(), 7);
+    }
+
+    #[test]
+    fn scroll_with_the_mouse() {
+        let mut app = App::from_value(Value::test_list(
+            (0..5).map(Value::test_int).collect::<Vec<_>>(),
+        ));
+        let scroll = |kind| MouseEvent {
+            kind,
+            column: 0,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        };
+
+        app.handle_mouse_events(scroll(MouseEventKind::ScrollDown));
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(3)]));
+        app.handle_mouse_events(scroll(MouseEventKind::ScrollDown));
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(4)]));
+        app.handle_mouse_events(scroll(MouseEventKind::ScrollUp));
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(1)]));
+
+        // the mouse is ignored outside of NORMAL mode
+        app.mode = Mode::Peeking;
+        app.handle_mouse_events(scroll(MouseEventKind::ScrollUp));
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(1)]));
+
+        // the mouse can be disabled
+        app.mode = Mode::Normal;
+        app.config.mouse = false;
+        app.handle_mouse_events(scroll(MouseEventKind::ScrollDown));
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(1)]));
+    }
+
+    #[cfg(not(feature = "clipboard"))]
+    #[test]
+    fn copy_without_clipboard_support() {
+        let mut app = App::from_value(Value::test_int(1));
+        let config = app.config.clone();
+
+        app.handle_key_events(config.keybindings.peek, 0).unwrap();
+        let result = app
+            .handle_key_events(config.keybindings.peeking.clipboard, 0)
+            .unwrap();
+
+        assert!(matches!(result, TransitionResult::Error(_)));
+        assert_eq!(app.mode, Mode::Peeking);
+
+        // the schema goes through the clipboard as well
+        let result = app
+            .handle_key_events(config.keybindings.peeking.yank_schema, 0)
+            .unwrap();
+
+        assert!(matches!(result, TransitionResult::Error(_)));
+        assert_eq!(app.mode, Mode::Peeking);
+    }
+
+    #[test]
+    fn write_to_a_file() {
+        let mut app = App::from_value(test_value());
+        let config = app.config.clone();
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+
+        let dir = std::env::temp_dir().join(format!("nu_plugin_explore-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        for (path, ok) in [
+            (dir.join("missing").join("x.nuon"), false),
+            (dir.join("x.nuon"), true),
+        ] {
+            app.mode = Mode::Normal;
+            app.handle_key_events(config.keybindings.peek, 0).unwrap();
+            app.handle_key_events(config.keybindings.peeking.write, 0)
+                .unwrap();
+            assert_eq!(app.mode, Mode::Writing);
+
+            for c in path.display().to_string().chars() {
+                app.handle_key_events(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE), 0)
+                    .unwrap();
+            }
+            let result = app.handle_key_events(enter, 0).unwrap();
+
+            if ok {
+                assert_eq!(result, TransitionResult::Continue);
+                assert_eq!(app.mode, Mode::Normal);
+                let written =
+                    nuon::from_nuon(&std::fs::read_to_string(&path).unwrap(), None).unwrap();
+                assert_eq!(written, app.value_under_cursor(None));
+            } else {
+                // the IO error is surfaced and the path can be fixed
+                assert!(matches!(result, TransitionResult::Error(_)));
+                assert_eq!(app.mode, Mode::Writing);
+            }
+        }
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let value = Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_record(record! {
+                "x" => Value::test_int(2),
+                "y" => Value::test_int(3),
+            }),
+        });
+        let mut app = App::from_value(value.clone());
+        let config = app.config.clone();
+
+        let a = CellPath {
+            members: to_path_member_vec(&[PM::S("a")]),
+        };
+        app.mutate(&Value::test_int(10), &a, app.position.clone());
+        let intermediate = app.value.clone();
+        app.mutate(&Value::test_int(100), &a, app.position.clone());
+        let last = app.value.clone();
+
+        app.handle_key_events(config.keybindings.undo, 0).unwrap();
+        assert_eq!(app.value, intermediate);
+        app.handle_key_events(config.keybindings.undo, 0).unwrap();
+        assert_eq!(app.value, value);
+        app.handle_key_events(config.keybindings.undo, 0).unwrap();
+        assert_eq!(app.value, value);
+        assert!(app.flash.is_some());
+
+        app.handle_key_events(config.keybindings.redo, 0).unwrap();
+        app.handle_key_events(config.keybindings.redo, 0).unwrap();
+        assert_eq!(app.value, last);
+        app.handle_key_events(config.keybindings.redo, 0).unwrap();
+        assert_eq!(app.value, last);
+        assert!(app.flash.is_some());
+
+        // a transpose can be undone as well, and the cursor goes back to where it was
+        app.handle_key_events(config.keybindings.navigation.down, 0)
+            .unwrap();
+        app.handle_key_events(config.keybindings.navigation.right, 0)
+            .unwrap();
+        let position = app.position.clone();
+        let before = app.value.clone();
+        match app
+            .handle_key_events(config.keybindings.transpose, 0)
+            .unwrap()
+        {
+            TransitionResult::Mutate(cell, path) => app.mutate(&cell, &path, position.clone()),
+            t => panic!("expected a mutation, found {:?}", t),
+        }
+        assert_ne!(app.value, before);
+        app.handle_key_events(config.keybindings.undo, 0).unwrap();
+        assert_eq!(app.value, before);
+        assert_eq!(app.position, position);
+
+        // the history is bounded
+        app.config.undo_depth = 2;
+        for i in 0..5 {
+            app.mutate(&Value::test_int(i), &a, app.position.clone());
+        }
+        assert_eq!(app.undo.len(), 2);
+    }
+
+    #[test]
+    fn toggle_the_layout() {
+        let mut app = App::from_value(Value::test_list(vec![Value::test_int(1)]));
+        let config = app.config.clone();
+        assert_eq!(app.config.layout, Layout::Table);
+
+        app.handle_key_events(config.keybindings.toggle_layout, 0)
+            .unwrap();
+        assert_eq!(app.config.layout, Layout::Compact);
+        assert_eq!(app.mode, Mode::Normal);
+
+        app.handle_key_events(config.keybindings.toggle_layout, 0)
+            .unwrap();
+        assert_eq!(app.config.layout, Layout::Table);
+    }
+
+    #[test]
+    fn scroll_the_columns_of_a_table() {
+        let row = Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_int(2),
+            "c" => Value::test_int(3),
+        });
+        let mut app = App::from_value(Value::test_list(vec![row.clone(), row]));
+        let mut config = app.config.clone();
+        config.pinned_columns = 1;
+        app.config = config.clone();
+        let nav = &config.keybindings.navigation;
+
+        // NOTE: only `b` can be scrolled past, `a` being pinned and `c` always staying visible
+        let strokes = vec![
+            (nav.scroll_left, 0),
+            (nav.scroll_right, 1),
+            (nav.scroll_right, 1),
+            (nav.scroll_left, 0),
+        ];
+        for (key, offset) in strokes {
+            let result = app.handle_key_events(key, 0).unwrap();
+            assert!(matches!(result, TransitionResult::Continue));
+            assert_eq!(app.horizontal_offset, offset, "after {:?}", key);
+        }
+
+        let mut app = App::from_value(Value::test_list(vec![Value::test_int(1)]));
+        let result = app.handle_key_events(nav.scroll_right, 0).unwrap();
+        assert!(matches!(result, TransitionResult::Error(_)));
+        assert_eq!(app.horizontal_offset, 0);
+    }
+
+    #[test]
+    fn expand_the_rows() {
+        let mut app = App::from_value(Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_list(vec![Value::test_int(2)]),
+        }));
+        let config = app.config.clone();
+        let b = CellPath {
+            members: to_path_member_vec(&[PM::S("b")]),
+        };
+
+        let result = app.handle_key_events(config.keybindings.expand, 0).unwrap();
+        assert!(matches!(result, TransitionResult::Error(_)));
+
+        app.handle_key_events(config.keybindings.navigation.down, 0)
+            .unwrap();
+        app.handle_key_events(config.keybindings.expand, 0).unwrap();
+        assert!(app.is_expanded(&b));
+        app.handle_key_events(config.keybindings.expand, 0).unwrap();
+        assert!(!app.is_expanded(&b));
+
+        // the rows of a table are already shown as such
+        let row = Value::test_record(record! { "a" => Value::test_int(1) });
+        let mut app = App::from_value(Value::test_list(vec![row.clone(), row]));
+        let result = app.handle_key_events(config.keybindings.expand, 0).unwrap();
+        assert!(matches!(result, TransitionResult::Error(_)));
+        assert!(app.expanded.is_empty());
+    }
+
+    #[test]
+    fn move_rows() {
+        let list = Value::test_list(vec![
+            Value::test_int(1),
+            Value::test_int(2),
+            Value::test_int(3),
+        ]);
+        let mut app = App::from_value(list);
+        let kmap = app.config.keybindings.clone();
+        let root = CellPath { members: vec![] };
+
+        app.handle_key_events(kmap.navigation.down, 0).unwrap();
+        assert_eq!(
+            app.handle_key_events(kmap.move_up, 0).unwrap(),
+            TransitionResult::Mutate(
+                Value::test_list(vec![
+                    Value::test_int(2),
+                    Value::test_int(1),
+                    Value::test_int(3),
+                ]),
+                root.clone()
+            )
+        );
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(0)]));
+
+        // the first row can't go up
+        app.mutate(
+            &Value::test_list(vec![
+                Value::test_int(2),
+                Value::test_int(1),
+                Value::test_int(3),
+            ]),
+            &root,
+            app.position.clone(),
+        );
+        assert_eq!(
+            app.handle_key_events(kmap.move_up, 0).unwrap(),
+            TransitionResult::Continue
+        );
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(0)]));
+
+        // the cursor stays on the key of a record
+        let mut app = App::from_value(Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_int(2),
+        }));
+        let result = app.handle_key_events(kmap.move_down, 0).unwrap();
+        let TransitionResult::Mutate(value, path) = result else {
+            panic!(
+                "moving a field should change the record, found {:?}",
+                result
+            );
+        };
+        assert_eq!(path, root);
+        assert_eq!(
+            value.as_record().unwrap().columns().collect::<Vec<_>>(),
+            vec!["b", "a"]
+        );
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("a")]));
+    }
+
+    #[test]
+    fn parse_strings_in_place() {
+        let mut app = App::from_value(Value::test_list(vec![
+            Value::test_string(r#"{"a": [1, 2]}"#),
+            Value::test_string("{b: 3}"),
+            Value::test_string("{not nuon"),
+            Value::test_int(4),
+        ]));
+        let kmap = app.config.keybindings.clone();
+
+        assert_eq!(
+            app.handle_key_events(kmap.parse, 0).unwrap(),
+            TransitionResult::Mutate(
+                Value::test_record(record! {
+                    "a" => Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+                }),
+                app.position.clone()
+            )
+        );
+
+        app.handle_key_events(kmap.navigation.down, 0).unwrap();
+        assert_eq!(
+            app.handle_key_events(kmap.parse, 0).unwrap(),
+            TransitionResult::Mutate(
+                Value::test_record(record! { "b" => Value::test_int(3) }),
+                app.position.clone()
+            )
+        );
+
+        for _ in 0..2 {
+            app.handle_key_events(kmap.navigation.down, 0).unwrap();
+            assert!(matches!(
+                app.handle_key_events(kmap.parse, 0).unwrap(),
+                TransitionResult::Error(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn yank_under_the_cursor() {
+        let mut app = App::from_value(Value::test_list(vec![
+            Value::test_int(1),
+            Value::test_record(record! { "a" => Value::test_string("foo") }),
+        ]));
+        let kmap = app.config.keybindings.clone();
+
+        app.handle_key_events(kmap.navigation.down, 0).unwrap();
+        app.handle_key_events(kmap.yank.prefix, 0).unwrap();
+        assert_eq!(app.mode, Mode::Yank);
+        assert_eq!(
+            app.handle_key_events(kmap.yank.under, 0).unwrap(),
+            TransitionResult::Return(Value::test_record(
+                record! { "a" => Value::test_string("foo") }
+            ))
+        );
+
+        // NOTE: any other key cancels the command
+        let mut app = App::from_value(Value::test_list(vec![Value::test_int(1)]));
+        app.handle_key_events(kmap.yank.prefix, 0).unwrap();
+        assert_eq!(
+            app.handle_key_events(kmap.navigation.down, 0).unwrap(),
+            TransitionResult::Continue
+        );
+        assert_eq!(app.mode, Mode::Normal);
+    }
+
+    #[test]
+    fn toggle_the_cell_path() {
+        let mut app = App::from_value(test_value());
+        let kmap = app.config.keybindings.clone();
+        assert!(app.config.show_cell_path);
+
+        app.handle_key_events(kmap.toggle_cell_path, 0).unwrap();
+        assert!(!app.config.show_cell_path);
+        assert_eq!(app.mode, Mode::Normal);
+
+        app.handle_key_events(kmap.toggle_cell_path, 0).unwrap();
+        assert!(app.config.show_cell_path);
+    }
+
+    #[test]
+    fn navigate_sorted_keys() {
+        let value = Value::test_record(record! {
+            "b" => Value::test_int(1),
+            "c" => Value::test_int(2),
+            "a" => Value::test_int(3),
+        });
+        let config = Config {
+            view_sort_keys: true,
+            ..Config::default()
+        };
+        let mut app = App::from_value(value.clone()).with_config(config);
+        let kmap = app.config.keybindings.clone();
+
+        // NOTE: the rows are shown as `a`, `b` and `c`
+        let strokes = vec![
+            (kmap.navigation.down, "b", 1),
+            (kmap.navigation.down, "c", 2),
+            (kmap.navigation.down, "c", 2),
+            (kmap.navigation.up, "b", 1),
+            // the cursor stays on its key, now shown at its original place
+            (kmap.toggle_sort_keys, "b", 0),
+            (kmap.navigation.down, "c", 1),
+            (kmap.toggle_sort_keys, "c", 2),
+        ];
+        for (key, expected, row) in strokes {
+            app.handle_key_events(key, 0).unwrap();
+            assert_eq!(app.position.members, to_path_member_vec(&[PM::S(expected)]));
+            assert_eq!(app.selected_row(), row, "after {}", repr_key(&key));
+        }
+
+        // the data keeps its order
+        app.handle_key_events(kmap.peek, 0).unwrap();
+        assert_eq!(
+            app.handle_key_events(kmap.peeking.all, 0).unwrap(),
+            TransitionResult::Return(value.clone())
+        );
+
+        // the keys can't be moved as they are not shown in their order
+        let mut app = App::from_value(value).with_config(app.config.clone());
+        assert!(matches!(
+            app.handle_key_events(kmap.move_down, 0).unwrap(),
+            TransitionResult::Error(_)
+        ));
+
+        // deleting a key goes to the next one as shown
+        app.handle_key_events(kmap.delete, 0).unwrap();
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("b")]));
+    }
+
+    #[test]
+    fn delete_rows() {
+        let mut app = App::from_value(Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_int(2),
+            "c" => Value::test_int(3),
+        }));
+        let kmap = app.config.keybindings.clone();
+        let root = CellPath { members: vec![] };
+
+        app.handle_key_events(kmap.navigation.down, 0).unwrap();
+        assert_eq!(
+            app.handle_key_events(kmap.delete, 0).unwrap(),
+            TransitionResult::Mutate(
+                Value::test_record(record! {
+                    "a" => Value::test_int(1),
+                    "c" => Value::test_int(3),
+                }),
+                root.clone()
+            )
+        );
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("c")]));
+
+        // the cursor goes back to the previous row after deleting the last one
+        let mut app = App::from_value(Value::test_list(vec![
+            Value::test_int(1),
+            Value::test_int(2),
+        ]));
+        app.handle_key_events(kmap.navigation.down, 0).unwrap();
+        let result = app.handle_key_events(kmap.delete, 0).unwrap();
+        assert_eq!(
+            result,
+            TransitionResult::Mutate(Value::test_list(vec![Value::test_int(1)]), root.clone())
+        );
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(0)]));
+
+        // deleting the only item leaves an empty list
+        let TransitionResult::Mutate(value, path) = result else {
+            unreachable!()
+        };
+        app.mutate(&value, &path, app.position.clone());
+        assert_eq!(
+            app.handle_key_events(kmap.delete, 0).unwrap(),
+            TransitionResult::Mutate(Value::test_list(vec![]), root.clone())
+        );
+        app.mutate(&Value::test_list(vec![]), &root, app.position.clone());
+        assert!(matches!(
+            app.handle_key_events(kmap.delete, 0).unwrap(),
+            TransitionResult::Error(_)
+        ));
+
+        // nothing can be deleted from read-only data
+        let mut app = App::from_value(Value::test_list(vec![Value::test_int(1)]));
+        app.config.editable = false;
+        assert_eq!(
+            app.handle_key_events(kmap.delete, 0).unwrap(),
+            TransitionResult::Continue
+        );
+        assert!(app.flash.is_some());
+    }
+
+    #[test]
+    fn reload_the_original_data() {
+        let value = Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_record(record! {
+                "x" => Value::test_int(2),
+            }),
+        });
+        let mut app = App::from_value(value.clone());
+        let config = app.config.clone();
+
+        let a = CellPath {
+            members: to_path_member_vec(&[PM::S("a")]),
+        };
+        app.mutate(&Value::test_int(10), &a, app.position.clone());
+        app.handle_key_events(config.keybindings.navigation.down, 0)
+            .unwrap();
+        app.handle_key_events(config.keybindings.navigation.right, 0)
+            .unwrap();
+        assert_ne!(app.value, value);
+        app.expanded.insert("$.b".into());
+        app.horizontal_offset = 1;
+        *app.range_cache.borrow_mut() = Some((
+            Range::new(
+                Value::test_int(1),
+                Value::test_nothing(),
+                Value::test_int(2),
+                RangeInclusion::Inclusive,
+                Span::test_data(),
+            )
+            .unwrap(),
+            Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+        ));
+
+        app.handle_key_events(config.keybindings.reload, 0).unwrap();
+        assert_eq!(app.value, value);
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("a")]));
+        // the state pointing into the changed data is forgotten as well
+        assert!(app.expanded.is_empty());
+        assert_eq!(app.horizontal_offset, 0);
+        assert!(app.range_cache.borrow().is_none());
+
+        // the reload itself can't be undone
+        app.handle_key_events(config.keybindings.undo, 0).unwrap();
+        assert_eq!(app.value, value);
+        assert!(app.flash.is_some());
+    }
+
+    #[test]
+    fn select_rows_in_visual_mode() {
+        let list = Value::test_list((0..10).map(Value::test_int).collect::<Vec<_>>());
+        let mut app = App::from_value(list);
+        let config = app.config.clone();
+        let nav = &config.keybindings.navigation;
+
+        app.handle_key_events(nav.down, 0).unwrap();
+        app.handle_key_events(nav.down, 0).unwrap();
+        app.handle_key_events(config.keybindings.visual, 0).unwrap();
+        assert_eq!(app.mode, Mode::Visual(2));
+        for _ in 0..3 {
+            app.handle_key_events(nav.down, 0).unwrap();
+        }
+        assert_eq!(app.visual_selection(), Some((2, 5)));
+        assert_eq!(
+            app.handle_key_events(config.keybindings.peek, 0).unwrap(),
+            TransitionResult::Return(Value::test_list(
+                (2..=5).map(Value::test_int).collect::<Vec<_>>()
+            ))
+        );
+        assert_eq!(app.mode, Mode::Normal);
+
+        // the selection can go upward from the anchor
+        app.handle_key_events(config.keybindings.visual, 0).unwrap();
+        for _ in 0..10 {
+            app.handle_key_events(nav.up, 0).unwrap();
+        }
+        assert_eq!(
+            app.handle_key_events(config.keybindings.peek, 0).unwrap(),
+            TransitionResult::Return(Value::test_list(
+                (0..=5).map(Value::test_int).collect::<Vec<_>>()
+            ))
+        );
+
+        // a record gives back only the selected keys
+        let mut app = App::from_value(Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_int(2),
+            "c" => Value::test_int(3),
+        }));
+        app.handle_key_events(nav.down, 0).unwrap();
+        app.handle_key_events(config.keybindings.visual, 0).unwrap();
+        app.handle_key_events(nav.goto_bottom, 0).unwrap();
+        assert_eq!(
+            app.handle_key_events(config.keybindings.peek, 0).unwrap(),
+            TransitionResult::Return(Value::test_record(record! {
+                "b" => Value::test_int(2),
+                "c" => Value::test_int(3),
+            }))
+        );
+
+        // anything else cannot be selected
+        let mut app = App::from_value(Value::test_int(1));
+        app.handle_key_events(config.keybindings.visual, 0).unwrap();
+        assert_eq!(app.mode, Mode::Normal);
+        assert!(app.flash.is_some());
+    }
 }
@@ -0,0 +1,525 @@
+//! the help overlay, listing all the keybindings grouped by mode, and the quick-start tour
+use crossterm::event::KeyEvent;
+use ratatui::{
+    prelude::{Constraint, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    app::Mode,
+    config::{
+        Config, GotoBindingsMap, KeyBindingsMap, NavigationBindingsMap, PeekingBindingsMap,
+        ScrollBindingsMap, YankBindingsMap,
+    },
+    handler::repr_key,
+    ui::styled,
+};
+
+/// a row of the help, i.e. the mode in which a binding is active, what it does and its key
+#[derive(Debug, PartialEq)]
+pub(crate) struct HelpRow {
+    pub(crate) mode: Mode,
+    pub(crate) label: &'static str,
+    pub(crate) key: String,
+}
+
+/// list all the *bindings*, grouped by mode
+///
+/// > :bulb: **Note**
+/// > the bindings are destructured without `..` so that a new binding can't be forgotten here
+pub(crate) fn help_rows(bindings: &KeyBindingsMap) -> Vec<HelpRow> {
+    let KeyBindingsMap {
+        quit,
+        insert,
+        normal,
+        navigation:
+            NavigationBindingsMap {
+                up,
+                down,
+                left,
+                right,
+                half_page_up,
+                half_page_down,
+                goto_bottom,
+                goto_line,
+                goto_root,
+                search,
+                next_match,
+                previous_match,
+                next_collection,
+                previous_collection,
+                scroll_left,
+                scroll_right,
+            },
+        goto:
+            GotoBindingsMap {
+                prefix,
+                top,
+                end,
+                middle,
+            },
+        scroll:
+            ScrollBindingsMap {
+                prefix: scroll_prefix,
+                top: scroll_top,
+                center,
+                bottom,
+            },
+        yank:
+            YankBindingsMap {
+                prefix: yank_prefix,
+                under: yank_under,
+            },
+        peek,
+        peeking:
+            PeekingBindingsMap {
+                all,
+                cell_path,
+                cell_path_string,
+                under,
+                view,
+                yank_schema,
+                clipboard,
+                write,
+                nuon,
+                ty,
+                markdown,
+                trail,
+                path,
+            },
+        transpose,
+        keys,
+        filter_shape,
+        filter,
+        clear_filter,
+        undo,
+        redo,
+        reload,
+        visual,
+        sort_ascending,
+        sort_descending,
+        move_down,
+        move_up,
+        delete,
+        parse,
+        toggle_wrap,
+        toggle_layout,
+        toggle_sort_keys,
+        toggle_cell_path,
+        expand,
+        command,
+        help,
+    } = bindings;
+
+    let rows: Vec<(Mode, &'static str, &KeyEvent)> = vec![
+        (Mode::Normal, "go one row up", up),
+        (Mode::Normal, "go one row down", down),
+        (Mode::Normal, "go one level back", left),
+        (Mode::Normal, "go one level deeper", right),
+        (Mode::Normal, "go one half page up", half_page_up),
+        (Mode::Normal, "go one half page down", half_page_down),
+        (Mode::Normal, "go to the bottom", goto_bottom),
+        (Mode::Normal, "go back to the top level", goto_root),
+        (Mode::Normal, "search in the current level", search),
+        (Mode::Normal, "go to the next match", next_match),
+        (Mode::Normal, "go to the previous match", previous_match),
+        (Mode::Normal, "go to the next collection", next_collection),
+        (
+            Mode::Normal,
+            "go to the previous collection",
+            previous_collection,
+        ),
+        (
+            Mode::Normal,
+            "scroll the columns of a table left",
+            scroll_left,
+        ),
+        (
+            Mode::Normal,
+            "scroll the columns of a table right",
+            scroll_right,
+        ),
+        (Mode::Normal, "wait for a motion, see GOTO", prefix),
+        (
+            Mode::Normal,
+            "wait for a command, see SCROLL",
+            scroll_prefix,
+        ),
+        (Mode::Normal, "wait for a command, see YANK", yank_prefix),
+        (Mode::Normal, "go to INSERT mode", insert),
+        (Mode::Normal, "go to PEEKING mode", peek),
+        (Mode::Normal, "go to VISUAL mode", visual),
+        (Mode::Normal, "go to COMMAND mode", command),
+        (Mode::Normal, "transpose", transpose),
+        (Mode::Normal, "explore the keys of the record", keys),
+        (
+            Mode::Normal,
+            "filter by the shape under the cursor",
+            filter_shape,
+        ),
+        (Mode::Normal, "filter by a query", filter),
+        (Mode::Normal, "clear the filter", clear_filter),
+        (Mode::Normal, "sort in ascending order", sort_ascending),
+        (Mode::Normal, "sort in descending order", sort_descending),
+        (Mode::Normal, "move the row down", move_down),
+        (Mode::Normal, "move the row up", move_up),
+        (Mode::Normal, "delete the row", delete),
+        (Mode::Normal, "undo the last change", undo),
+        (Mode::Normal, "redo the last undone change", redo),
+        (Mode::Normal, "reload the original data", reload),
+        (
+            Mode::Normal,
+            "toggle the wrapping at the bottom",
+            toggle_wrap,
+        ),
+        (
+            Mode::Normal,
+            "switch between the table and compact layouts",
+            toggle_layout,
+        ),
+        (
+            Mode::Normal,
+            "show the keys sorted or not",
+            toggle_sort_keys,
+        ),
+        (Mode::Normal, "show or hide the cell path", toggle_cell_path),
+        (Mode::Normal, "expand or collapse the row", expand),
+        (Mode::Normal, "parse the string in place", parse),
+        (Mode::Normal, "show this help", help),
+        (Mode::Normal, "quit", quit),
+        (Mode::Waiting(0), "go to the line typed before", goto_line),
+        (Mode::Goto(None), "go to the top", top),
+        (Mode::Goto(None), "go to the end", end),
+        (Mode::Goto(None), "go to the middle of the frame", middle),
+        (
+            Mode::Scroll,
+            "put the cursor at the top of the frame",
+            scroll_top,
+        ),
+        (
+            Mode::Scroll,
+            "put the cursor in the middle of the frame",
+            center,
+        ),
+        (
+            Mode::Scroll,
+            "put the cursor at the bottom of the frame",
+            bottom,
+        ),
+        (
+            Mode::Yank,
+            "give back the value under the cursor and quit",
+            yank_under,
+        ),
+        (Mode::Peeking, "peek all the data", all),
+        (Mode::Peeking, "peek the current view", view),
+        (Mode::Peeking, "peek under the cursor", under),
+        (Mode::Peeking, "peek the cell path", cell_path),
+        (
+            Mode::Peeking,
+            "peek the cell path as a string",
+            cell_path_string,
+        ),
+        (
+            Mode::Peeking,
+            "copy the schema to the clipboard",
+            yank_schema,
+        ),
+        (Mode::Peeking, "peek the type", ty),
+        (Mode::Peeking, "peek as NUON", nuon),
+        (Mode::Peeking, "peek the table as Markdown", markdown),
+        (Mode::Peeking, "peek the values down to the cursor", trail),
+        (Mode::Peeking, "peek the metadata of a path", path),
+        (Mode::Peeking, "copy to the clipboard", clipboard),
+        (Mode::Peeking, "write to a file", write),
+        (
+            Mode::Insert,
+            "go back to NORMAL mode, from any mode",
+            normal,
+        ),
+    ];
+
+    rows.into_iter()
+        .map(|(mode, label, key)| HelpRow {
+            mode,
+            label,
+            key: repr_key(key),
+        })
+        .collect()
+}
+
+/// render the help as a centered overlay on top of the data
+pub(super) fn render(frame: &mut Frame, config: &Config, top: &mut usize) {
+    let help = help_rows(&config.keybindings);
+
+    let enabled = config.colors.enabled;
+    let frame_style = styled(
+        Style::default(),
+        config.colors.editor.frame.foreground,
+        config.colors.editor.frame.background,
+        enabled,
+    );
+    let buffer_style = styled(
+        Style::default(),
+        config.colors.editor.buffer.foreground,
+        config.colors.editor.buffer.background,
+        enabled,
+    );
+
+    // NOTE: the mode is only shown on the first row of each group
+    let mut previous_mode = None;
+    let rows = help.iter().map(|row| {
+        let mode = if previous_mode == Some(&row.mode) {
+            String::new()
+        } else {
+            row.mode.to_string()
+        };
+        previous_mode = Some(&row.mode);
+
+        Row::new(vec![
+            Cell::from(mode).style(buffer_style.add_modifier(Modifier::BOLD)),
+            Cell::from(row.key.clone()),
+            Cell::from(row.label),
+        ])
+        .style(buffer_style)
+    });
+
+    let mode_width = help
+        .iter()
+        .map(|row| row.mode.to_string().len())
+        .max()
+        .unwrap_or(0);
+    let key_width = help
+        .iter()
+        .map(|row| row.key.chars().count())
+        .max()
+        .unwrap_or(0);
+    let label_width = help.iter().map(|row| row.label.len()).max().unwrap_or(0);
+
+    // NOTE: the two column spacings and the borders of the frame
+    let width = (mode_width + key_width + label_width + 2 + 2) as u16;
+    let height = help.len() as u16 + 2;
+    let area = Rect {
+        x: frame.size().width.saturating_sub(width) / 2,
+        y: frame.size().height.saturating_sub(height) / 2,
+        width: width.min(frame.size().width),
+        height: height.min(frame.size().height),
+    };
+
+    // NOTE: the rows that do not fit in the frame are scrolled to, the last one being at the
+    // bottom of the frame at most
+    let visible = area.height.saturating_sub(2) as usize;
+    *top = (*top).min(help.len().saturating_sub(visible));
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(mode_width as u16),
+            Constraint::Length(key_width as u16),
+            Constraint::Length(label_width as u16),
+        ],
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(
+                "Help ({}{} to scroll, any other key to close)",
+                repr_key(&config.keybindings.navigation.down),
+                repr_key(&config.keybindings.navigation.up),
+            ))
+            .style(frame_style),
+    );
+
+    // NOTE: the table goes back to its selected row, the first one by default, hence the top one
+    // being selected, without any highlight
+    let mut state = TableState::default()
+        .with_offset(*top)
+        .with_selected(Some(*top));
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+/// the lines of the quick-start tour, shown when exploring until the first key is pressed
+///
+/// > :bulb: **Note**  
+/// > there is no tour without the hints, i.e. when [`Config::show_hints`] is not set
+pub(crate) fn tour(config: &Config) -> Option<Vec<String>> {
+    if !config.show_hints {
+        return None;
+    }
+
+    let kmap = &config.keybindings;
+    Some(vec![
+        format!(
+            "{}{}{}{} to move around, deeper and back in the data",
+            repr_key(&kmap.navigation.left),
+            repr_key(&kmap.navigation.down),
+            repr_key(&kmap.navigation.up),
+            repr_key(&kmap.navigation.right),
+        ),
+        format!(
+            "{} to search in the current level",
+            repr_key(&kmap.navigation.search)
+        ),
+        format!(
+            "{} then {} to peek what's under the cursor",
+            repr_key(&kmap.peek),
+            repr_key(&kmap.peeking.under)
+        ),
+        format!(
+            "{} to edit the cell under the cursor",
+            repr_key(&kmap.insert)
+        ),
+        format!("{} to show all the bindings", repr_key(&kmap.help)),
+        format!("{} to quit", repr_key(&kmap.quit)),
+    ])
+}
+
+/// render the quick-start tour as a centered overlay on top of the data, if any, see [`tour`]
+pub(super) fn render_tour(frame: &mut Frame, config: &Config) {
+    let Some(lines) = tour(config) else {
+        return;
+    };
+
+    let enabled = config.colors.enabled;
+    let frame_style = styled(
+        Style::default(),
+        config.colors.editor.frame.foreground,
+        config.colors.editor.frame.background,
+        enabled,
+    );
+    let buffer_style = styled(
+        Style::default(),
+        config.colors.editor.buffer.foreground,
+        config.colors.editor.buffer.background,
+        enabled,
+    );
+
+    let title = "Quick start (any key to close)";
+    // NOTE: the borders of the frame
+    let width = lines
+        .iter()
+        .map(|line| line.chars().count())
+        .chain([title.len()])
+        .max()
+        .unwrap_or(0) as u16
+        + 2;
+    let height = lines.len() as u16 + 2;
+    let area = Rect {
+        x: frame.size().width.saturating_sub(width) / 2,
+        y: frame.size().height.saturating_sub(height) / 2,
+        width: width.min(frame.size().width),
+        height: height.min(frame.size().height),
+    };
+
+    let paragraph = Paragraph::new(lines.into_iter().map(Line::from).collect::<Vec<_>>())
+        .style(buffer_style)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .style(frame_style),
+        );
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(paragraph, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::{help_rows, render, render_tour, tour};
+    use crate::{
+        app::Mode,
+        config::{Config, KeyBindingsMap},
+    };
+
+    #[test]
+    fn list_all_bindings() {
+        let bindings = KeyBindingsMap {
+            help: KeyEvent::new(KeyCode::Char('H'), KeyModifiers::NONE),
+            ..Config::default().keybindings
+        };
+
+        let rows = help_rows(&bindings);
+
+        // NOTE: 26 top-level bindings, 16 for the navigation, 4 for GOTO, 4 for SCROLL, 2 for YANK
+        // and 13 for PEEKING
+        assert_eq!(rows.len(), 26 + 16 + 4 + 4 + 2 + 13);
+
+        let help = rows
+            .iter()
+            .find(|row| row.label == "show this help")
+            .unwrap();
+        assert_eq!(help.mode, Mode::Normal);
+        assert_eq!(help.key, "H");
+
+        let mut labels = rows.iter().map(|row| row.label).collect::<Vec<_>>();
+        labels.sort();
+        labels.dedup();
+        assert_eq!(labels.len(), rows.len(), "labels should be unique");
+    }
+
+    #[test]
+    fn render_in_small_frames() {
+        let config = Config::default();
+        for (width, height) in [(20, 5), (40, 10), (200, 80)] {
+            let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+            terminal
+                .draw(|frame| render(frame, &config, &mut 0))
+                .unwrap();
+            terminal.draw(|frame| render_tour(frame, &config)).unwrap();
+        }
+    }
+
+    #[test]
+    fn scroll_to_the_last_row() {
+        let config = Config::default();
+        let last = help_rows(&config.keybindings).pop().unwrap().label;
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        let text = |terminal: &Terminal<TestBackend>| {
+            let buffer = terminal.backend().buffer();
+            (0..24)
+                .map(|y| {
+                    (0..80)
+                        .map(|x| buffer.get(x, y).symbol())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let mut top = 0;
+        terminal
+            .draw(|frame| render(frame, &config, &mut top))
+            .unwrap();
+        assert!(!text(&terminal).contains(last));
+
+        // NOTE: scrolling past the end stops at the last row
+        let mut top = usize::MAX;
+        terminal
+            .draw(|frame| render(frame, &config, &mut top))
+            .unwrap();
+        assert!(text(&terminal).contains(last), "{}", text(&terminal));
+        assert_eq!(top, help_rows(&config.keybindings).len() - (24 - 2));
+    }
+
+    #[test]
+    fn tour_only_with_hints() {
+        let config = Config::default();
+        let lines = tour(&config).unwrap();
+        assert!(lines.contains(&"p then p to peek what's under the cursor".to_string()));
+
+        let config = Config {
+            show_hints: false,
+            ..Config::default()
+        };
+        assert_eq!(tour(&config), None);
+    }
+}
@@ -1,10 +1,16 @@
 #![doc = include_str!("../README.md")]
+#![allow(clippy::result_large_err)]
 mod app;
+mod clipboard;
+mod command;
 mod config;
 mod edit;
+mod error;
 mod handler;
+mod help;
 mod navigation;
 mod nu;
+mod prompt;
 mod tui;
 mod ui;
 
@@ -17,21 +23,59 @@ use std::io;
 use nu_protocol::{Span, Value};
 
 use app::{App, Mode};
-use config::Config;
+use config::UiState;
+pub use config::{Config, CONFIG_FILE_ENV_VAR, NO_COLOR_ENV_VAR};
+use error::{ErrorTransition, ErrorView};
 use handler::TransitionResult;
+use nu::value::count_changes;
 use tui::{
     event::{Event, EventHandler},
     Tui,
 };
 
+/// explore the `input` data interactively and give back the data that has been peeked, if any
+///
+/// the `config` is loaded with [`Config::load`], see [`explore_with_config`] to use a [`Config`]
+/// directly.
 pub fn explore(config: &Value, input: Value) -> Result<Value> {
+    explore_with_config(Config::load(config)?, input)
+}
+
+/// same as [`explore`] but with an already built [`Config`]
+///
+/// # Example
+/// ```no_run
+/// use nu_plugin_explore::{explore_with_config, Config};
+/// use nu_protocol::Value;
+///
+/// let peeked = explore_with_config(Config::default(), Value::test_int(1)).unwrap();
+/// ```
+pub fn explore_with_config(config: Config, input: Value) -> Result<Value> {
+    Ok(explore_with_summary(config, input)?.0)
+}
+
+/// same as [`explore_with_config`] but also gives a summary of the changes made to the data
+///
+/// > **Note**  
+/// > the summary is only computed when [`Config::announce_changes`] is set
+pub fn explore_with_summary(config: Config, input: Value) -> Result<(Value, Option<String>)> {
     let mut tui = Tui::new(
         Terminal::new(CrosstermBackend::new(io::stderr()))?,
-        EventHandler::new(250),
+        EventHandler::new(config.tick_rate_ms),
     );
     tui.init()?;
+    // NOTE: building the app from a huge input can take a while, e.g. `seq 1 5_000_000`
+    tui.draw_loading()?;
+
+    // NOTE: the input is only kept around when needed because it might be huge
+    let original = if config.announce_changes {
+        Some(input.clone())
+    } else {
+        None
+    };
 
-    let mut app = App::from_value(input).with_config(Config::from_value(config)?);
+    let mut app = App::from_value(input).with_config(config);
+    app.tour = true;
 
     loop {
         if app.mode == Mode::Insert {
@@ -44,35 +88,66 @@ pub fn explore(config: &Value, input: Value) -> Result<Value> {
             Event::Tick => app.tick(),
             Event::Key(key_event) => {
                 if key_event.kind == KeyEventKind::Press {
-                    match app.handle_key_events(key_event, (tui.size()?.height as usize - 5) / 2)? {
+                    // NOTE: this is where the cursor should go back when undoing a change
+                    let position = app.position.clone();
+                    let half_page = (tui.size()?.height as usize).saturating_sub(5) / 2;
+                    match app.handle_key_events(key_event, half_page)? {
                         TransitionResult::Quit => break,
                         TransitionResult::Continue => {}
-                        TransitionResult::Mutate(cell, path) => {
-                            app.value =
-                                crate::nu::value::mutate_value_cell(&app.value, &path, &cell)
-                                    .unwrap()
-                        }
+                        TransitionResult::Mutate(cell, path) => app.mutate(&cell, &path, position),
                         TransitionResult::Error(error) => {
-                            tui.draw(&mut app, Some(&error))?;
+                            let mut error = ErrorView::new(&error);
                             loop {
-                                if let Event::Key(_) = tui.events.next()? {
-                                    break;
+                                tui.draw(&mut app, Some(&error))?;
+                                if let Event::Key(key_event) = tui.events.next()? {
+                                    if key_event.kind == KeyEventKind::Press
+                                        && error.handle_key(&key_event, &app.config, tui.size()?)
+                                            == ErrorTransition::Quit
+                                    {
+                                        break;
+                                    }
                                 }
                             }
                         }
                         TransitionResult::Return(value) => {
                             tui.exit()?;
-                            return Ok(value);
+                            save_ui_state(&app.config);
+                            return Ok((value, summarize_changes(original, &app.value)));
                         }
                     }
                 }
             }
-            Event::Mouse(_) => {}
+            Event::Mouse(mouse_event) => app.handle_mouse_events(mouse_event),
+            // NOTE: the next draw, at the top of the loop, fits the editor and the scroll offsets
+            // to the new size of the terminal
             Event::Resize(_, _) => {}
         }
     }
 
     tui.exit()?;
+    save_ui_state(&app.config);
 
-    Ok(Value::nothing(Span::unknown()))
+    Ok((
+        Value::nothing(Span::unknown()),
+        summarize_changes(original, &app.value),
+    ))
+}
+
+/// summarize the changes between the `original` data, if any, and the explored `value`
+fn summarize_changes(original: Option<Value>, value: &Value) -> Option<String> {
+    original.map(|original| match count_changes(&original, value) {
+        0 => "no cell has been changed".to_string(),
+        1 => "1 cell has been changed".to_string(),
+        n => format!("{} cells have been changed", n),
+    })
+}
+
+/// remember the state of the UI for the next session, see [`Config::persist_ui_state`]
+///
+/// > **Note**  
+/// > the state is a nicety, failing to save it does not fail the whole exploration
+fn save_ui_state(config: &Config) {
+    if config.persist_ui_state {
+        let _ = UiState::from_config(config).save();
+    }
 }
@@ -1,11 +1,11 @@
-use std::io::IsTerminal;
+use std::{io::IsTerminal, path::Path};
 
 use nu_plugin::{
     serve_plugin, EngineInterface, EvaluatedCall, MsgPackSerializer, Plugin, PluginCommand,
     SimplePluginCommand,
 };
-use nu_plugin_explore::explore;
-use nu_protocol::{Example, LabeledError, Record, Signature, Span, Type, Value};
+use nu_plugin_explore::{explore_with_summary, Config, CONFIG_FILE_ENV_VAR, NO_COLOR_ENV_VAR};
+use nu_protocol::{Example, LabeledError, PipelineData, Record, Signature, Span, Type, Value};
 
 struct ExplorePlugin;
 
@@ -33,7 +33,23 @@ impl SimplePluginCommand for Explore {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build(PluginCommand::name(self)).input_output_type(Type::Any, Type::Any)
+        Signature::build(PluginCommand::name(self))
+            .input_output_type(Type::Any, Type::Any)
+            .switch(
+                "readonly",
+                "prevent the data from being changed, overrides the `editable` config",
+                Some('r'),
+            )
+            .switch(
+                "peek-on-enter",
+                "peek the value under the cursor with <enter>, overrides the `enter_peeks` config",
+                None,
+            )
+            .switch(
+                "diff",
+                "compare the two records of the input side by side, overrides the `diff` config",
+                None,
+            )
     }
 
     fn search_terms(&self) -> Vec<&str> {
@@ -53,6 +69,16 @@ impl SimplePluginCommand for Explore {
                 description: "explore `$nu` and set some config options",
                 result: None,
             },
+            Example {
+                example: "open Cargo.toml | nu_plugin_explore --readonly",
+                description: "explore the Cargo.toml file without being able to change it",
+                result: None,
+            },
+            Example {
+                example: "[(open a.toml) (open b.toml)] | nu_plugin_explore --diff",
+                description: "compare two TOML files side by side",
+                result: None,
+            },
         ]
     }
 
@@ -67,6 +93,31 @@ impl SimplePluginCommand for Explore {
 
         let default_config = Value::record(Record::new(), Span::unknown());
         let config = config.plugins.get("explore").unwrap_or(&default_config);
+        // NOTE: the environment of the shell is used instead of the one of the plugin process,
+        // which might be outdated
+        let mut config = match engine.get_env_var(CONFIG_FILE_ENV_VAR)? {
+            // NOTE: a relative path is relative to the shell, not to the plugin process
+            Some(path) => {
+                let path = Path::new(&engine.get_current_dir()?).join(path.coerce_into_string()?);
+                Config::from_file_and_value(&path, config)?
+            }
+            None => Config::from_value(config)?,
+        };
+        // NOTE: any value that is neither empty nor null disables the colors, e.g. `true`
+        match engine.get_env_var(NO_COLOR_ENV_VAR)? {
+            None | Some(Value::Nothing { .. }) => {}
+            Some(Value::String { val, .. }) if val.is_empty() => {}
+            Some(_) => config.colors.enabled = false,
+        }
+        if call.has_flag("readonly")? {
+            config.editable = false;
+        }
+        if call.has_flag("peek-on-enter")? {
+            config.enter_peeks = true;
+        }
+        if call.has_flag("diff")? {
+            config.diff = true;
+        }
 
         if !std::io::stdin().is_terminal() {
             return Err(LabeledError::new("Can't start nu_plugin_explore")
@@ -79,7 +130,10 @@ impl SimplePluginCommand for Explore {
 
         let foreground = engine.enter_foreground()?;
 
-        let value = explore(config, input.clone()).map_err(|err| {
+        // NOTE: as a `SimplePluginCommand`, the input has already been collected by Nushell, i.e.
+        // streams are materialized once and there are no lazy records in the protocol anymore, so
+        // following cell paths while navigating never evaluates anything again
+        let (value, summary) = explore_with_summary(config, input.clone()).map_err(|err| {
             match err.downcast_ref::<LabeledError>() {
                 Some(err) => err.clone(),
                 None => LabeledError::new("unexpected internal error").with_label(
@@ -91,6 +145,19 @@ impl SimplePluginCommand for Explore {
 
         foreground.leave()?;
 
+        if let Some(summary) = summary {
+            if let Some(print) = engine.find_decl("print")? {
+                engine.call_decl(
+                    print,
+                    EvaluatedCall::new(call.head)
+                        .with_positional(Value::string(summary, call.head)),
+                    PipelineData::Empty,
+                    false,
+                    false,
+                )?;
+            }
+        }
+
         Ok(value)
     }
 }
@@ -1,7 +1,14 @@
 //! navigate in the data in all directions
-use nu_protocol::{ast::PathMember, Span, Value};
+use nu_protocol::{
+    ast::{CellPath, PathMember},
+    Span, Value,
+};
 
-use crate::app::{App, Mode};
+use crate::{
+    app::{App, Mode, RANGE_LIMIT},
+    nu::value::expand_range,
+    ui::{anchored_top, repr_data, ReprOptions, ScrollAnchor},
+};
 
 /// specify a vertical direction in which to go in the data
 pub enum Direction {
@@ -17,6 +24,41 @@ pub enum Direction {
     At(usize),
 }
 
+/// pick the first of the sorted *rows* after the *selected* one when going [`Direction::Down`],
+/// or the last one before it when going [`Direction::Up`], wrapping around, the first row
+/// otherwise
+///
+/// > :bulb: **Note**  
+/// > the *rows* should not be empty
+fn wrapping_neighbour(rows: &[usize], selected: usize, direction: Direction) -> usize {
+    match direction {
+        Direction::Down(_) => *rows.iter().find(|&&i| i > selected).unwrap_or(&rows[0]),
+        Direction::Up(_) => *rows
+            .iter()
+            .rev()
+            .find(|&&i| i < selected)
+            .unwrap_or(&rows[rows.len() - 1]),
+        _ => rows[0],
+    }
+}
+
+/// the index reached from *index* when going in the *direction* through *len* items, clamped at
+/// both ends or wrapping around them, see [`crate::config::Config::wrap_navigation`]
+///
+/// > :bulb: **Note**  
+/// > *len* should not be zero
+fn step_index(index: usize, len: usize, direction: Direction, wrap: bool) -> usize {
+    match direction {
+        Direction::Up(step) if wrap => (index + len - step % len) % len,
+        Direction::Down(step) if wrap => (index + step) % len,
+        Direction::Up(step) => index.saturating_sub(step),
+        Direction::Down(step) => index.saturating_add(step).min(len - 1),
+        Direction::Top => 0,
+        Direction::Bottom => len - 1,
+        Direction::At(id) => id.min(len - 1),
+    }
+}
+
 impl App {
     /// go up or down in the data
     ///
@@ -24,7 +66,9 @@ impl App {
     /// - early return if the user is already at the bottom => this is to avoid the confusing following
     ///   situation: you are at the bottom of the data, looking at one item in a list, without this early
     ///   return, you'd be able to scroll the list without seeing it as a whole... confusing, right?
-    /// - cycle the list indices or the record column names => the index / column will wrap around
+    /// - move through the list indices or the record column names => the index / column stops at
+    ///   both ends, unless [`crate::config::Config::wrap_navigation`] is set, in which case it wraps
+    ///   around
     ///
     /// > :bulb: **Note**  
     /// > this function will only modify the last element of the state's *cell path* either by
@@ -35,29 +79,44 @@ impl App {
             return;
         }
 
+        // NOTE: the cursor goes through the keys of the records compared side by side instead
+        if let Some(nb_keys) = self.diff_keys() {
+            if nb_keys > 0 {
+                let wrap = self.config.wrap_navigation;
+                self.diff_cursor = step_index(self.diff_cursor, nb_keys, direction, wrap);
+            }
+            return;
+        }
+
         // NOTE: this should never fail by construction
         let current = self.position.members.pop().unwrap();
 
         match self.value_under_cursor(None) {
             Value::List { vals, .. } => {
+                // NOTE: only the items that pass through the filter, if any, can be selected
+                let visible = self.visible_indices(&self.position, &vals);
+
                 let new = match current {
                     PathMember::Int {
                         val,
                         span,
                         optional,
                     } => PathMember::Int {
-                        val: if vals.is_empty() {
+                        val: if visible.is_empty() {
                             val
                         } else {
-                            match direction {
-                                Direction::Up(step) => val.saturating_sub(step).max(0),
-                                Direction::Down(step) => {
-                                    val.saturating_add(step).min(vals.len() - 1)
-                                }
-                                Direction::Top => 0,
-                                Direction::Bottom => vals.len() - 1,
-                                Direction::At(id) => id.min(vals.len() - 1),
-                            }
+                            let index = visible
+                                .iter()
+                                .position(|&i| i >= val)
+                                .unwrap_or(visible.len() - 1);
+                            let new_index = step_index(
+                                index,
+                                visible.len(),
+                                direction,
+                                self.config.wrap_navigation,
+                            );
+
+                            visible[new_index]
                         },
                         span,
                         optional,
@@ -73,23 +132,20 @@ impl App {
                         span,
                         optional,
                     } => {
-                        let cols = rec.columns().collect::<Vec<_>>();
+                        let cols = self.columns_in_view(&rec);
 
                         PathMember::String {
                             val: if cols.is_empty() {
                                 "".into()
                             } else {
                                 // NOTE: this should never fail
-                                let index = rec.columns().position(|x| x == &val).unwrap();
-                                let new_index = match direction {
-                                    Direction::Up(step) => index.saturating_sub(step).max(0),
-                                    Direction::Down(step) => {
-                                        index.saturating_add(step).min(cols.len() - 1)
-                                    }
-                                    Direction::Top => 0,
-                                    Direction::Bottom => cols.len() - 1,
-                                    Direction::At(id) => id.min(cols.len() - 1),
-                                };
+                                let index = cols.iter().position(|x| x == &val).unwrap();
+                                let new_index = step_index(
+                                    index,
+                                    cols.len(),
+                                    direction,
+                                    self.config.wrap_navigation,
+                                );
 
                                 cols[new_index].to_string()
                             },
@@ -105,13 +161,245 @@ impl App {
         }
     }
 
+    /// jump half a page up or down, moving the view along with the cursor, like `<c-u>` and
+    /// `<c-d>` in Vim
+    ///
+    /// the step of [`Direction::Up`] or [`Direction::Down`] is usually the half page, but can be
+    /// any amount, see [`crate::config::Config::scroll_amount`], the view being `2 * half_page`
+    /// rows high. the view is scrolled by as many rows as the cursor has moved and then, where
+    /// possible, the cursor is kept [`crate::config::Config::margin`] rows away from its top and
+    /// bottom, in the same way as when moving row by row.
+    ///
+    /// > :bulb: **Note**  
+    /// > any other direction does not scroll the view
+    pub(super) fn go_half_page_up_or_down(&mut self, direction: Direction, half_page: usize) {
+        if !matches!(direction, Direction::Up(_) | Direction::Down(_)) {
+            return self.go_up_or_down_in_data(direction);
+        }
+        if self.is_at_bottom() {
+            return;
+        }
+
+        let before = self.selected_row() as i32;
+        self.go_up_or_down_in_data(direction);
+        let cursor = self.selected_row() as i32;
+
+        let mut path = self.position.clone();
+        path.members.pop();
+        let nb_rows = match self.view_at(&path) {
+            Value::List { vals, .. } => vals.len(),
+            Value::Record { val: rec, .. } => rec.len(),
+            _ => 0,
+        } as i32;
+
+        let height = 2 * half_page as i32;
+        let margin = (self.config.margin as i32).min((height - 1) / 2);
+
+        let top = self.rendering_tops.pop().unwrap_or(0) + cursor - before;
+        let top = top
+            .min(cursor - margin)
+            .max(cursor + margin + 1 - height)
+            .min(nb_rows - height)
+            .max(0);
+        self.rendering_tops.push(top);
+    }
+
+    /// go to the row in the middle of the frame, which is `2 * half_page` rows high
+    ///
+    /// > :bulb: **Note**  
+    /// > the middle is the one of the rows that are visible, when the frame is not full
+    pub(super) fn go_to_middle_of_frame(&mut self, half_page: usize) {
+        if self.is_at_bottom() {
+            return;
+        }
+
+        let mut path = self.position.clone();
+        path.members.pop();
+        let nb_rows = match self.view_at(&path) {
+            Value::List { vals, .. } => vals.len(),
+            Value::Record { val: rec, .. } => rec.len(),
+            _ => 0,
+        };
+
+        let top = (*self.rendering_tops.last().unwrap_or(&0)).max(0) as usize;
+        let last = (top + 2 * half_page).min(nb_rows).saturating_sub(1);
+        self.go_up_or_down_in_data(Direction::At((top + last) / 2));
+    }
+
+    /// scroll the view so that the row under the cursor is at the *anchor* of the frame, which is
+    /// `2 * half_page` rows high, without moving the cursor
+    pub(super) fn scroll_to_cursor(&mut self, anchor: ScrollAnchor, half_page: usize) {
+        if self.is_at_bottom() {
+            return;
+        }
+
+        let mut path = self.position.clone();
+        path.members.pop();
+        let nb_rows = match self.view_at(&path) {
+            Value::List { vals, .. } => vals.len(),
+            Value::Record { val: rec, .. } => rec.len(),
+            _ => 0,
+        };
+
+        let top = anchored_top(
+            anchor,
+            self.selected_row() as i32,
+            2 * half_page as i32,
+            self.config.margin,
+            nb_rows,
+        );
+        self.rendering_tops.pop();
+        self.rendering_tops.push(top);
+    }
+
+    /// the index of the row under the cursor in the current level, as seen by the user
+    ///
+    /// > :bulb: **Note**  
+    /// > the items of a list hidden by the filter, if any, are not taken into account
+    pub(crate) fn selected_row(&self) -> usize {
+        let mut path = self.position.clone();
+        let current = path.members.pop();
+
+        match (current, self.value_under_cursor(Some(path.clone()))) {
+            (Some(PathMember::Int { val, .. }), Value::List { vals, .. }) => self
+                .visible_indices(&path, &vals)
+                .iter()
+                .position(|&i| i == val)
+                .unwrap_or(0),
+            (Some(PathMember::String { val, .. }), Value::Record { val: rec, .. }) => self
+                .columns_in_view(&rec)
+                .iter()
+                .position(|x| x == &val)
+                .unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    /// the rows of the current level that match the search query, as seen by the user
+    ///
+    /// a row matches if either its name, i.e. the key in a record, or its rendered data contains
+    /// the query.
+    fn search_matches(&self) -> Vec<usize> {
+        if self.search.is_empty() {
+            return vec![];
+        }
+
+        let mut path = self.position.clone();
+        path.members.pop();
+
+        repr_data(&self.view_at(&path), ReprOptions::from_config(&self.config))
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| {
+                row.data.contains(&self.search)
+                    || row.name.as_ref().is_some_and(|n| n.contains(&self.search))
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// jump to a row of the current level matching the search query
+    ///
+    /// depending on the direction (see [`Direction`]), this function will jump to
+    /// - [`Direction::Down`]: the next match after the cursor, wrapping around
+    /// - [`Direction::Up`]: the previous match before the cursor, wrapping around
+    /// - any other direction: the first match
+    ///
+    /// > :bulb: **Note**  
+    /// > - an empty query does not do anything
+    /// > - a query that matches nothing does not move the cursor and flashes a message
+    pub(super) fn go_to_search_match(&mut self, direction: Direction) {
+        if self.search.is_empty() {
+            return;
+        }
+
+        let matches = self.search_matches();
+        if matches.is_empty() {
+            self.flash = Some(format!("no match for '{}'", self.search));
+            return;
+        }
+
+        let row = wrapping_neighbour(&matches, self.selected_row(), direction);
+        self.go_up_or_down_in_data(Direction::At(row));
+    }
+
+    /// jump to the next or previous row of the current level holding a collection, i.e. a list or
+    /// a record, wrapping around, see [`Direction::Down`] and [`Direction::Up`]
+    ///
+    /// > :bulb: **Note**  
+    /// > the cursor does not move and a message is flashed if there is no collection at all
+    pub(super) fn go_to_collection(&mut self, direction: Direction) {
+        let mut path = self.position.clone();
+        path.members.pop();
+
+        let is_collection = |v: &Value| matches!(v, Value::List { .. } | Value::Record { .. });
+        let collections: Vec<usize> = match self.view_at(&path) {
+            Value::List { vals, .. } => vals
+                .iter()
+                .enumerate()
+                .filter(|(_, v)| is_collection(v))
+                .map(|(i, _)| i)
+                .collect(),
+            Value::Record { val: rec, .. } => rec
+                .values()
+                .enumerate()
+                .filter(|(_, v)| is_collection(v))
+                .map(|(i, _)| i)
+                .collect(),
+            _ => vec![],
+        };
+        if collections.is_empty() {
+            self.flash = Some("no list or record in the current level".into());
+            return;
+        }
+
+        let row = wrapping_neighbour(&collections, self.selected_row(), direction);
+        self.go_up_or_down_in_data(Direction::At(row));
+    }
+
+    /// jump to the first row of the current level starting with the jump prefix
+    ///
+    /// a row starts with the prefix if either its name, i.e. the key in a record, or its rendered
+    /// data does.
+    ///
+    /// > :bulb: **Note**  
+    /// > the cursor does not move and a message is flashed if no row starts with the prefix
+    pub(super) fn go_to_prefix_match(&mut self) {
+        let mut path = self.position.clone();
+        path.members.pop();
+
+        let row = repr_data(&self.view_at(&path), ReprOptions::from_config(&self.config))
+            .iter()
+            .position(|row| match &row.name {
+                Some(name) => name.starts_with(&self.jump_prefix),
+                None => row.data.starts_with(&self.jump_prefix),
+            });
+
+        match row {
+            Some(row) => self.go_up_or_down_in_data(Direction::At(row)),
+            None => self.flash = Some(format!("no row starting with '{}'", self.jump_prefix)),
+        }
+    }
+
     /// go one level deeper in the data
     ///
     /// > :bulb: **Note**  
     /// > this function will
     /// > - push a new *cell path* member to the state if there is more depth ahead
     /// > - mark the state as *at the bottom* if the value at the new depth is of a simple type
+    /// > - explore a range as a list, flashing to the user when not all its items can be
+    /// > - push a new rendering top in any case, even at the bottom, so that the scroll offset of
+    /// >   the current level is left untouched
     pub(super) fn go_deeper_in_data(&mut self) {
+        if let Value::Range { val, .. } = self.value_at(&self.position) {
+            if expand_range(&val, RANGE_LIMIT).1 {
+                self.flash = Some(format!(
+                    "only the first {} items of the range can be explored",
+                    RANGE_LIMIT
+                ));
+            }
+        }
+
         match self.value_under_cursor(None) {
             Value::List { vals, .. } => self.position.members.push(PathMember::Int {
                 val: 0,
@@ -119,7 +407,7 @@ impl App {
                 optional: vals.is_empty(),
             }),
             Value::Record { val: rec, .. } => {
-                let cols = rec.columns().cloned().collect::<Vec<_>>();
+                let cols = self.columns_in_view(&rec);
 
                 self.position.members.push(PathMember::String {
                     val: cols.first().unwrap_or(&"".to_string()).into(),
@@ -138,21 +426,76 @@ impl App {
     /// > :bulb: **Note**  
     /// > - the state is always marked as *not at the bottom*
     /// > - the state *cell path* can have it's last member popped if possible
+    /// > - the rendering top of the level being left is popped, which restores the scroll offset of
+    /// >   the parent level
+    /// > - going back from the keys of a record goes back to the record itself, at the same level
     pub(super) fn go_back_in_data(&mut self) {
+        let in_keys = self
+            .keys
+            .as_ref()
+            .is_some_and(|path| path.members.len() + 1 == self.position.members.len());
+        if !self.is_at_bottom() && in_keys {
+            self.leave_keys_view();
+            return;
+        }
+
         if !self.is_at_bottom() & (self.position.members.len() > 1) {
             self.position.members.pop();
         }
         self.mode = Mode::Normal;
         self.rendering_tops.pop();
     }
+
+    /// jump to an arbitrary cell path in the data, e.g. `foo.0.bar`, with the cursor on its last
+    /// member
+    ///
+    /// > :bulb: **Note**  
+    /// > - nothing happens and `false` is returned if the path does not exist in the data
+    /// > - the filter, if any, is removed because it could hide the target row
+    /// > - the keys of a record are not explored anymore, if they were
+    pub(crate) fn go_to_cell_path(&mut self, path: CellPath) -> bool {
+        if path.members.is_empty()
+            || self
+                .value
+                .clone()
+                .follow_cell_path(&path.members, false)
+                .is_err()
+        {
+            return false;
+        }
+
+        self.keys = None;
+        self.filter = None;
+        self.mode = Mode::Normal;
+        self.rendering_tops = vec![0; path.members.len()];
+        self.position = path;
+
+        true
+    }
+
+    /// go back to the top level of the data, on the key or index that has been entered from
+    ///
+    /// > :bulb: **Note**  
+    /// > - the state is always marked as *not at the bottom*
+    /// > - only the rendering top of the root level is kept
+    /// > - the keys of a record are not explored anymore, if they were
+    pub(super) fn go_to_root(&mut self) {
+        self.leave_keys_view();
+        self.position.members.truncate(1);
+        self.mode = Mode::Normal;
+        self.rendering_tops.truncate(1);
+    }
 }
 
 // TODO: add proper assert error messages
 #[cfg(test)]
 mod tests {
     use super::Direction;
-    use crate::app::App;
-    use nu_protocol::{ast::PathMember, record, Span, Value};
+    use crate::app::{App, RANGE_LIMIT};
+    use nu_protocol::{
+        ast::{PathMember, RangeInclusion},
+        record, Range, Span, Value,
+    };
 
     fn test_string_pathmember(val: impl Into<String>) -> PathMember {
         PathMember::String {
@@ -201,6 +544,68 @@ mod tests {
         }
     }
 
+    #[test]
+    fn wrap_around_a_list() {
+        let value = Value::test_list(vec![
+            Value::test_nothing(),
+            Value::test_nothing(),
+            Value::test_nothing(),
+        ]);
+        let mut app = App::from_value(value);
+        app.config.wrap_navigation = true;
+
+        let sequence = vec![
+            (Direction::Up(1), 2),
+            (Direction::Down(1), 0),
+            (Direction::Down(1), 1),
+            (Direction::Down(1), 2),
+            (Direction::Down(1), 0),
+            (Direction::Down(4), 1),
+            (Direction::Up(5), 2),
+            (Direction::Bottom, 2),
+            (Direction::Top, 0),
+        ];
+        for (direction, id) in sequence {
+            app.go_up_or_down_in_data(direction);
+            let expected = vec![test_int_pathmember(id)];
+            assert_eq!(app.position.members, expected);
+        }
+    }
+
+    #[test]
+    fn go_half_page_up_and_down() {
+        let value = Value::test_list((0..100).map(Value::test_int).collect());
+        let mut app = App::from_value(value);
+        app.config.margin = 2;
+
+        // (direction, cursor, top of the view of 20 rows)
+        let sequence = vec![
+            // the view follows the cursor, which keeps the margin above
+            (Direction::Down(10), 10, 8),
+            (Direction::Down(10), 20, 18),
+            (Direction::Up(10), 10, 8),
+            // the view cannot go above the top
+            (Direction::Up(10), 0, 0),
+            (Direction::Up(10), 0, 0),
+            // the view cannot go below the bottom
+            (Direction::Down(10), 10, 8),
+            (Direction::Bottom, 99, 8),
+            (Direction::Down(10), 99, 80),
+            (Direction::Up(10), 89, 72),
+            // the cursor keeps the margin above ...
+            (Direction::Top, 0, 72),
+            (Direction::Down(10), 10, 8),
+            // ... and below
+            (Direction::At(30), 30, 8),
+            (Direction::Down(10), 40, 23),
+        ];
+        for (direction, id, top) in sequence {
+            app.go_half_page_up_or_down(direction, 10);
+            assert_eq!(app.position.members, vec![test_int_pathmember(id)]);
+            assert_eq!(app.rendering_tops.last(), Some(&top), "at row {}", id);
+        }
+    }
+
     #[test]
     fn go_up_and_down_in_record() {
         let value = Value::test_record(record! {
@@ -232,6 +637,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn wrap_around_a_record() {
+        let value = Value::test_record(record! {
+            "a" => Value::test_nothing(),
+            "b" => Value::test_nothing(),
+            "c" => Value::test_nothing(),
+        });
+        let mut app = App::from_value(value);
+        app.config.wrap_navigation = true;
+
+        let sequence = vec![
+            (Direction::Up(1), "c"),
+            (Direction::Up(1), "b"),
+            (Direction::Down(1), "c"),
+            (Direction::Down(1), "a"),
+            (Direction::Up(4), "c"),
+        ];
+        for (direction, id) in sequence {
+            app.go_up_or_down_in_data(direction);
+            let expected = vec![test_string_pathmember(id)];
+            assert_eq!(app.position.members, expected);
+        }
+    }
+
     #[test]
     fn go_deeper() {
         let value = Value::test_list(vec![Value::test_record(record! {
@@ -251,6 +680,50 @@ mod tests {
         assert_eq!(app.position.members, expected);
     }
 
+    #[test]
+    fn go_deeper_in_ranges() {
+        let range = |end: Value| {
+            Value::test_range(
+                Range::new(
+                    Value::test_int(1),
+                    Value::test_nothing(),
+                    end,
+                    RangeInclusion::Inclusive,
+                    Span::test_data(),
+                )
+                .unwrap(),
+            )
+        };
+
+        let mut app = App::from_value(Value::test_record(record! {
+            "r" => range(Value::test_int(5)),
+        }));
+        app.go_deeper_in_data();
+        assert_eq!(
+            app.position.members,
+            vec![test_string_pathmember("r"), test_int_pathmember(0)]
+        );
+        assert_eq!(app.value_under_cursor(None), Value::test_int(1));
+        assert_eq!(app.flash, None);
+
+        app.go_up_or_down_in_data(Direction::Bottom);
+        assert_eq!(app.value_under_cursor(None), Value::test_int(5));
+
+        // only the first items of an unbounded range are explored
+        let mut app = App::from_value(Value::test_list(vec![range(Value::test_nothing())]));
+        app.go_deeper_in_data();
+        assert!(app.flash.is_some());
+        app.go_up_or_down_in_data(Direction::Bottom);
+        assert_eq!(
+            app.value_under_cursor(None),
+            Value::test_int(RANGE_LIMIT as i64)
+        );
+
+        // a range at the root is explored right away
+        let app = App::from_value(range(Value::test_int(3)));
+        assert_eq!(app.position.members, vec![test_int_pathmember(0)]);
+    }
+
     #[test]
     fn hit_bottom() {
         let value = Value::test_nothing();
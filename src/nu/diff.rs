@@ -0,0 +1,108 @@
+//! compare two records side by side, see [`crate::config::Config::diff`]
+use nu_protocol::{Record, Value};
+
+/// how a key differs from one record to the other
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum DiffKind {
+    /// the key has the same value in both records
+    Same,
+    /// the key is in both records but with different values
+    Changed,
+    /// the key is only in the first record
+    Removed,
+    /// the key is only in the second record
+    Added,
+}
+
+/// the two records to compare, if the *value* is a list of exactly two records
+pub(crate) fn diff_pair(value: &Value) -> Option<(&Record, &Record)> {
+    match value {
+        Value::List { vals, .. } => match vals.as_slice() {
+            [Value::Record { val: a, .. }, Value::Record { val: b, .. }] => Some((a, b)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// compare the records *a* and *b* key by key, one level deep
+///
+/// the keys of *a* come first, in their order, followed by the keys only found in *b*.
+///
+/// > :bulb: **Note**  
+/// > nested values are compared as a whole, i.e. a record with a single different field is
+/// > [`DiffKind::Changed`]
+pub(crate) fn diff_records(a: &Record, b: &Record) -> Vec<(String, DiffKind)> {
+    let mut diff = a
+        .iter()
+        .map(|(key, val)| {
+            let kind = match b.get(key) {
+                Some(other) if other == val => DiffKind::Same,
+                Some(_) => DiffKind::Changed,
+                None => DiffKind::Removed,
+            };
+            (key.clone(), kind)
+        })
+        .collect::<Vec<_>>();
+    diff.extend(
+        b.columns()
+            .filter(|key| !a.contains(key))
+            .map(|key| (key.clone(), DiffKind::Added)),
+    );
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use nu_protocol::{record, Record, Value};
+
+    use super::{diff_pair, diff_records, DiffKind};
+
+    #[test]
+    fn diff_two_records() {
+        let a = record! {
+            "same" => Value::test_int(1),
+            "changed" => Value::test_string("foo"),
+            "removed" => Value::test_bool(true),
+            "nested" => Value::test_record(record! { "x" => Value::test_int(1) }),
+        };
+        let b = record! {
+            "added" => Value::test_nothing(),
+            "nested" => Value::test_record(record! { "x" => Value::test_int(2) }),
+            "changed" => Value::test_int(1),
+            "same" => Value::test_int(1),
+        };
+
+        assert_eq!(
+            diff_records(&a, &b),
+            vec![
+                ("same".into(), DiffKind::Same),
+                ("changed".into(), DiffKind::Changed),
+                ("removed".into(), DiffKind::Removed),
+                ("nested".into(), DiffKind::Changed),
+                ("added".into(), DiffKind::Added),
+            ]
+        );
+        assert!(diff_records(&a, &a)
+            .iter()
+            .all(|(_, kind)| kind == &DiffKind::Same));
+        assert_eq!(diff_records(&Record::new(), &Record::new()), vec![]);
+    }
+
+    #[test]
+    fn pairs_of_records() {
+        let rec = Value::test_record(record! { "a" => Value::test_int(1) });
+
+        assert!(diff_pair(&Value::test_list(vec![rec.clone(), rec.clone()])).is_some());
+        assert!(diff_pair(&Value::test_list(vec![rec.clone()])).is_none());
+        assert!(diff_pair(&Value::test_list(vec![
+            rec.clone(),
+            rec.clone(),
+            rec.clone()
+        ]))
+        .is_none());
+        assert!(diff_pair(&Value::test_list(vec![rec.clone(), Value::test_int(1)])).is_none());
+        assert!(diff_pair(&rec).is_none());
+    }
+}
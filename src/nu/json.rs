@@ -0,0 +1,116 @@
+//! convert [`Value`]s to and from JSON strings
+use nu_protocol::{Record, Span, Value};
+use serde_json::{Map, Number};
+
+/// convert a [`Value`] to a single-line JSON string
+///
+/// JSON has fewer types than Nushell, thus
+/// - file sizes and durations become integers, in bytes and nanoseconds
+/// - dates, globs, ranges and cell paths become strings
+/// - binary data becomes a list of bytes
+///
+/// closures and errors can't be represented and are rejected.
+pub(crate) fn to_json(value: &Value) -> Result<String, String> {
+    let json = value_to_json(value)?;
+    serde_json::to_string(&json).map_err(|err| err.to_string())
+}
+
+/// parse a JSON string into a [`Value`], keeping the order of the keys in objects
+pub(crate) fn from_json(json: &str, span: Span) -> Result<Value, String> {
+    let json: serde_json::Value = serde_json::from_str(json).map_err(|err| err.to_string())?;
+    Ok(json_to_value(json, span))
+}
+
+pub(crate) fn value_to_json(value: &Value) -> Result<serde_json::Value, String> {
+    let json = match value {
+        Value::Nothing { .. } => serde_json::Value::Null,
+        Value::Bool { val, .. } => serde_json::Value::Bool(*val),
+        Value::Int { val, .. } => serde_json::Value::Number((*val).into()),
+        Value::Float { val, .. } => match Number::from_f64(*val) {
+            Some(n) => serde_json::Value::Number(n),
+            None => return Err(format!("{} is not a valid JSON number", val)),
+        },
+        Value::Filesize { val, .. } => serde_json::Value::Number((*val).into()),
+        Value::Duration { val, .. } => serde_json::Value::Number((*val).into()),
+        Value::String { val, .. } | Value::Glob { val, .. } => {
+            serde_json::Value::String(val.clone())
+        }
+        Value::Date { val, .. } => serde_json::Value::String(val.to_rfc3339()),
+        Value::CellPath { val, .. } => serde_json::Value::String(val.to_string()),
+        Value::Range { val, .. } => serde_json::Value::String(val.to_string()),
+        Value::Binary { val, .. } => serde_json::Value::Array(
+            val.iter()
+                .map(|b| serde_json::Value::Number((*b).into()))
+                .collect(),
+        ),
+        Value::List { vals, .. } => serde_json::Value::Array(
+            vals.iter()
+                .map(value_to_json)
+                .collect::<Result<Vec<_>, _>>()?,
+        ),
+        Value::Record { val: rec, .. } => {
+            let mut map = Map::new();
+            for (col, val) in rec.iter() {
+                map.insert(col.clone(), value_to_json(val)?);
+            }
+            serde_json::Value::Object(map)
+        }
+        x => return Err(format!("{} can't be represented in JSON", x.get_type())),
+    };
+
+    Ok(json)
+}
+
+pub(crate) fn json_to_value(json: serde_json::Value, span: Span) -> Value {
+    match json {
+        serde_json::Value::Null => Value::nothing(span),
+        serde_json::Value::Bool(b) => Value::bool(b, span),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Value::int(i, span),
+            // NOTE: integers too large for an `i64` and floats
+            None => Value::float(n.as_f64().unwrap_or(f64::NAN), span),
+        },
+        serde_json::Value::String(s) => Value::string(s, span),
+        serde_json::Value::Array(vals) => Value::list(
+            vals.into_iter().map(|v| json_to_value(v, span)).collect(),
+            span,
+        ),
+        serde_json::Value::Object(map) => Value::record(
+            map.into_iter()
+                .map(|(col, val)| (col, json_to_value(val, span)))
+                .collect::<Record>(),
+            span,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nu_protocol::{record, Span, Value};
+
+    use super::{from_json, to_json};
+
+    #[test]
+    fn round_trip() {
+        let value = Value::test_record(record! {
+            "name" => Value::test_string("explore"),
+            "version" => Value::test_int(1),
+            "ratio" => Value::test_float(0.5),
+            "tags" => Value::test_list(vec![Value::test_string("tui"), Value::test_nothing()]),
+            "enabled" => Value::test_bool(true),
+        });
+
+        let json = to_json(&value).unwrap();
+        assert_eq!(
+            json,
+            r#"{"name":"explore","version":1,"ratio":0.5,"tags":["tui",null],"enabled":true}"#
+        );
+        assert_eq!(from_json(&json, Span::test_data()), Ok(value));
+    }
+
+    #[test]
+    fn invalid_json() {
+        assert!(from_json("{a: 1}", Span::test_data()).is_err());
+        assert!(to_json(&Value::test_float(f64::NAN)).is_err());
+    }
+}
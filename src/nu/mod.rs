@@ -1,4 +1,7 @@
 #[cfg(test)]
 pub(super) mod cell_path;
+pub(super) mod diff;
+pub(super) mod json;
+pub(super) mod serialize;
 pub(super) mod strings;
 pub(super) mod value;
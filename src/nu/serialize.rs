@@ -0,0 +1,119 @@
+//! serialize [`Value`]s to text in various formats, see `:peek` in [`crate::command`]
+use std::fmt;
+
+use nu_protocol::{Span, Value};
+use nuon::{to_nuon, ToStyle};
+
+use super::json::{json_to_value, to_json, value_to_json};
+
+/// a text format a [`Value`] can be serialized to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Format {
+    Json,
+    Yaml,
+    Toml,
+    Nuon,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let repr = match self {
+            Self::Json => "JSON",
+            Self::Yaml => "YAML",
+            Self::Toml => "TOML",
+            Self::Nuon => "NUON",
+        };
+        write!(f, "{}", repr)
+    }
+}
+
+/// convert a [`Value`] to a YAML string, through its JSON representation, see
+/// [`super::json::to_json`] for how the types of Nushell are converted
+pub(crate) fn to_yaml(value: &Value) -> Result<String, String> {
+    let json = value_to_json(value)?;
+    serde_yaml_ng::to_string(&json).map_err(|err| err.to_string())
+}
+
+/// parse a YAML string into a [`Value`], keeping the order of the keys in mappings
+pub(crate) fn from_yaml(yaml: &str, span: Span) -> Result<Value, String> {
+    let json: serde_json::Value = serde_yaml_ng::from_str(yaml).map_err(|err| err.to_string())?;
+    Ok(json_to_value(json, span))
+}
+
+/// serialize the *value* to a string in the format *fmt*
+///
+/// YAML and TOML go through the JSON representation of the *value*, see
+/// [`super::json::to_json`] for how the types of Nushell are converted.
+///
+/// > :bulb: **Note**  
+/// > TOML documents are tables, thus only records can be serialized to TOML, and without `null`
+/// > in them
+pub(crate) fn serialize_value(value: &Value, fmt: Format) -> Result<String, String> {
+    let res = match fmt {
+        Format::Json => to_json(value),
+        Format::Yaml => to_yaml(value),
+        Format::Toml => match value {
+            Value::Record { .. } => value_to_json(value)
+                .and_then(|json| toml::to_string(&json).map_err(|err| err.to_string())),
+            x => Err(format!("{} is not a record", x.get_type())),
+        },
+        Format::Nuon => to_nuon(value, ToStyle::Raw, None).map_err(|err| err.to_string()),
+    };
+
+    res.map_err(|err| format!("could not convert to {}: {}", fmt, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use nu_protocol::{engine::Closure, record, BlockId, Value};
+
+    use super::{serialize_value, Format};
+
+    fn value() -> Value {
+        Value::test_record(record! {
+            "name" => Value::test_string("explore"),
+            "tags" => Value::test_list(vec![Value::test_string("tui"), Value::test_int(1)]),
+        })
+    }
+
+    #[test]
+    fn serialize_to_json() {
+        assert_eq!(
+            serialize_value(&value(), Format::Json),
+            Ok(r#"{"name":"explore","tags":["tui",1]}"#.into())
+        );
+        assert!(serialize_value(&Value::test_float(f64::NAN), Format::Json).is_err());
+    }
+
+    #[test]
+    fn serialize_to_nuon() {
+        assert_eq!(
+            serialize_value(&value(), Format::Nuon),
+            Ok(r#"{name: explore, tags: [tui, 1]}"#.into())
+        );
+
+        let closure = Value::test_closure(Closure {
+            block_id: BlockId::new(0),
+            captures: vec![],
+        });
+        assert!(serialize_value(&closure, Format::Nuon).is_err());
+    }
+
+    #[test]
+    fn serialize_to_yaml_and_toml() {
+        assert_eq!(
+            serialize_value(&value(), Format::Yaml),
+            Ok("name: explore\ntags:\n- tui\n- 1\n".into())
+        );
+        assert_eq!(
+            serialize_value(&value(), Format::Toml),
+            Ok("name = \"explore\"\ntags = [\"tui\", 1]\n".into())
+        );
+        assert!(serialize_value(&Value::test_int(1), Format::Toml).is_err());
+        assert!(serialize_value(
+            &Value::test_record(record! { "a" => Value::test_nothing() }),
+            Format::Toml
+        )
+        .is_err());
+    }
+}
@@ -1,7 +1,15 @@
+use std::path::PathBuf;
+
+use nu_protocol::{record, Span, Value};
+
 #[derive(Debug, PartialEq)]
 pub(crate) enum SpecialString {
     Url,
     Path,
+    /// a JSON object or array, e.g. `{"a": 1}`, that can be parsed in place
+    Json,
+    /// an integer or a float, e.g. `-12` or `1.5e3`
+    Number,
 }
 
 impl std::fmt::Display for SpecialString {
@@ -9,6 +17,8 @@ impl std::fmt::Display for SpecialString {
         let repr = match self {
             Self::Url => "url".to_string(),
             Self::Path => "path".to_string(),
+            Self::Json => "json".to_string(),
+            Self::Number => "number".to_string(),
         };
         write!(f, "{}", repr)
     }
@@ -16,7 +26,12 @@ impl std::fmt::Display for SpecialString {
 
 impl SpecialString {
     pub(crate) fn parse(input: &str) -> Option<Self> {
-        if let Ok(url) = url::Url::parse(input) {
+        // NOTE: `f64` also parses `inf` and `NaN`, which are not numbers written by hand
+        if input.parse::<f64>().is_ok() && input.bytes().any(|b| b.is_ascii_digit()) {
+            Some(Self::Number)
+        } else if input.trim_start().starts_with(['{', '[']) && is_json(input) {
+            Some(Self::Json)
+        } else if let Ok(url) = url::Url::parse(input) {
             if url.scheme() == "file" {
                 Some(Self::Path)
             } else {
@@ -32,9 +47,51 @@ impl SpecialString {
     }
 }
 
+fn is_json(input: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(input).is_ok()
+}
+
+/// stat the path of a [`SpecialString::Path`] string, following the symlinks, as a record
+/// `{ path: string, exists: bool, is_dir: bool, size: filesize }`
+///
+/// the path is resolved to an absolute one, relative to the current directory, and `file://`
+/// URLs are turned into their local path.
+///
+/// > :bulb: **Note**  
+/// > a path that does not exist is not an error: it is not a directory and its size is `null`
+pub(crate) fn stat_path(input: &str) -> Value {
+    let path = match url::Url::parse(input)
+        .ok()
+        .and_then(|url| url.to_file_path().ok())
+    {
+        Some(path) => path,
+        None => PathBuf::from(input),
+    };
+    let path = std::fs::canonicalize(&path).unwrap_or_else(|_| {
+        std::env::current_dir()
+            .map(|dir| dir.join(&path))
+            .unwrap_or(path)
+    });
+    let metadata = std::fs::metadata(&path).ok();
+
+    let record = record! {
+        "path" => Value::string(path.to_string_lossy(), Span::unknown()),
+        "exists" => Value::bool(metadata.is_some(), Span::unknown()),
+        "is_dir" => Value::bool(metadata.as_ref().is_some_and(|m| m.is_dir()), Span::unknown()),
+        "size" => match metadata {
+            Some(m) => Value::filesize(m.len() as i64, Span::unknown()),
+            None => Value::nothing(Span::unknown()),
+        },
+    };
+
+    Value::record(record, Span::unknown())
+}
+
 #[cfg(test)]
 mod special_strings_tests {
-    use super::SpecialString;
+    use nu_protocol::{record, Value};
+
+    use super::{stat_path, SpecialString};
 
     #[test]
     fn parse_strings() {
@@ -49,6 +106,18 @@ mod special_strings_tests {
             ("file:", Some(SpecialString::Path)),
             ("normal string with a / inside", Some(SpecialString::Path)),
             ("normal string with \na / inside", None),
+            ("12", Some(SpecialString::Number)),
+            ("-12", Some(SpecialString::Number)),
+            ("1.5e3", Some(SpecialString::Number)),
+            ("inf", None),
+            ("NaN", None),
+            ("12 apples", None),
+            (r#"{"a": 1, "b": "/path"}"#, Some(SpecialString::Json)),
+            ("[1, 2, 3]", Some(SpecialString::Json)),
+            (" [] ", Some(SpecialString::Json)),
+            ("{a: 1}", None),
+            ("[1, 2", None),
+            ("true", None),
         ];
 
         for (input, expected) in cases {
@@ -59,4 +128,54 @@ mod special_strings_tests {
             );
         }
     }
+
+    #[test]
+    fn stat_paths() {
+        let dir = std::env::temp_dir().join(format!("nu_plugin_explore-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir = std::fs::canonicalize(dir).unwrap();
+        let file = dir.join("stat.txt");
+        std::fs::write(&file, "hello").unwrap();
+
+        let cases = vec![
+            (
+                file.to_string_lossy().to_string(),
+                Value::test_record(record! {
+                    "path" => Value::test_string(file.to_string_lossy()),
+                    "exists" => Value::test_bool(true),
+                    "is_dir" => Value::test_bool(false),
+                    "size" => Value::test_filesize(5),
+                }),
+            ),
+            (
+                format!("file://{}", file.to_string_lossy()),
+                Value::test_record(record! {
+                    "path" => Value::test_string(file.to_string_lossy()),
+                    "exists" => Value::test_bool(true),
+                    "is_dir" => Value::test_bool(false),
+                    "size" => Value::test_filesize(5),
+                }),
+            ),
+            (
+                dir.join("missing").to_string_lossy().to_string(),
+                Value::test_record(record! {
+                    "path" => Value::test_string(dir.join("missing").to_string_lossy()),
+                    "exists" => Value::test_bool(false),
+                    "is_dir" => Value::test_bool(false),
+                    "size" => Value::test_nothing(),
+                }),
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(stat_path(&input), expected, "on input {input}");
+        }
+
+        let actual = stat_path(&dir.to_string_lossy());
+        assert_eq!(
+            actual.get_data_by_key("is_dir"),
+            Some(Value::test_bool(true))
+        );
+
+        std::fs::remove_file(file).unwrap();
+    }
 }
@@ -1,11 +1,13 @@
-use std::collections::HashMap;
+use std::{cmp::Ordering, collections::HashMap};
 
 use nu_protocol::{
     ast::{CellPath, PathMember},
-    record, Record, Span, Type, Value,
+    record, Range, Record, Signals, Span, Type, Value,
 };
 
-#[derive(Debug, PartialEq)]
+use crate::app::RANGE_LIMIT;
+
+#[derive(Clone, Debug, PartialEq)]
 pub(crate) enum Table {
     /// value is a list but with no items in it
     Empty,
@@ -48,10 +50,30 @@ impl Table {
     }
 }
 
+/// expand a *range* into the list of its first items, at most *limit* of them, telling whether
+/// there are more, e.g. for unbounded ranges such as `1..`
+pub(crate) fn expand_range(range: &Range, limit: usize) -> (Vec<Value>, bool) {
+    let mut items = range.into_range_iter(Span::unknown(), Signals::empty());
+    let vals = items.by_ref().take(limit).collect::<Vec<_>>();
+    let truncated = items.next().is_some();
+
+    (vals, truncated)
+}
+
+fn is_unbounded(range: &Range) -> bool {
+    match range {
+        Range::IntRange(range) => range.is_unbounded(),
+        Range::FloatRange(range) => range.is_unbounded(),
+    }
+}
+
 /// mutate the input `value`, changing the _value_ at `cell_path` into the `cell` argument
 ///
+/// a bounded range on the way to the `cell` is turned into the list of all its items.
+///
 /// > **Note**  
-/// > returns [`None`] if the `cell_path` is not valid in `value`.
+/// > returns [`None`] if the `cell_path` is not valid in `value` or goes through an unbounded
+/// > range or a range of more than [`RANGE_LIMIT`] items, which can't be turned into a list.
 pub(crate) fn mutate_value_cell(
     value: &Value,
     cell_path: &CellPath,
@@ -84,10 +106,19 @@ pub(crate) fn mutate_value_cell(
             cell_path.members.remove(0);
 
             let mut vals = vals.clone();
-            vals[id] = mutate_value_cell(&vals[id], &cell_path, cell).unwrap();
+            vals[id] = mutate_value_cell(&vals[id], &cell_path, cell)?;
 
             Value::list(vals, Span::unknown())
         }
+        Value::Range { val, .. } if is_unbounded(val) => return None,
+        Value::Range { val, .. } => {
+            // NOTE: a huge range, e.g. `1..1_000_000_000`, would not fit in memory as a list
+            let (vals, truncated) = expand_range(val, RANGE_LIMIT);
+            if truncated {
+                return None;
+            }
+            return mutate_value_cell(&Value::list(vals, Span::unknown()), &cell_path, cell);
+        }
         Value::Record { val: rec, .. } => {
             let col = match first {
                 PathMember::String { val, .. } => val.clone(),
@@ -104,12 +135,12 @@ pub(crate) fn mutate_value_cell(
                 .enumerate()
                 .map(|(i, v)| {
                     if i == id {
-                        mutate_value_cell(&v, &cell_path, cell).unwrap()
+                        mutate_value_cell(&v, &cell_path, cell)
                     } else {
-                        v
+                        Some(v)
                     }
                 })
-                .collect();
+                .collect::<Option<_>>()?;
 
             Value::record(
                 // NOTE: this cannot fail because `cols` and `vals` have the same length by
@@ -125,7 +156,12 @@ pub(crate) fn mutate_value_cell(
     Some(res)
 }
 
-pub(crate) fn is_table(value: &Value) -> Table {
+/// check whether a value is a valid table, i.e. a list of records with compatible columns
+///
+/// when `loose` is set, the rows do not need to share the same columns: the table is then made
+/// of the union of all the columns and a missing cell is the same as a `null` one, see
+/// [`table_columns`].
+pub(crate) fn is_table(value: &Value, loose: bool) -> Table {
     match value {
         Value::List { vals, .. } => {
             if vals.is_empty() {
@@ -134,6 +170,7 @@ pub(crate) fn is_table(value: &Value) -> Table {
 
             // extract the columns of each row as hashmaps for easier access
             let mut rows = Vec::new();
+            let mut recs: Vec<&Record> = Vec::new();
             for (i, val) in vals.iter().enumerate() {
                 match val.get_type() {
                     Type::Record(fields) => rows.push(
@@ -143,24 +180,29 @@ pub(crate) fn is_table(value: &Value) -> Table {
                     ),
                     t => return Table::RowNotARecord(i, t),
                 };
+                // NOTE: `val` has just been checked to be a record
+                recs.push(val.as_record().unwrap());
             }
 
             // check the number of columns for each row
-            let n = rows[0].keys().len();
-            for (i, row) in rows.iter().skip(1).enumerate() {
-                if row.keys().len() != n {
-                    return Table::RowIncompatibleLen(i + 1, row.keys().len(), n);
+            if !loose {
+                let n = rows[0].keys().len();
+                for (i, row) in rows.iter().skip(1).enumerate() {
+                    if row.keys().len() != n {
+                        return Table::RowIncompatibleLen(i + 1, row.keys().len(), n);
+                    }
                 }
             }
 
             // check the actual types for each column
             // - if a row has a null, it doesn't count as "not a table"
             // - if two rows are numeric, then the check can continue
-            for (key, val) in rows[0].iter() {
-                let mut ty = val;
+            // - if the table is loose, a missing cell is the same as a null
+            for key in table_columns(recs[..if loose { recs.len() } else { 1 }].iter().copied()) {
+                let mut ty = &Type::Nothing;
 
-                for (i, row) in rows.iter().skip(1).enumerate() {
-                    match row.get(key) {
+                for (i, row) in rows.iter().enumerate() {
+                    match row.get(&key) {
                         Some(v) => match ty {
                             Type::Nothing => ty = v,
                             _ => {
@@ -173,7 +215,7 @@ pub(crate) fn is_table(value: &Value) -> Table {
                                         | (v != ty)
                                     {
                                         return Table::RowIncompatibleType(
-                                            i + 1,
+                                            i,
                                             key.clone(),
                                             v.clone(),
                                             ty.clone(),
@@ -182,10 +224,11 @@ pub(crate) fn is_table(value: &Value) -> Table {
                                 }
                             }
                         },
+                        None if loose => {}
                         None => {
                             let mut keys = row.keys().cloned().collect::<Vec<String>>();
                             keys.sort();
-                            return Table::RowInvalidKey(i + 1, key.clone(), keys);
+                            return Table::RowInvalidKey(i, key.clone(), keys);
                         }
                     }
                 }
@@ -197,6 +240,128 @@ pub(crate) fn is_table(value: &Value) -> Table {
     }
 }
 
+/// compute the columns of a table, i.e. the union of the columns of all its rows, in order of
+/// first appearance
+pub(crate) fn table_columns<'a>(table: impl IntoIterator<Item = &'a Record>) -> Vec<String> {
+    let mut columns: Vec<String> = vec![];
+    for row in table {
+        for col in row.columns() {
+            if !columns.contains(col) {
+                columns.push(col.clone());
+            }
+        }
+    }
+
+    columns
+}
+
+/// compute the type of each column of a valid table
+///
+/// numeric columns with mixed types, e.g. `int` and `float`, are collapsed into `number` and
+/// `null` cells do not count towards the type of their column.
+///
+/// > **Note**  
+/// > `table` is expected to be a valid table, possibly loose, see [`is_table`].
+pub(crate) fn table_column_types(table: &[Record]) -> Vec<Type> {
+    let columns = table_columns(table);
+    let mut types = vec![Type::Nothing; columns.len()];
+
+    for row in table {
+        for (j, col) in columns.iter().enumerate() {
+            // NOTE: a missing cell, in a loose table, is the same as a `null` one
+            let cell_type = row.get(col).map(|v| v.get_type()).unwrap_or(Type::Nothing);
+            if !matches!(cell_type, Type::Nothing) {
+                if types[j].is_numeric() && cell_type.is_numeric() && (types[j] != cell_type) {
+                    types[j] = Type::Number;
+                } else {
+                    types[j] = cell_type;
+                }
+            }
+        }
+    }
+
+    types
+}
+
+/// compute the *schema* of a value
+///
+/// - a table gives a record of `{column: type}`
+/// - a record gives a record of `{field: type}`
+/// - a list gives the type of its elements
+/// - any other value gives its own type
+pub(crate) fn schema(value: &Value) -> Value {
+    match value {
+        Value::List { vals, .. } => {
+            if matches!(is_table(value, false), Table::IsValid) {
+                let recs = vals
+                    .iter()
+                    // NOTE: all the rows of a valid table are records
+                    .map(|v| v.as_record().unwrap().clone())
+                    .collect::<Vec<Record>>();
+
+                let mut schema = Record::new();
+                for (col, ty) in recs[0].columns().zip(table_column_types(&recs)) {
+                    schema.push(col, Value::string(ty.to_string(), Span::unknown()));
+                }
+
+                Value::record(schema, Span::unknown())
+            } else {
+                let ty = match value.get_type() {
+                    Type::List(ty) => *ty,
+                    ty => ty,
+                };
+                Value::string(ty.to_string(), Span::unknown())
+            }
+        }
+        Value::Record { val: rec, .. } => {
+            let mut schema = Record::new();
+            for (col, val) in rec.iter() {
+                schema.push(
+                    col,
+                    Value::string(val.get_type().to_string(), Span::unknown()),
+                );
+            }
+
+            Value::record(schema, Span::unknown())
+        }
+        v => Value::string(v.get_type().to_string(), Span::unknown()),
+    }
+}
+
+/// count the number of cells that differ between an `old` and a `new` value
+///
+/// - two records are compared field by field, a field that is only in one of them counts as one
+///   change
+/// - two lists are compared item by item, an item that is only in one of them counts as one change
+/// - any other pair of values counts as one change if they are not equal
+pub(crate) fn count_changes(old: &Value, new: &Value) -> usize {
+    match (old, new) {
+        (Value::Record { val: old, .. }, Value::Record { val: new, .. }) => {
+            old.iter()
+                .map(|(col, val)| match new.get(col) {
+                    Some(v) => count_changes(val, v),
+                    None => 1,
+                })
+                .sum::<usize>()
+                + new.columns().filter(|col| !old.contains(col)).count()
+        }
+        (Value::List { vals: old, .. }, Value::List { vals: new, .. }) => {
+            old.iter()
+                .zip(new.iter())
+                .map(|(a, b)| count_changes(a, b))
+                .sum::<usize>()
+                + old.len().abs_diff(new.len())
+        }
+        (old, new) => {
+            if old == new {
+                0
+            } else {
+                1
+            }
+        }
+    }
+}
+
 /// this effectively implements the following idempotent `transpose` command written in Nushell
 /// ```nushell
 /// alias "core transpose" = transpose
@@ -227,7 +392,7 @@ pub(crate) fn is_table(value: &Value) -> Table {
 /// ```
 // WARNING: some _unwraps_ haven't been proven to be safe in this function
 pub(crate) fn transpose(value: &Value) -> Value {
-    if matches!(is_table(value), Table::IsValid) {
+    if matches!(is_table(value, false), Table::IsValid) {
         let value_rows = match value {
             Value::List { vals, .. } => vals,
             _ => return value.clone(),
@@ -327,19 +492,208 @@ pub(crate) fn transpose(value: &Value) -> Value {
     }
 }
 
+/// compare two values, to sort them
+///
+/// values of the same type, or numbers, are compared naturally, otherwise they are compared as
+/// strings
+pub(crate) fn compare_values(a: &Value, b: &Value) -> Ordering {
+    let same_kind = std::mem::discriminant(a) == std::mem::discriminant(b)
+        || (matches!(a, Value::Int { .. } | Value::Float { .. })
+            && matches!(b, Value::Int { .. } | Value::Float { .. }));
+
+    match a.partial_cmp(b) {
+        Some(ordering) if same_kind => ordering,
+        _ => {
+            let config = nu_protocol::Config::default();
+            a.to_expanded_string(" ", &config)
+                .cmp(&b.to_expanded_string(" ", &config))
+        }
+    }
+}
+
+/// whether or not a value contains the *query*
+///
+/// the query is searched as a substring of the value, e.g. `"ell"` matches `"hello"` and `12`
+/// matches `123`, and of the values inside lists and records, i.e. a row of a table matches if
+/// any of its columns does.
+///
+/// > **Note**  
+/// > the column names themselves are not searched
+pub(crate) fn matches_query(value: &Value, query: &str) -> bool {
+    match value {
+        Value::Record { val: rec, .. } => rec.values().any(|v| matches_query(v, query)),
+        Value::List { vals, .. } => vals.iter().any(|v| matches_query(v, query)),
+        value => value
+            .to_expanded_string(" ", &nu_protocol::Config::default())
+            .contains(query),
+    }
+}
+
+/// only keep the items of a list that match the *query*, see [`matches_query`]
+///
+/// any other value is given back untouched.
+pub(crate) fn filter_rows(value: &Value, query: &str) -> Value {
+    match value {
+        Value::List { vals, .. } => Value::list(
+            vals.iter()
+                .filter(|v| matches_query(v, query))
+                .cloned()
+                .collect(),
+            Span::unknown(),
+        ),
+        value => value.clone(),
+    }
+}
+
+/// sort a value, giving it back untouched if it can't be sorted
+///
+/// - a record is sorted by its keys
+/// - a list is sorted by its items, or by the given `column` of its items, a missing column
+///   being the same as `null`
+///
+/// > **Note**  
+/// > the sort is stable, i.e. equal items keep their order, even when `descending`
+pub(crate) fn sort(value: &Value, column: Option<&str>, descending: bool) -> Value {
+    let order = |ordering: Ordering| {
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    };
+
+    match value {
+        Value::Record { val: rec, .. } => {
+            let mut fields = rec
+                .iter()
+                .map(|(col, val)| (col.clone(), val.clone()))
+                .collect::<Vec<_>>();
+            fields.sort_by(|(a, _), (b, _)| order(a.cmp(b)));
+
+            Value::record(fields.into_iter().collect(), Span::unknown())
+        }
+        Value::List { vals, .. } => {
+            let key = |v: &Value| match column {
+                Some(col) => v
+                    .as_record()
+                    .ok()
+                    .and_then(|rec| rec.get(col).cloned())
+                    .unwrap_or(Value::nothing(Span::unknown())),
+                None => v.clone(),
+            };
+
+            let mut vals = vals.clone();
+            vals.sort_by(|a, b| order(compare_values(&key(a), &key(b))));
+
+            Value::list(vals, Span::unknown())
+        }
+        v => v.clone(),
+    }
+}
+
+/// swap the items *i* and *j* of a list, or the fields *i* and *j* of a record, giving the value
+/// back untouched if it's neither or if any of the indices is out of bounds
+pub(crate) fn swap(value: &Value, i: usize, j: usize) -> Value {
+    match value {
+        Value::Record { val: rec, .. } if i.max(j) < rec.len() => {
+            let mut fields = rec
+                .iter()
+                .map(|(col, val)| (col.clone(), val.clone()))
+                .collect::<Vec<_>>();
+            fields.swap(i, j);
+
+            Value::record(fields.into_iter().collect(), Span::unknown())
+        }
+        Value::List { vals, .. } if i.max(j) < vals.len() => {
+            let mut vals = vals.clone();
+            vals.swap(i, j);
+
+            Value::list(vals, Span::unknown())
+        }
+        v => v.clone(),
+    }
+}
+
+/// the rough cost in bytes of each item of a list or field of a record, on top of its value
+const ITEM_OVERHEAD: usize = 8;
+
+/// estimate how many bytes a value takes in memory, roughly
+///
+/// - strings, globs and binary data cost their length
+/// - lists and records cost [`ITEM_OVERHEAD`] per item or field, plus the length of the keys
+/// - booleans cost 1 byte and `null` is free
+/// - any other value, e.g. ints, floats or dates, costs 8 bytes
+///
+/// > :bulb: **Note**  
+/// > this is only an estimate, e.g. to spot the big parts of some data, not the exact allocation
+pub(crate) fn estimate_size(value: &Value) -> usize {
+    match value {
+        Value::String { val, .. } | Value::Glob { val, .. } => val.len(),
+        Value::Binary { val, .. } => val.len(),
+        Value::List { vals, .. } => vals.iter().map(|v| ITEM_OVERHEAD + estimate_size(v)).sum(),
+        Value::Record { val: rec, .. } => rec
+            .iter()
+            .map(|(k, v)| ITEM_OVERHEAD + k.len() + estimate_size(v))
+            .sum(),
+        Value::Bool { .. } => 1,
+        Value::Nothing { .. } => 0,
+        _ => 8,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{is_table, mutate_value_cell};
+    use super::{
+        count_changes, estimate_size, expand_range, filter_rows, is_table, mutate_value_cell,
+        schema, sort, swap,
+    };
     use crate::nu::{
         cell_path::{to_path_member_vec, PM},
         value::{transpose, Table},
     };
-    use nu_protocol::{ast::CellPath, record, Config, Type, Value};
+    use nu_protocol::{
+        ast::{CellPath, RangeInclusion},
+        record, Config, Range, Span, Type, Value,
+    };
 
     fn default_value_repr(value: &Value) -> String {
         value.to_expanded_string(" ", &Config::default())
     }
 
+    /// the range `1..end`, which is unbounded when `end` is `null`
+    fn range(end: Value) -> Value {
+        Value::test_range(
+            Range::new(
+                Value::test_int(1),
+                Value::test_nothing(),
+                end,
+                RangeInclusion::Inclusive,
+                Span::test_data(),
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn expand_ranges() {
+        let (vals, truncated) = expand_range(&range(Value::test_int(3)).as_range().unwrap(), 10);
+        assert_eq!(
+            vals,
+            vec![Value::test_int(1), Value::test_int(2), Value::test_int(3)]
+        );
+        assert!(!truncated);
+
+        let (vals, truncated) = expand_range(&range(Value::test_int(3)).as_range().unwrap(), 2);
+        assert_eq!(vals, vec![Value::test_int(1), Value::test_int(2)]);
+        assert!(truncated);
+
+        let (vals, truncated) = expand_range(&range(Value::test_nothing()).as_range().unwrap(), 5);
+        assert_eq!(vals.len(), 5);
+        assert!(truncated);
+
+        assert_eq!(default_value_repr(&range(Value::test_int(100))), "1..100");
+    }
+
     #[test]
     fn value_mutation() {
         let list = Value::test_list(vec![
@@ -464,6 +818,33 @@ mod tests {
                 Value::test_string("bar"),
                 None,
             ),
+            // mutate an item of a bounded range, which becomes a list
+            (
+                Value::test_record(record! { "r" => range(Value::test_int(3)) }),
+                vec![PM::S("r"), PM::I(1)],
+                Value::test_int(0),
+                Some(Value::test_record(record! {
+                    "r" => Value::test_list(vec![
+                        Value::test_int(1),
+                        Value::test_int(0),
+                        Value::test_int(3),
+                    ]),
+                })),
+            ),
+            // the items of an unbounded range can't be changed
+            (
+                range(Value::test_nothing()),
+                vec![PM::I(1)],
+                Value::test_int(0),
+                None,
+            ),
+            // nor the ones of a range too big to be turned into a list
+            (
+                range(Value::test_int(1_000_000_000)),
+                vec![PM::I(0)],
+                Value::test_int(0),
+                None,
+            ),
         ];
 
         for (value, members, cell, expected) in cases {
@@ -540,7 +921,7 @@ mod tests {
             table_with_number_colum,
         ] {
             assert_eq!(
-                is_table(&table),
+                is_table(&table, false),
                 Table::IsValid,
                 "{} should be a table",
                 default_value_repr(&table)
@@ -607,15 +988,96 @@ mod tests {
             not_a_table_row_invalid_key,
         ] {
             assert_eq!(
-                is_table(&not_a_table),
+                is_table(&not_a_table, false),
                 expected,
                 "{} should not be a table",
                 default_value_repr(&not_a_table)
             );
         }
 
-        assert_eq!(is_table(&Value::test_int(0)), Table::NotAList);
-        assert_eq!(is_table(&Value::test_list(vec![])), Table::Empty);
+        assert_eq!(is_table(&Value::test_int(0), false), Table::NotAList);
+        assert_eq!(is_table(&Value::test_list(vec![]), false), Table::Empty);
+    }
+
+    #[test]
+    fn is_a_loose_table() {
+        let ragged = Value::test_list(vec![
+            Value::test_record(record! {
+                "a" => Value::test_string("a"),
+            }),
+            Value::test_record(record! {
+                "a" => Value::test_string("a"),
+                "b" => Value::test_int(1),
+            }),
+            Value::test_record(record! {
+                "c" => Value::test_int(2),
+            }),
+        ]);
+        assert_eq!(is_table(&ragged, false), Table::RowIncompatibleLen(1, 2, 1));
+        assert_eq!(is_table(&ragged, true), Table::IsValid);
+
+        let incompatible_types = Value::test_list(vec![
+            Value::test_record(record! {
+                "a" => Value::test_string("a"),
+            }),
+            Value::test_record(record! {
+                "b" => Value::test_int(1),
+            }),
+            Value::test_record(record! {
+                "a" => Value::test_int(2),
+            }),
+        ]);
+        assert_eq!(
+            is_table(&incompatible_types, true),
+            Table::RowIncompatibleType(2, "a".into(), Type::Int, Type::String)
+        );
+
+        assert_eq!(
+            is_table(
+                &Value::test_list(vec![Value::test_record(record! {}), Value::test_int(0)]),
+                true
+            ),
+            Table::RowNotARecord(1, Type::Int)
+        );
+    }
+
+    #[test]
+    fn is_a_table_with_optional_columns() {
+        // NOTE: like `ls`, where only the symlinks have a `target`
+        let ls = Value::test_list(vec![
+            Value::test_record(record! {
+                "name" => Value::test_string("Cargo.toml"),
+                "size" => Value::test_filesize(512),
+            }),
+            Value::test_record(record! {
+                "name" => Value::test_string("link"),
+                "size" => Value::test_filesize(4),
+                "target" => Value::test_string("Cargo.toml"),
+            }),
+        ]);
+        assert_eq!(is_table(&ls, false), Table::RowIncompatibleLen(1, 3, 2));
+        assert_eq!(is_table(&ls, true), Table::IsValid);
+
+        let numbers = Value::test_list(vec![
+            Value::test_record(record! { "a" => Value::test_int(1) }),
+            Value::test_record(record! {
+                "a" => Value::test_float(2.0),
+                "b" => Value::test_nothing(),
+            }),
+        ]);
+        assert_eq!(is_table(&numbers, true), Table::IsValid);
+
+        let incompatible = Value::test_list(vec![
+            Value::test_record(record! { "a" => Value::test_int(1) }),
+            Value::test_record(record! {
+                "a" => Value::test_string("x"),
+                "b" => Value::test_int(2),
+            }),
+        ]);
+        assert_eq!(
+            is_table(&incompatible, true),
+            Table::RowIncompatibleType(1, "a".into(), Type::String, Type::Int)
+        );
     }
 
     #[test]
@@ -709,4 +1171,325 @@ mod tests {
             Value::test_list(vec![Value::test_int(1), Value::test_int(2)])
         );
     }
+
+    #[test]
+    fn table_schema() {
+        let table = Value::test_list(vec![
+            Value::test_record(record! {
+                "a" => Value::test_string("foo"),
+                "b" => Value::test_int(1),
+                "c" => Value::test_nothing(),
+            }),
+            Value::test_record(record! {
+                "a" => Value::test_string("bar"),
+                "b" => Value::test_float(2.34),
+                "c" => Value::test_bool(true),
+            }),
+        ]);
+        let expected = Value::test_record(record! {
+            "a" => Value::test_string("string"),
+            "b" => Value::test_string("number"),
+            "c" => Value::test_string("bool"),
+        });
+
+        let result = schema(&table);
+        assert_eq!(
+            result,
+            expected,
+            "schema of {} should be {}, found {}",
+            default_value_repr(&table),
+            default_value_repr(&expected),
+            default_value_repr(&result)
+        );
+    }
+
+    #[test]
+    fn record_schema() {
+        let record = Value::test_record(record! {
+            "s" => Value::test_string("foo"),
+            "i" => Value::test_int(1),
+            "l" => Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+            }),
+        });
+        let expected = Value::test_record(record! {
+            "s" => Value::test_string("string"),
+            "i" => Value::test_string("int"),
+            "l" => Value::test_string("list<int>"),
+            "r" => Value::test_string("record<a: int>"),
+        });
+
+        let result = schema(&record);
+        assert_eq!(
+            result,
+            expected,
+            "schema of {} should be {}, found {}",
+            default_value_repr(&record),
+            default_value_repr(&expected),
+            default_value_repr(&result)
+        );
+
+        assert_eq!(
+            schema(&Value::test_list(vec![
+                Value::test_int(1),
+                Value::test_int(2)
+            ])),
+            Value::test_string("int")
+        );
+        assert_eq!(schema(&Value::test_int(1)), Value::test_string("int"));
+    }
+
+    #[test]
+    fn changes_count() {
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            }),
+            "s" => Value::test_string("foo"),
+        });
+
+        let cases = vec![
+            (vec![], value.clone(), 0),
+            (vec![PM::S("s")], Value::test_string("bar"), 1),
+            (vec![PM::S("l"), PM::I(0)], Value::test_int(0), 1),
+            (
+                vec![PM::S("l")],
+                Value::test_list(vec![Value::test_int(0)]),
+                2,
+            ),
+            (
+                vec![PM::S("r")],
+                Value::test_record(record! {
+                    "a" => Value::test_int(0),
+                    "c" => Value::test_int(2),
+                }),
+                3,
+            ),
+            (vec![PM::S("r")], Value::test_int(0), 1),
+        ];
+
+        for (members, cell, expected) in cases {
+            let cell_path = CellPath {
+                members: to_path_member_vec(&members),
+            };
+            let new = mutate_value_cell(&value, &cell_path, &cell).unwrap();
+
+            let result = count_changes(&value, &new);
+            assert_eq!(
+                result,
+                expected,
+                "mutating {} at {:?} with {} should give {} changes, found {}",
+                default_value_repr(&value),
+                PM::as_cell_path(&members),
+                default_value_repr(&cell),
+                expected,
+                result,
+            );
+        }
+    }
+
+    #[test]
+    fn swap_rows() {
+        let list = Value::test_list(vec![
+            Value::test_int(1),
+            Value::test_int(2),
+            Value::test_int(3),
+        ]);
+        assert_eq!(
+            swap(&list, 0, 2),
+            Value::test_list(vec![
+                Value::test_int(3),
+                Value::test_int(2),
+                Value::test_int(1),
+            ])
+        );
+        assert_eq!(swap(&list, 1, 3), list);
+
+        let rec = Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_int(2),
+        });
+        let swapped = swap(&rec, 0, 1);
+        assert_eq!(
+            swapped.as_record().unwrap().columns().collect::<Vec<_>>(),
+            vec!["b", "a"]
+        );
+        assert_eq!(
+            swapped.as_record().unwrap().get("a"),
+            Some(&Value::test_int(1))
+        );
+
+        assert_eq!(swap(&Value::test_int(1), 0, 1), Value::test_int(1));
+    }
+
+    #[test]
+    fn sort_values() {
+        let record = Value::test_record(record! {
+            "b" => Value::test_int(1),
+            "c" => Value::test_int(2),
+            "a" => Value::test_int(3),
+        });
+        // NOTE: the columns are compared explicitly because two records with the same fields in
+        // a different order are equal
+        let columns = |v: Value| {
+            v.as_record()
+                .unwrap()
+                .columns()
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(columns(sort(&record, None, false)), vec!["a", "b", "c"]);
+        assert_eq!(columns(sort(&record, None, true)), vec!["c", "b", "a"]);
+        assert_eq!(sort(&record, None, false), record);
+
+        // numbers are compared together, other mixed types as strings
+        let list = Value::test_list(vec![
+            Value::test_int(3),
+            Value::test_string("b"),
+            Value::test_float(1.5),
+            Value::test_string("a"),
+            Value::test_int(2),
+        ]);
+        assert_eq!(
+            sort(&list, None, false),
+            Value::test_list(vec![
+                Value::test_float(1.5),
+                Value::test_int(2),
+                Value::test_int(3),
+                Value::test_string("a"),
+                Value::test_string("b"),
+            ])
+        );
+
+        // equal items keep their order, even when descending
+        let table = Value::test_list(vec![
+            Value::test_record(
+                record! { "a" => Value::test_int(1), "b" => Value::test_string("x") },
+            ),
+            Value::test_record(
+                record! { "a" => Value::test_int(2), "b" => Value::test_string("y") },
+            ),
+            Value::test_record(
+                record! { "a" => Value::test_int(1), "b" => Value::test_string("z") },
+            ),
+        ]);
+        assert_eq!(
+            sort(&table, Some("a"), false),
+            Value::test_list(vec![
+                Value::test_record(
+                    record! { "a" => Value::test_int(1), "b" => Value::test_string("x") }
+                ),
+                Value::test_record(
+                    record! { "a" => Value::test_int(1), "b" => Value::test_string("z") }
+                ),
+                Value::test_record(
+                    record! { "a" => Value::test_int(2), "b" => Value::test_string("y") }
+                ),
+            ])
+        );
+        assert_eq!(
+            sort(&table, Some("a"), true),
+            Value::test_list(vec![
+                Value::test_record(
+                    record! { "a" => Value::test_int(2), "b" => Value::test_string("y") }
+                ),
+                Value::test_record(
+                    record! { "a" => Value::test_int(1), "b" => Value::test_string("x") }
+                ),
+                Value::test_record(
+                    record! { "a" => Value::test_int(1), "b" => Value::test_string("z") }
+                ),
+            ])
+        );
+
+        assert_eq!(sort(&Value::test_int(1), None, false), Value::test_int(1));
+    }
+
+    #[test]
+    fn filter_values() {
+        let table = Value::test_list(vec![
+            Value::test_record(
+                record! { "name" => Value::test_string("foo"), "size" => Value::test_int(123) },
+            ),
+            Value::test_record(
+                record! { "name" => Value::test_string("bar"), "size" => Value::test_int(45) },
+            ),
+            Value::test_record(
+                record! { "name" => Value::test_string("baz"), "size" => Value::test_int(12) },
+            ),
+        ]);
+
+        // any column can match
+        assert_eq!(
+            filter_rows(&table, "ba"),
+            Value::test_list(vec![
+                Value::test_record(
+                    record! { "name" => Value::test_string("bar"), "size" => Value::test_int(45) }
+                ),
+                Value::test_record(
+                    record! { "name" => Value::test_string("baz"), "size" => Value::test_int(12) }
+                ),
+            ])
+        );
+        assert_eq!(
+            filter_rows(&table, "12"),
+            Value::test_list(vec![
+                Value::test_record(
+                    record! { "name" => Value::test_string("foo"), "size" => Value::test_int(123) }
+                ),
+                Value::test_record(
+                    record! { "name" => Value::test_string("baz"), "size" => Value::test_int(12) }
+                ),
+            ])
+        );
+        // the column names are not searched
+        assert_eq!(filter_rows(&table, "size"), Value::test_list(vec![]));
+        assert_eq!(filter_rows(&table, ""), table);
+
+        // the items of a simple list and nested data can match
+        let list = Value::test_list(vec![
+            Value::test_string("hello"),
+            Value::test_list(vec![Value::test_string("world")]),
+            Value::test_nothing(),
+        ]);
+        assert_eq!(
+            filter_rows(&list, "o"),
+            Value::test_list(vec![
+                Value::test_string("hello"),
+                Value::test_list(vec![Value::test_string("world")]),
+            ])
+        );
+
+        // anything else is not filtered
+        assert_eq!(
+            filter_rows(&Value::test_string("foo"), "x"),
+            Value::test_string("foo")
+        );
+    }
+
+    #[test]
+    fn size_estimation() {
+        assert_eq!(estimate_size(&Value::test_int(123456)), 8);
+        assert_eq!(estimate_size(&Value::test_float(1.5)), 8);
+        assert_eq!(estimate_size(&Value::test_string("hello")), 5);
+        assert_eq!(estimate_size(&Value::test_bool(true)), 1);
+        assert_eq!(estimate_size(&Value::test_nothing()), 0);
+
+        let list = Value::test_list(vec![Value::test_int(1), Value::test_string("abc")]);
+        assert_eq!(estimate_size(&list), (8 + 8) + (8 + 3));
+        assert_eq!(estimate_size(&Value::test_list(vec![])), 0);
+
+        let record = Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "bb" => list,
+            "c" => Value::test_record(record! { "d" => Value::test_string("xy") }),
+        });
+        assert_eq!(
+            estimate_size(&record),
+            (8 + 1 + 8) + (8 + 2 + 27) + (8 + 1 + (8 + 1 + 2))
+        );
+    }
 }
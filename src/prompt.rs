@@ -0,0 +1,128 @@
+//! a small one-line input overlay, e.g. to ask for the path of a file
+use crossterm::event::KeyCode;
+use ratatui::{
+    prelude::Rect,
+    style::Style,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::{config::Config, ui::styled};
+
+#[derive(Default, Clone)]
+pub struct Prompt {
+    title: String,
+    buffer: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PromptTransition {
+    Continue,
+    Quit,
+    Submit(String),
+}
+
+impl Prompt {
+    pub(super) fn new(title: &str) -> Self {
+        Self {
+            title: title.into(),
+            buffer: String::new(),
+        }
+    }
+
+    /// update the input with a key
+    ///
+    /// > **Note**
+    /// > an empty input cannot be submitted
+    pub(super) fn handle_key(&mut self, key: &KeyCode) -> PromptTransition {
+        match key {
+            KeyCode::Char(c) => self.buffer.push(*c),
+            KeyCode::Backspace => {
+                self.buffer.pop();
+            }
+            KeyCode::Enter if !self.buffer.is_empty() => {
+                return PromptTransition::Submit(self.buffer.clone())
+            }
+            KeyCode::Esc => return PromptTransition::Quit,
+            _ => {}
+        }
+
+        PromptTransition::Continue
+    }
+
+    pub(super) fn render(&self, frame: &mut Frame, config: &Config) {
+        let block = Paragraph::new(self.buffer.as_str())
+            .style(styled(
+                Style::default(),
+                config.colors.editor.buffer.foreground,
+                config.colors.editor.buffer.background,
+                config.colors.enabled,
+            ))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(self.title.as_str())
+                    .style(styled(
+                        Style::default(),
+                        config.colors.editor.frame.foreground,
+                        config.colors.editor.frame.background,
+                        config.colors.enabled,
+                    )),
+            );
+
+        let width = frame.size().width.saturating_sub(4);
+        let area = Rect {
+            x: (frame.size().width - width) / 2,
+            y: frame.size().height.saturating_sub(3 + 2),
+            width,
+            height: 3,
+        };
+
+        frame.render_widget(Clear, area); //this clears out the background
+        frame.render_widget(block, area);
+
+        // NOTE: the input is not scrolled, the cursor stays on the frame when it is too long
+        let x = (self.buffer.chars().count() as u16).min(width.saturating_sub(3));
+        frame.set_cursor(area.x + 1 + x, area.y + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use super::{Prompt, PromptTransition};
+
+    #[test]
+    fn type_a_path() {
+        let mut prompt = Prompt::new("test");
+
+        let strokes = vec![
+            (KeyCode::Enter, "", PromptTransition::Continue),
+            (KeyCode::Backspace, "", PromptTransition::Continue),
+            (KeyCode::Char('a'), "a", PromptTransition::Continue),
+            (KeyCode::Char('.'), "a.", PromptTransition::Continue),
+            (KeyCode::Char('x'), "a.x", PromptTransition::Continue),
+            (KeyCode::Backspace, "a.", PromptTransition::Continue),
+            (KeyCode::Left, "a.", PromptTransition::Continue),
+            (KeyCode::Char('n'), "a.n", PromptTransition::Continue),
+            (
+                KeyCode::Enter,
+                "a.n",
+                PromptTransition::Submit("a.n".into()),
+            ),
+            (KeyCode::Esc, "a.n", PromptTransition::Quit),
+        ];
+
+        for (key, buffer, expected) in strokes {
+            let result = prompt.handle_key(&key);
+
+            assert_eq!(
+                result, expected,
+                "expected {:?} after {:?} on '{}'",
+                expected, key, prompt.buffer
+            );
+            assert_eq!(prompt.buffer, buffer);
+        }
+    }
+}
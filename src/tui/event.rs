@@ -12,7 +12,6 @@ pub enum Event {
     /// Key press.
     Key(KeyEvent),
     /// Mouse click/scroll.
-    #[allow(dead_code)]
     Mouse(MouseEvent),
     /// Terminal resize.
     #[allow(dead_code)]
@@ -9,7 +9,7 @@ use std::panic;
 
 pub(crate) mod event;
 
-use crate::{app::App, ui};
+use crate::{app::App, error::ErrorView, ui};
 use event::EventHandler;
 
 /// Representation of a terminal user interface.
@@ -54,12 +54,18 @@ impl<B: Backend> Tui<B> {
     ///
     /// [`Draw`]: tui::Terminal::draw
     /// [`rendering`]: crate::ui:render
-    pub fn draw(&mut self, app: &mut App, error: Option<&str>) -> Result<()> {
+    pub fn draw(&mut self, app: &mut App, error: Option<&ErrorView>) -> Result<()> {
         self.terminal
             .draw(|frame| ui::render_ui(frame, app, error))?;
         Ok(())
     }
 
+    /// Draws a placeholder until the data is ready, see [`ui::render_loading`].
+    pub fn draw_loading(&mut self) -> Result<()> {
+        self.terminal.draw(ui::render_loading)?;
+        Ok(())
+    }
+
     /// Resets the terminal interface.
     ///
     /// This function is also used for the panic hook to revert
@@ -1,8 +1,16 @@
 //! the module responsible for rendering the TUI
+use std::fmt::Write;
+
 use crate::{
-    config::Layout,
+    config::{ColorConfig, Config, DataAlignment, Layout},
+    error::ErrorView,
     handler::repr_key,
-    nu::{strings::SpecialString, value::is_table},
+    help,
+    nu::{
+        diff::{diff_pair, diff_records, DiffKind},
+        strings::SpecialString,
+        value::{is_table, table_column_types, table_columns},
+    },
 };
 
 use super::{App, Mode};
@@ -12,43 +20,111 @@ use nu_protocol::{Record, Type, Value};
 use ratatui::{
     prelude::{Alignment, Constraint, Rect},
     style::{Color, Modifier, Style},
-    text::{Line, Span},
+    text::{Line, Span, Text},
     widgets::{
-        Block, Borders, Cell, List, ListItem, ListState, Paragraph, Row, Table, TableState, Wrap,
+        Block, Borders, Cell, List, ListItem, ListState, Paragraph, Row, Scrollbar,
+        ScrollbarOrientation, ScrollbarState, Table, TableState, Wrap,
     },
     Frame,
 };
 
+/// the smallest terminal, in rows and columns, in which the ui can be rendered
+const MIN_TERMINAL_SIZE: (u16, u16) = (5, 20);
+
 /// render the whole ui
-pub(super) fn render_ui(frame: &mut Frame, app: &mut App, error: Option<&str>) {
+///
+/// > **Note**  
+/// > a terminal smaller than [`MIN_TERMINAL_SIZE`] only shows a message, e.g. after a resize
+pub(super) fn render_ui(frame: &mut Frame, app: &mut App, error: Option<&ErrorView>) {
+    let (min_height, min_width) = MIN_TERMINAL_SIZE;
+    if frame.size().height < min_height || frame.size().width < min_width {
+        frame.render_widget(
+            Paragraph::new("terminal too small").wrap(Wrap { trim: true }),
+            frame.size(),
+        );
+        return;
+    }
+
     render_data(frame, app);
     if app.config.show_cell_path {
         render_cell_path(frame, app);
     }
+    if app.config.show_shape_summary {
+        render_shape_summary(frame, app);
+    }
 
     match error {
-        Some(err) => render_error(frame, err),
+        Some(err) => render_error(frame, err, &app.config),
         None => {
+            if app.config.show_size {
+                app.cache_size();
+            }
             render_status_bar(frame, app);
 
             if app.mode == Mode::Insert {
                 app.editor.render(frame, &app.config);
+            } else if matches!(app.mode, Mode::Writing | Mode::Filtering) {
+                app.prompt.render(frame, &app.config);
+            } else if app.mode == Mode::Help {
+                help::render(frame, &app.config, &mut app.help_top);
+            } else if app.tour {
+                help::render_tour(frame, &app.config);
             }
         }
     }
 }
 
-pub(super) fn render_error(frame: &mut Frame, error: &str) {
-    let bottom_two_lines = Rect::new(0, frame.size().height - 2, frame.size().width, 2);
+/// render a placeholder while the data is being loaded, e.g. a huge stream being collected, before
+/// the first frame of [`render_ui`]
+pub(super) fn render_loading(frame: &mut Frame) {
+    let area = frame.size();
+    frame.render_widget(
+        Paragraph::new("loading…").alignment(Alignment::Center),
+        Rect::new(
+            area.x,
+            area.y + area.height / 2,
+            area.width,
+            area.height.min(1),
+        ),
+    );
+}
+
+/// style *base* with the *fg* and *bg* colors, unless the colors are not *enabled*
+///
+/// > :bulb: **Note**  
+/// > the modifiers of *base* are always kept, e.g. to still see the row under the cursor
+pub(crate) fn styled(base: Style, fg: Color, bg: Color, enabled: bool) -> Style {
+    if enabled {
+        base.fg(fg).bg(bg)
+    } else {
+        base
+    }
+}
+
+/// render the *error* at the bottom of the frame, or in an overlay when it's too long, see
+/// [`ErrorView`]
+pub(super) fn render_error(frame: &mut Frame, error: &ErrorView, config: &Config) {
+    if error.is_long(frame.size().width) {
+        error.render(frame, config);
+        return;
+    }
+
+    let enabled = config.colors.enabled;
+    let bottom_two_lines = Rect::new(
+        0,
+        frame.size().height.saturating_sub(2),
+        frame.size().width,
+        2,
+    );
 
     let lines = vec![
         Line::from(Span::styled(
-            format!("Err: {error}"),
-            Style::default().fg(Color::Red),
+            format!("Err: {}", error.message()),
+            styled(Style::default(), Color::Red, Color::Reset, enabled),
         )),
         Line::from(Span::styled(
             "Press any key to continue exploring the data.",
-            Style::default().fg(Color::Blue),
+            styled(Style::default(), Color::Blue, Color::Reset, enabled),
         )),
     ];
 
@@ -60,18 +136,23 @@ pub(super) fn render_error(frame: &mut Frame, error: &str) {
 
 /// a common representation for an explore row
 #[derive(Clone, Debug, PartialEq)]
-struct DataRowRepr {
-    name: Option<String>,
-    shape: String,
-    data: String,
+pub(crate) struct DataRowRepr {
+    pub(crate) name: Option<String>,
+    pub(crate) shape: String,
+    /// the type of the value, without the inner types of lists and records nor special strings,
+    /// e.g. `list` or `string`, see [`crate::config::ColorConfig::types`]
+    pub(crate) type_name: String,
+    pub(crate) data: String,
 }
 
 impl DataRowRepr {
     #[cfg(test)]
     fn unnamed(data: impl Into<String>, shape: impl Into<String>) -> Self {
+        let shape = shape.into();
         Self {
             name: None,
-            shape: shape.into(),
+            type_name: Self::type_of_shape(&shape),
+            shape,
             data: data.into(),
         }
     }
@@ -80,8 +161,16 @@ impl DataRowRepr {
     fn named(name: impl Into<String>, data: impl Into<String>, shape: impl Into<String>) -> Self {
         Self {
             name: Some(name.into()),
-            shape: shape.into(),
-            data: data.into(),
+            ..Self::unnamed(data, shape)
+        }
+    }
+
+    /// the type of the values of the *shape*, e.g. `string` for `url`
+    #[cfg(test)]
+    fn type_of_shape(shape: &str) -> String {
+        match shape {
+            "url" | "path" | "json" | "number" => "string".into(),
+            s => s.into(),
         }
     }
 }
@@ -89,16 +178,28 @@ impl DataRowRepr {
 /// compute the preview representation of a list
 ///
 /// > see the tests for detailed examples
-fn repr_list(vals: &[Value]) -> DataRowRepr {
+fn repr_list(vals: &[Value], options: ReprOptions) -> DataRowRepr {
     let data = match vals.len() {
         0 => "[]".into(),
-        1 => "[1 item]".into(),
-        x => format!("[{} items]", x),
+        _ if options.inline_nested => truncate(
+            repr_inline_list(vals, options, 1, options.max_length),
+            options,
+        ),
+        n if options.first_item => {
+            let first = repr_inline_value(&vals[0], options, 1, options.max_length);
+            let rest = if n > 1 { ", …" } else { "" };
+            truncate(
+                format!("[{}: {}{}]", pluralize(n, "item"), first, rest),
+                options,
+            )
+        }
+        n => format!("[{}]", pluralize(n, "item")),
     };
 
     DataRowRepr {
         name: None,
         shape: "list".into(),
+        type_name: "list".into(),
         data,
     }
 }
@@ -106,24 +207,236 @@ fn repr_list(vals: &[Value]) -> DataRowRepr {
 /// compute the preview representation of a record
 ///
 /// > see the tests for detailed examples
-fn repr_record(cols: &[String]) -> DataRowRepr {
-    let data = match cols.len() {
+fn repr_record(rec: &Record, options: ReprOptions) -> DataRowRepr {
+    let data = match rec.len() {
         0 => "{}".into(),
-        1 => "{1 field}".into(),
-        x => format!("{{{} fields}}", x),
+        _ if options.inline_nested => truncate(
+            repr_inline_record(rec, options, 1, options.max_length),
+            options,
+        ),
+        n if options.first_item => {
+            // NOTE: the record is not empty here
+            let (col, val) = rec.iter().next().unwrap();
+            let first = repr_inline_value(val, options, 1, options.max_length);
+            let rest = if n > 1 { ", …" } else { "" };
+            truncate(
+                format!("{{{}: {}: {}{}}}", pluralize(n, "field"), col, first, rest),
+                options,
+            )
+        }
+        n => format!("{{{}}}", pluralize(n, "field")),
     };
 
     DataRowRepr {
         name: None,
         shape: "record".into(),
+        type_name: "record".into(),
         data,
     }
 }
 
+/// give the number *n* of things along with their name, e.g. `1 item`, `0 items` or `2 items`
+fn pluralize(n: usize, singular: &str) -> String {
+    if n == 1 {
+        format!("{} {}", n, singular)
+    } else {
+        format!("{} {}s", n, singular)
+    }
+}
+
+/// compute a one-line inline preview of the items of a list, e.g. `[1, 2, 3]`
+///
+/// the nested lists and records are previewed inline *depth* more times, e.g. `[[1, 2]]`, and
+/// then only summarized, e.g. `[[2 items]]`
+///
+/// > :bulb: **Note**  
+/// > no more items are added once the preview is longer than *limit* characters, if any, because
+/// > it will be truncated anyway, e.g. a list of 1M items is not previewed as a whole
+fn repr_inline_list(
+    vals: &[Value],
+    options: ReprOptions,
+    depth: usize,
+    limit: Option<usize>,
+) -> String {
+    let items = vals.iter().map(|val| (String::new(), val));
+
+    format!("[{}]", repr_inline_items(items, options, depth, limit))
+}
+
+/// compute a one-line inline preview of the fields of a record, e.g. `{a: 1, b: 2}`
+///
+/// > see [`repr_inline_list`] for the *depth* and the *limit*
+fn repr_inline_record(
+    rec: &Record,
+    options: ReprOptions,
+    depth: usize,
+    limit: Option<usize>,
+) -> String {
+    let fields = rec.iter().map(|(col, val)| (format!("{}: ", col), val));
+
+    format!("{{{}}}", repr_inline_items(fields, options, depth, limit))
+}
+
+/// join the inline previews of the *items*, each one after its prefix, e.g. the key of a field,
+/// until the preview, opening bracket included, is longer than the *limit*, if any
+fn repr_inline_items<'a>(
+    items: impl Iterator<Item = (String, &'a Value)>,
+    options: ReprOptions,
+    depth: usize,
+    limit: Option<usize>,
+) -> String {
+    let mut repr = String::new();
+    let mut len = 1;
+    for (i, (prefix, val)) in items.enumerate() {
+        if limit.is_some_and(|limit| len > limit) {
+            break;
+        }
+        if i > 0 {
+            repr.push_str(", ");
+        }
+        repr.push_str(&prefix);
+        len += if i > 0 { 2 } else { 0 } + prefix.chars().count();
+
+        let item = repr_inline_value(val, options, depth, limit.map(|l| l.saturating_sub(len)));
+        len += item.chars().count();
+        repr.push_str(&item);
+    }
+
+    repr
+}
+
+/// compute the inline preview of a value nested in a list or a record
+///
+/// > **Note**  
+/// > the value is never truncated, only the whole preview is, see [`repr_inline_list`] for the
+/// > *limit*
+fn repr_inline_value(
+    value: &Value,
+    options: ReprOptions,
+    depth: usize,
+    limit: Option<usize>,
+) -> String {
+    let options = ReprOptions {
+        max_length: None,
+        inline_nested: false,
+        first_item: false,
+        ..options
+    };
+
+    match value {
+        Value::List { vals, .. } if depth > 0 => repr_inline_list(vals, options, depth - 1, limit),
+        Value::Record { val: rec, .. } if depth > 0 => {
+            repr_inline_record(rec, options, depth - 1, limit)
+        }
+        value => repr_value(value, options).data,
+    }
+}
+
+/// the options to compute the representation of simple values
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ReprOptions<'a> {
+    /// the maximum number of characters of the data, if any
+    pub(crate) max_length: Option<usize>,
+    /// the `strftime` format of the dates, RFC3339 if not set
+    pub(crate) date_format: Option<&'a str>,
+    /// the representation of `null`
+    pub(crate) null_placeholder: &'a str,
+    /// represent the empty string as `""`
+    pub(crate) empty_string_quotes: bool,
+    /// preview nested lists and records inline, e.g. `{a: 1}` instead of `{1 field}`
+    pub(crate) inline_nested: bool,
+    /// preview the first item of nested lists and records, e.g. `[2 items: a, …]`
+    pub(crate) first_item: bool,
+    /// the symbol at the end of the truncated data
+    pub(crate) truncation_symbol: &'a str,
+    /// the glyphs of `true` and `false`, if they are not shown as is
+    pub(crate) bool_glyphs: Option<(&'a str, &'a str)>,
+}
+
+impl Default for ReprOptions<'_> {
+    fn default() -> Self {
+        Self {
+            max_length: None,
+            date_format: None,
+            null_placeholder: "∅",
+            empty_string_quotes: true,
+            inline_nested: false,
+            first_item: false,
+            truncation_symbol: "…",
+            bool_glyphs: None,
+        }
+    }
+}
+
+impl<'a> ReprOptions<'a> {
+    /// the options to compute the representation of the data in the TUI
+    pub(crate) fn from_config(config: &'a Config) -> Self {
+        Self {
+            max_length: Some(config.preview_max_length),
+            date_format: config.date_format.as_deref(),
+            null_placeholder: &config.null_placeholder,
+            empty_string_quotes: config.show_empty_string_quotes,
+            inline_nested: config.inline_nested_preview,
+            first_item: config.preview_first_item,
+            truncation_symbol: &config.truncation_symbol,
+            bool_glyphs: config
+                .bool_glyphs
+                .then_some((config.glyphs.yes.as_str(), config.glyphs.no.as_str())),
+        }
+    }
+
+    /// the options to export the data as text, e.g. when peeking a table as Markdown, i.e. with
+    /// the full data and without any placeholder
+    pub(crate) fn export() -> Self {
+        Self {
+            max_length: None,
+            date_format: None,
+            null_placeholder: "",
+            empty_string_quotes: false,
+            ..Default::default()
+        }
+    }
+}
+
 /// compute the preview representation of a simple value
 ///
 /// > see the tests for detailed examples
-fn repr_simple_value(value: &Value) -> DataRowRepr {
+fn repr_simple_value(value: &Value, options: ReprOptions) -> DataRowRepr {
+    let data = match value {
+        Value::Binary { val, .. } => {
+            return DataRowRepr {
+                name: None,
+                shape: value.get_type().to_string(),
+                type_name: value.get_type().to_string(),
+                data: repr_binary(val, options.max_length),
+            };
+        }
+        // NOTE: an invalid format falls back to RFC3339
+        Value::Date { val, .. } => {
+            let mut repr = String::new();
+            match options.date_format {
+                Some(format) if write!(repr, "{}", val.format(format)).is_ok() => repr,
+                _ => val.to_rfc3339(),
+            }
+        }
+        Value::Nothing { .. } => options.null_placeholder.to_string(),
+        Value::Bool { val, .. } => match options.bool_glyphs {
+            Some((yes, no)) => if *val { yes } else { no }.to_string(),
+            None => val.to_string(),
+        },
+        // NOTE: the internal representation of closures and errors is not useful to the user
+        Value::Closure { .. } => "<closure>".to_string(),
+        Value::Error { error, .. } => error.to_string(),
+        // NOTE: cell paths are shown like the one above the status bar and globs as they are typed
+        Value::CellPath { val, .. } => format_cell_path(&val.members),
+        Value::Glob { val, .. } => val.clone(),
+        Value::String { val, .. } if val.is_empty() && options.empty_string_quotes => {
+            r#""""#.to_string()
+        }
+        // FIXME: use a real config
+        v => v.to_expanded_string(" ", &nu_protocol::Config::default()),
+    };
+
     let shape = match value {
         Value::String { val, .. } => match SpecialString::parse(val) {
             Some(x) => x.to_string(),
@@ -134,36 +447,366 @@ fn repr_simple_value(value: &Value) -> DataRowRepr {
     DataRowRepr {
         name: None,
         shape,
-        // FIXME: use a real config
-        data: value.to_expanded_string(" ", &nu_protocol::Config::default()),
+        type_name: value.get_type().to_string(),
+        data: truncate(data, options),
+    }
+}
+
+/// compute a short hex preview of some binary data, e.g. `0x DE AD BE EF … (1234 bytes)`
+///
+/// > :bulb: **Note**  
+/// > the hex bytes will take at most *max_length* characters, if any
+fn repr_binary(bytes: &[u8], max_length: Option<usize>) -> String {
+    // NOTE: each byte takes 2 characters, plus one for the separating space
+    let nb_bytes = match max_length {
+        Some(max_length) => bytes.len().min((max_length + 1) / 3),
+        None => bytes.len(),
+    };
+
+    let mut hex = bytes[..nb_bytes]
+        .iter()
+        .map(|b| format!(" {:02X}", b))
+        .collect::<Vec<_>>()
+        .concat();
+    if nb_bytes < bytes.len() {
+        hex.push_str(" …");
+    }
+
+    format!("0x{} ({})", hex, pluralize(bytes.len(), "byte"))
+}
+
+/// compute the first row to show in a frame of `height` rows, starting from the current `top`,
+/// such that the `cursor` stays visible with `margin` rows above and below it when possible
+///
+/// > **Note**  
+/// > - this is computed on every draw, so the offset follows a resize of the terminal
+/// > - a frame too small to show anything is treated as a single row, e.g. in a tiny terminal
+fn scroll_top(top: i32, cursor: i32, height: i32, margin: usize, nb_rows: usize) -> i32 {
+    let height = height.max(1);
+    // NOTE: the margins above and below the cursor cannot overlap, otherwise the view would jump
+    // back and forth between them on every redraw, e.g. when coming back from the bottom
+    let margin = (margin as i32).min((height - 1) / 2);
+
+    if cursor >= top + height - margin {
+        anchored_top(
+            ScrollAnchor::Bottom,
+            cursor,
+            height,
+            margin as usize,
+            nb_rows,
+        )
+    } else if cursor <= top + margin {
+        anchored_top(ScrollAnchor::Top, cursor, height, margin as usize, nb_rows)
+    } else {
+        top
+    }
+}
+
+/// like [`scroll_top`] but for rows that can take more than one line, e.g. the expanded ones, with
+/// `top` and `cursor` being rows and *starts* the first line of each row, followed by the total
+/// number of lines, see [`line_starts`]
+///
+/// > **Note**  
+/// > the first row is the first one starting at or after the top line, so that the row under the
+/// > cursor is always shown in full, if it fits
+fn scroll_top_in_lines(
+    top: i32,
+    cursor: usize,
+    height: i32,
+    margin: usize,
+    starts: &[usize],
+) -> i32 {
+    let nb_rows = starts.len().saturating_sub(1);
+    if cursor >= nb_rows {
+        return 0;
+    }
+
+    // NOTE: the lines of the row under the cursor, below its first one, must fit in the frame
+    let extra = (starts[cursor + 1] - starts[cursor] - 1) as i32;
+    let top_line = starts[(top.max(0) as usize).min(nb_rows)] as i32;
+    let line = scroll_top(
+        top_line,
+        starts[cursor] as i32,
+        height - extra,
+        margin,
+        starts[nb_rows] - extra as usize,
+    );
+
+    (starts.partition_point(|&s| (s as i32) < line).min(cursor)) as i32
+}
+
+/// compute the first line of each row, followed by the total number of lines, when each row
+/// takes one line plus the lines of its *expansions*
+///
+/// > **Note**  
+/// > returns nothing when no row is expanded, i.e. when each row takes exactly one line
+fn line_starts(expansions: &[Vec<String>]) -> Vec<usize> {
+    if expansions.iter().all(|rows| rows.is_empty()) {
+        return vec![];
+    }
+
+    let mut starts = vec![0];
+    for rows in expansions {
+        starts.push(starts.last().unwrap() + 1 + rows.len());
+    }
+
+    starts
+}
+
+/// where to put the row under the cursor in the frame
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ScrollAnchor {
+    /// as high as possible, i.e. right below the top margin
+    Top,
+    /// in the middle of the frame
+    Center,
+    /// as low as possible, i.e. right above the bottom margin
+    Bottom,
+}
+
+/// compute the first row to show in a frame of `height` rows such that the `cursor` is at the
+/// `anchor`, with `margin` rows above and below it when possible
+///
+/// > **Note**  
+/// > the frame never goes past the last row, e.g. the cursor can't be at the top near the end
+pub(crate) fn anchored_top(
+    anchor: ScrollAnchor,
+    cursor: i32,
+    height: i32,
+    margin: usize,
+    nb_rows: usize,
+) -> i32 {
+    let height = height.max(1);
+    let margin = (margin as i32).min((height - 1) / 2);
+
+    let top = match anchor {
+        ScrollAnchor::Top => cursor - margin,
+        ScrollAnchor::Center => cursor - (height - 1) / 2,
+        ScrollAnchor::Bottom => cursor - height + margin + 1,
+    };
+
+    top.min(nb_rows as i32 - height).max(0)
+}
+
+/// compute the start and the size of the thumb of a scrollbar, along a track of `track` cells
+///
+/// `nb_rows` is the total number of rows, `visible` the number of rows that fit on screen and
+/// `top` the index of the first visible row.
+///
+/// > **Note**  
+/// > - returns [`None`] when all the rows fit on screen, i.e. there is nothing to scroll
+/// > - the scrollbar itself is drawn by [`Scrollbar`], this only decides whether it's needed
+fn scrollbar_thumb(
+    nb_rows: usize,
+    visible: usize,
+    top: usize,
+    track: usize,
+) -> Option<(usize, usize)> {
+    if nb_rows <= visible || track == 0 {
+        return None;
+    }
+
+    let size = (track * visible / nb_rows).clamp(1, track);
+    let start = (track - size) * top.min(nb_rows - visible) / (nb_rows - visible);
+
+    Some((start, size))
+}
+
+/// compute the lines of the hex dump of some binary data
+///
+/// each line is made of the offset of the first byte, 16 bytes in hex and their ASCII
+/// representation, where non-printable characters are replaced with a `.`.
+fn hex_dump(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(16)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let hex = chunk
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let ascii = chunk
+                .iter()
+                .map(|&b| {
+                    if b.is_ascii_graphic() || b == b' ' {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>();
+
+            format!("{:08x}  {:<47}  |{}|", i * 16, hex, ascii)
+        })
+        .collect()
+}
+
+/// cut a string to at most *max* characters, telling whether anything has been cut
+///
+/// > :bulb: **Note**  
+/// > the string is cut on `char` boundaries and the truncation symbol is not added, to be styled
+/// > on its own
+fn truncate_styled(s: &str, max: usize) -> (String, bool) {
+    match s.char_indices().nth(max) {
+        Some((i, _)) => (s[..i].to_string(), true),
+        None => (s.to_string(), false),
+    }
+}
+
+/// truncate a string to at most [`ReprOptions::max_length`] characters, adding
+/// [`ReprOptions::truncation_symbol`] when truncated
+///
+/// > :bulb: **Note**  
+/// > [`None`] does not truncate anything
+fn truncate(s: String, options: ReprOptions) -> String {
+    match options.max_length.map(|max| truncate_styled(&s, max)) {
+        Some((kept, true)) => format!("{}{}", kept, options.truncation_symbol),
+        _ => s,
+    }
+}
+
+/// split some *text* into its spans, the occurrences of the *query* having the *hl* style and the
+/// rest of the text the *base* one
+///
+/// > :bulb: **Note**  
+/// > an empty query does not highlight anything
+pub(crate) fn highlight_spans<'a>(
+    text: &'a str,
+    query: &str,
+    base: Style,
+    hl: Style,
+) -> Vec<Span<'a>> {
+    if query.is_empty() {
+        return vec![Span::styled(text, base)];
+    }
+
+    let mut spans = vec![];
+    let mut end = 0;
+    for (start, m) in text.match_indices(query) {
+        if start > end {
+            spans.push(Span::styled(&text[end..start], base));
+        }
+        spans.push(Span::styled(m, hl));
+        end = start + m.len();
+    }
+    if end < text.len() || spans.is_empty() {
+        spans.push(Span::styled(&text[end..], base));
+    }
+
+    spans
+}
+
+/// split some *data* into its spans, the truncation symbol, if the data has been truncated by
+/// [`truncate`], having its own *truncation_style* and the matches of the search *query* the
+/// *match_style*, see [`highlight_spans`]
+///
+/// > :bulb: **Note**  
+/// > only truncated data is longer than [`ReprOptions::max_length`], so shorter data that happens
+/// > to end with the truncation symbol is left untouched
+fn data_spans<'a>(
+    data: &'a str,
+    style: Style,
+    truncation_style: Style,
+    (query, match_style): (&str, Style),
+    options: &ReprOptions<'a>,
+) -> Vec<Span<'a>> {
+    let symbol = options.truncation_symbol;
+    match (options.max_length, data.strip_suffix(symbol)) {
+        (Some(max), Some(kept)) if !symbol.is_empty() && data.chars().count() > max => {
+            let mut spans = highlight_spans(kept, query, style, match_style);
+            spans.push(Span::styled(symbol, truncation_style));
+            spans
+        }
+        _ => highlight_spans(data, query, style, match_style),
     }
 }
 
 /// compute the preview representation of a value
 ///
 /// > see the tests for detailed examples
-fn repr_value(value: &Value) -> DataRowRepr {
+fn repr_value(value: &Value, options: ReprOptions) -> DataRowRepr {
     match value {
-        Value::List { vals, .. } => repr_list(vals),
-        Value::Record { val: rec, .. } => repr_record(&rec.columns().cloned().collect::<Vec<_>>()),
-        x => repr_simple_value(x),
+        Value::List { vals, .. } => repr_list(vals, options),
+        Value::Record { val: rec, .. } => repr_record(rec, options),
+        x => repr_simple_value(x, options),
+    }
+}
+
+/// compute a summary of the shapes of all the items of a list or all the fields of a record
+///
+/// the shapes are sorted by decreasing number of occurences, e.g. `2 records, 1 string, 1 null`.
+///
+/// > **Note**  
+/// > returns [`None`] if the value is neither a list nor a record
+fn shape_summary(value: &Value) -> Option<String> {
+    let vals = match value {
+        Value::List { vals, .. } => vals.iter().collect::<Vec<_>>(),
+        Value::Record { val: rec, .. } => rec.values().collect::<Vec<_>>(),
+        _ => return None,
+    };
+
+    let mut counts: Vec<(String, usize)> = vec![];
+    for val in vals {
+        let shape = match repr_value(val, ReprOptions::default()).shape.as_str() {
+            "nothing" => "null".to_string(),
+            s => s.to_string(),
+        };
+
+        match counts.iter_mut().find(|(s, _)| s == &shape) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((shape, 1)),
+        }
     }
+
+    // NOTE: the sort is stable so shapes with the same count keep their order of appearance
+    counts.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+    Some(
+        counts
+            .iter()
+            .map(|(shape, n)| pluralize(*n, shape))
+            .collect::<Vec<String>>()
+            .join(", "),
+    )
+}
+
+/// summarize the top-level type of the *value*, e.g. `root: table (348 rows)`, see
+/// [`App::root_summary`]
+///
+/// a list is only a table when it is detected as a valid one, see [`is_table`], *loose* or not.
+pub(crate) fn root_summary(value: &Value, loose: bool) -> String {
+    let summary = match value {
+        Value::List { vals, .. } => match is_table(value, loose) {
+            crate::nu::value::Table::IsValid => format!("table ({})", pluralize(vals.len(), "row")),
+            _ => format!("list ({})", pluralize(vals.len(), "item")),
+        },
+        Value::Record { val: rec, .. } => format!("record ({})", pluralize(rec.len(), "field")),
+        x => x.get_type().to_string(),
+    };
+
+    format!("root: {}", summary)
 }
 
 /// compute the row / item representation of a complete Nushell Value
 ///
 /// > see the tests for detailed examples
-fn repr_data(data: &Value) -> Vec<DataRowRepr> {
+///
+/// the simple values are represented according to the `options`, see [`ReprOptions`].
+pub(crate) fn repr_data(data: &Value, options: ReprOptions) -> Vec<DataRowRepr> {
     match data {
         Value::List { vals, .. } => {
             if vals.is_empty() {
                 vec![DataRowRepr {
                     name: None,
                     shape: "list".into(),
+                    type_name: "list".into(),
                     data: "[]".into(),
                 }]
             } else {
-                vals.iter().map(repr_value).collect::<Vec<DataRowRepr>>()
+                vals.iter()
+                    .map(|val| repr_value(val, options))
+                    .collect::<Vec<DataRowRepr>>()
             }
         }
         Value::Record { val: rec, .. } => {
@@ -171,53 +814,191 @@ fn repr_data(data: &Value) -> Vec<DataRowRepr> {
                 vec![DataRowRepr {
                     name: None,
                     shape: "record".into(),
+                    type_name: "record".into(),
                     data: "{}".into(),
                 }]
             } else {
                 rec.iter()
                     .map(|(col, val)| {
-                        let mut repr = repr_value(val);
+                        let mut repr = repr_value(val, options);
                         repr.name = Some(col.to_string());
                         repr
                     })
                     .collect::<Vec<DataRowRepr>>()
             }
         }
-        value => vec![repr_simple_value(value)],
+        value => vec![repr_simple_value(value, options)],
+    }
+}
+
+/// compute the width of each column of a table, i.e. the width of its widest cell or header,
+/// capped at *cap*
+///
+/// > :bulb: **Note**  
+/// > the width is the number of terminal cells, not the number of bytes or characters
+fn column_widths(cells: &[Vec<String>], headers: &[String], cap: u16) -> Vec<u16> {
+    headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| {
+            let width = cells
+                .iter()
+                .filter_map(|row| row.get(i))
+                .chain(std::iter::once(header))
+                .map(|cell| Span::raw(cell.as_str()).width())
+                .max()
+                .unwrap_or(0);
+
+            width.min(cap as usize) as u16
+        })
+        .collect()
+}
+
+/// the indices of the columns of a table that are visible, i.e. the *pinned* ones followed by the
+/// other ones, without the first *offset* of them
+///
+/// > :bulb: **Note**  
+/// > the *offset* is clamped so that at least one column that is not pinned is always visible
+pub(crate) fn visible_columns(nb_columns: usize, offset: usize, pinned: usize) -> Vec<usize> {
+    let pinned = pinned.min(nb_columns);
+    let offset = offset.min(nb_columns.saturating_sub(pinned + 1));
+
+    (0..pinned).chain(pinned + offset..nb_columns).collect()
+}
+
+/// compute the rows of a nested *value*, indented to be shown below the row of the value when it's
+/// expanded, e.g. `  a: 1 (int)` for a field `a` of a record or `  0: 1 (int)` for the first item
+/// of a list
+///
+/// > :bulb: **Note**  
+/// > only one level is expanded, the values nested deeper being previewed as usual
+pub(crate) fn expansion(value: &Value, options: ReprOptions) -> Vec<String> {
+    match value {
+        Value::List { vals, .. } if vals.is_empty() => vec!["  []".into()],
+        Value::Record { val, .. } if val.is_empty() => vec!["  {}".into()],
+        Value::List { .. } | Value::Record { .. } => repr_data(value, options)
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let name = row.name.unwrap_or(i.to_string());
+                format!("  {}: {} ({})", name, row.data, row.shape)
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// the *line* of a row, followed by the rows of its *expansion*, if any, see [`expansion`]
+fn with_expansion<'a>(line: Line<'a>, expansion: Option<&'a [String]>, style: Style) -> Text<'a> {
+    let mut lines = vec![line];
+    lines.extend(
+        expansion
+            .unwrap_or_default()
+            .iter()
+            .map(|row| Line::from(Span::styled(row.as_str(), style))),
+    );
+
+    Text::from(lines)
+}
+
+/// the alignment of some data of type *type_name*, e.g. `int`, in its column, see
+/// [`Config::data_alignment`] and [`Config::auto_align_numbers`]
+fn data_alignment(config: &Config, type_name: &str) -> Alignment {
+    if config.auto_align_numbers && matches!(type_name, "int" | "float" | "filesize" | "duration") {
+        return Alignment::Right;
+    }
+
+    match config.data_alignment {
+        DataAlignment::Left => Alignment::Left,
+        DataAlignment::Right => Alignment::Right,
+        DataAlignment::Center => Alignment::Center,
     }
 }
 
+/// compute the shape of each column of a valid *table*, e.g. `list<int>`, see
+/// [`table_column_types`]
+///
+/// > :bulb: **Note**  
+/// > the nested types are only detailed when all the cells of a column have the same one, e.g. a
+/// > column with both `list<int>` and `list<string>` cells is only a `list`
+fn table_column_shapes(table: &[Record], columns: &[String]) -> Vec<String> {
+    table_column_types(table)
+        .into_iter()
+        .zip(columns)
+        .map(|(ty, col)| {
+            if !matches!(ty, Type::List(_) | Type::Table(_) | Type::Record(_)) {
+                return ty.to_string();
+            }
+
+            let types = table
+                .iter()
+                .filter_map(|row| row.get(col))
+                .map(|val| val.get_type())
+                .filter(|ty| !matches!(ty, Type::Nothing))
+                .collect::<Vec<Type>>();
+            if types.iter().all(|t| t == &ty) {
+                ty.to_string()
+            } else if types.iter().all(|t| matches!(t, Type::Record(_))) {
+                "record".into()
+            } else if types.iter().all(|t| matches!(t, Type::Table(_))) {
+                "table".into()
+            } else if types
+                .iter()
+                .all(|t| matches!(t, Type::List(_) | Type::Table(_)))
+            {
+                "list".into()
+            } else {
+                ty.to_string()
+            }
+        })
+        .collect()
+}
+
+/// the representation of a table, i.e. its columns, their shapes and the rows of cells
+pub(crate) type TableRepr = (Vec<String>, Vec<String>, Vec<Vec<String>>);
+
 /// compute the representation of a complete Nushell table
 ///
 /// > see the tests for detailed examples
-fn repr_table(table: &[Record]) -> (Vec<String>, Vec<String>, Vec<Vec<String>>) {
-    let mut shapes = vec![Type::Nothing; table[0].len()];
-
-    let mut rows = vec![vec![]; table.len()];
-
-    for (i, row) in table.iter().enumerate() {
-        for (j, col) in table[0].columns().enumerate() {
-            // NOTE: because `table` is a valid table, this should always be a `Some`
-            let val = row.get(col).unwrap();
-
-            let cell_type = val.get_type();
-            if !matches!(cell_type, Type::Nothing) {
-                if shapes[j].is_numeric() && cell_type.is_numeric() && (shapes[j] != cell_type) {
-                    shapes[j] = Type::Number;
-                } else {
-                    shapes[j] = cell_type;
-                }
-            }
+pub(crate) fn repr_table(table: &[Record], options: ReprOptions) -> TableRepr {
+    let columns = table_columns(table);
 
-            rows[i].push(repr_value(val).data);
-        }
-    }
+    let rows = table
+        .iter()
+        .map(|row| {
+            columns
+                .iter()
+                // NOTE: a missing cell, in a loose table, is the same as a `null` one
+                .map(|col| match row.get(col) {
+                    Some(val) => repr_value(val, options).data,
+                    None => options.null_placeholder.into(),
+                })
+                .collect::<Vec<String>>()
+        })
+        .collect();
 
-    (
-        table[0].columns().cloned().collect::<Vec<_>>(),
-        shapes.iter().map(|s| s.to_string()).collect(),
-        rows,
-    )
+    let shapes = table_column_shapes(table, &columns);
+
+    (columns, shapes, rows)
+}
+
+/// format the *columns* and the *rows* of a table, e.g. from [`repr_table`], as a Markdown table
+///
+/// the `|` in the cells are escaped and the new lines become `<br>`, not to break the table.
+pub(crate) fn to_markdown(columns: &[String], rows: &[Vec<String>]) -> String {
+    let escape = |cell: &String| cell.replace('|', "\\|").replace('\n', "<br>");
+    let line = |cells: Vec<String>| format!("| {} |", cells.join(" | "));
+
+    let mut lines = vec![
+        line(columns.iter().map(escape).collect()),
+        line(columns.iter().map(|_| "---".to_string()).collect()),
+    ];
+    lines.extend(
+        rows.iter()
+            .map(|row| line(row.iter().map(escape).collect())),
+    );
+
+    lines.join("\n")
 }
 
 /// render the whole data
@@ -227,8 +1008,6 @@ fn repr_table(table: &[Record]) -> (Vec<String>, Vec<String>, Vec<Vec<String>>)
 /// the data will be rendered on top of the bar, and on top of the cell path in case
 /// [`crate::config::Config::show_cell_path`] is set to `true`.
 fn render_data(frame: &mut Frame, app: &mut App) {
-    let config = &app.config;
-
     let mut data_path = app.position.members.clone();
     let current = if !app.is_at_bottom() {
         data_path.pop()
@@ -236,56 +1015,145 @@ fn render_data(frame: &mut Frame, app: &mut App) {
         None
     };
 
-    let value = app.value_under_cursor(Some(CellPath { members: data_path }));
+    let data_path = CellPath { members: data_path };
+    let value = app.view_at(&data_path);
+
+    // NOTE: whether the level is a table is only checked again when it changes, along with the
+    // rows, see `App::cache_rows`
+    app.cache_rows(&data_path, &value);
+    let table_type = app.rows_cache.table_type.as_ref();
+    let is_a_table = table_type == Some(&crate::nu::value::Table::IsValid);
 
-    let table_type = is_table(&value);
-    let is_a_table = matches!(table_type, crate::nu::value::Table::IsValid);
+    let config = &app.config;
 
-    let mut data_frame_height = if config.show_cell_path {
-        frame.size().height - 2
+    let mut data_frame_height = if config.show_cell_path || config.show_shape_summary {
+        frame.size().height.saturating_sub(2)
     } else {
-        frame.size().height - 1
+        frame.size().height.saturating_sub(1)
     };
-    if !is_a_table {
-        if let Some(msg) = table_type.to_msg() {
-            data_frame_height -= 1;
-            frame.render_widget(
-                Paragraph::new(msg).alignment(Alignment::Right).style(
-                    Style::default()
-                        .bg(config.colors.warning.background)
-                        .fg(config.colors.warning.foreground),
-                ),
-                Rect::new(0, data_frame_height, frame.size().width, 1),
-            );
-        }
+    // NOTE: the flash message, if any, has priority over the table warning
+    let banner = match &app.flash {
+        Some(msg) => Some(msg.clone()),
+        None if !is_a_table => table_type.and_then(|t| t.to_msg()),
+        None => None,
+    };
+    if let Some(msg) = banner {
+        data_frame_height = data_frame_height.saturating_sub(1);
+        frame.render_widget(
+            Paragraph::new(msg)
+                .alignment(Alignment::Right)
+                .style(styled(
+                    Style::default(),
+                    config.colors.warning.foreground,
+                    config.colors.warning.background,
+                    config.colors.enabled,
+                )),
+            Rect::new(0, data_frame_height, frame.size().width, 1),
+        );
     }
 
-    let normal_name_style = Style::default()
-        .fg(config.colors.normal.name.foreground)
-        .bg(config.colors.normal.name.background);
-    let normal_data_style = Style::default()
-        .fg(config.colors.normal.data.foreground)
-        .bg(config.colors.normal.data.background);
-    let normal_shape_style = Style::default()
-        .fg(config.colors.normal.shape.foreground)
-        .bg(config.colors.normal.shape.background);
-    let highlight_style = Style::default()
-        .fg(config.colors.selected.foreground)
-        .bg(config.colors.selected.background)
-        .add_modifier(config.colors.selected_modifier);
+    // NOTE: the two records are compared side by side at the top level only
+    if config.diff && data_path.members.is_empty() {
+        if let Some((a, b)) = diff_pair(&value) {
+            let diff = diff_records(a, b);
+
+            let border = if config.show_borders { 1 } else { 0 };
+            let height = data_frame_height as i32 - 2 * border - 1; // borders + header
+            let top = *app.rendering_tops.last().unwrap_or(&0);
+            let top = scroll_top(
+                top,
+                app.diff_cursor as i32,
+                height,
+                config.margin,
+                diff.len(),
+            );
+
+            let rect = Rect::new(0, 0, frame.size().width, data_frame_height);
+            let state = (app.diff_cursor, top as usize);
+            render_diff(frame, config, (a, b), &diff, rect, &data_path, state);
 
-    let selected = match current {
-        Some(PathMember::Int { val, .. }) => val,
-        Some(PathMember::String { val, .. }) => {
-            value.columns().position(|x| x == &val).unwrap_or(0)
+            app.rendering_tops.pop();
+            app.rendering_tops.push(top);
+            return;
         }
-        None => 0,
-    };
+    }
 
-    let show_line_numbers = (config.number || config.relativenumber)
-        && matches!(value, Value::List { .. } | Value::Record { .. });
-    let nb_lines = match value.clone() {
-        // NOTE: it's annoying that `vals` cant' be cloned
+    let colors = &config.colors;
+    let normal_name_style = styled(
+        Style::default(),
+        colors.normal.name.foreground,
+        colors.normal.name.background,
+        colors.enabled,
+    );
+    let normal_data_style = styled(
+        Style::default(),
+        colors.normal.data.foreground,
+        colors.normal.data.background,
+        colors.enabled,
+    );
+    let normal_shape_style = styled(
+        Style::default(),
+        colors.normal.shape.foreground,
+        colors.normal.shape.background,
+        colors.enabled,
+    );
+    let error_style = styled(
+        Style::default(),
+        colors.warning.foreground,
+        colors.warning.background,
+        colors.enabled,
+    );
+    let truncation_style = styled(
+        Style::default(),
+        colors.truncation.foreground,
+        colors.truncation.background,
+        colors.enabled,
+    );
+    // NOTE: without colors, the matches of the search query are underlined to still be visible
+    let match_style = styled(
+        if colors.enabled {
+            Style::default()
+        } else {
+            Style::default().add_modifier(Modifier::UNDERLINED)
+        },
+        colors.r#match.foreground,
+        colors.r#match.background,
+        colors.enabled,
+    );
+    let options = ReprOptions::from_config(config);
+    // NOTE: the data of some types can have its own colors, instead of the normal ones
+    let type_style = |row: &DataRowRepr| {
+        colors
+            .types
+            .get(&row.type_name)
+            .map(|c| styled(Style::default(), c.foreground, c.background, colors.enabled))
+    };
+    // NOTE: without colors, the row under the cursor is reversed to still be visible
+    let highlight_style = styled(
+        Style::default().add_modifier(selection_modifier(colors)),
+        colors.selected.foreground,
+        colors.selected.background,
+        colors.enabled,
+    );
+
+    let selected = if current.is_some() {
+        app.selected_row()
+    } else {
+        0
+    };
+    // NOTE: the rows selected in VISUAL mode are reversed, except the one under the cursor which
+    // is already highlighted
+    let visual_selection = app.visual_selection();
+    let row_style = |i: usize| match visual_selection {
+        Some((start, end)) if start <= i && i <= end && i != selected => {
+            Style::default().add_modifier(Modifier::REVERSED)
+        }
+        _ => Style::default(),
+    };
+
+    let show_line_numbers = (config.number || config.relativenumber)
+        && matches!(value, Value::List { .. } | Value::Record { .. });
+    let nb_lines = match &value {
         Value::List { vals, .. } => vals.len(),
         Value::Record { val, .. } => val.columns().len(),
         _ => 0,
@@ -296,80 +1164,174 @@ fn render_data(frame: &mut Frame, app: &mut App) {
         0
     };
 
+    // NOTE: the expanded rows show the rows of their value below them, one level deep
+    let expansions: Vec<Vec<String>> = if app.expanded.is_empty() || is_a_table {
+        vec![]
+    } else {
+        let member = |val: PathMember| CellPath {
+            members: data_path.members.iter().cloned().chain([val]).collect(),
+        };
+        let row_paths = match app.value_under_cursor(Some(data_path.clone())) {
+            Value::List { vals, .. } => app
+                .visible_indices(&data_path, &vals)
+                .into_iter()
+                .map(|val| {
+                    member(PathMember::Int {
+                        val,
+                        span: nu_protocol::Span::unknown(),
+                        optional: false,
+                    })
+                })
+                .collect(),
+            Value::Record { val: rec, .. } => app
+                .columns_in_view(&rec)
+                .into_iter()
+                .map(|val| {
+                    member(PathMember::String {
+                        val,
+                        span: nu_protocol::Span::unknown(),
+                        optional: false,
+                    })
+                })
+                .collect(),
+            _ => vec![],
+        };
+
+        row_paths
+            .iter()
+            .map(|path| match app.is_expanded(path) {
+                true => expansion(&app.value_under_cursor(Some(path.clone())), options),
+                false => vec![],
+            })
+            .collect()
+    };
+    // NOTE: an expanded row takes more than one line in the frame, which the scrolling, the line
+    // numbers and the scrollbar count in lines rather than in rows
+    let starts = line_starts(&expansions);
+    let line_of = |i: usize| starts.get(i).copied().unwrap_or(i);
+    let extra_lines = |i: usize| expansions.get(i).map_or(0, |rows| rows.len());
+
     let rect_without_bottom_bar =
         Rect::new(line_numbers_width, 0, frame.size().width, data_frame_height);
 
-    let height = data_frame_height as i32 - 3; // 3: border x 2 + header
-    let cursor = selected as i32;
+    let border = if config.show_borders { 1 } else { 0 };
+    let height = data_frame_height as i32 - 2 * border - 1; // borders + header
     let top = *app.rendering_tops.last().unwrap_or(&0);
-    let margin = config.margin as i32;
-
-    if cursor >= top + height - margin {
-        app.rendering_tops.pop();
-        app.rendering_tops.push(
-            (cursor - height + margin + 1)
-                .min(nb_lines as i32 - height)
-                .max(0),
-        );
-    } else if cursor <= top + margin {
+    let new_top = if starts.is_empty() {
+        scroll_top(top, selected as i32, height, config.margin, nb_lines)
+    } else {
+        scroll_top_in_lines(top, selected, height, config.margin, &starts)
+    };
+    if new_top != top {
         app.rendering_tops.pop();
-        app.rendering_tops
-            .push((cursor - margin).min(nb_lines as i32 - height).max(0));
+        app.rendering_tops.push(new_top);
     }
 
     let margin_offset = *app.rendering_tops.last().unwrap_or(&0) as usize;
+    let visible = height.max(0) as usize;
+    // NOTE: only the rows in the frame are rendered, to stay fast on big data, the first one being
+    // at index `margin_offset` among *len* rows
+    let in_frame = |len: usize| margin_offset.min(len)..(margin_offset + visible).min(len);
+
+    // NOTE: only the rows in the frame are searched for the query, to stay fast on big data
+    let search = app.search.clone();
+    let search_in = |i: usize| {
+        let in_frame = (margin_offset..margin_offset + data_frame_height as usize).contains(&i);
+        (if in_frame { search.as_str() } else { "" }, match_style)
+    };
+
+    // NOTE: the scrollbar takes the rightmost column of the data, when not all rows fit on screen
+    let thumb = if config.show_scrollbar && !app.is_at_bottom() {
+        scrollbar_thumb(
+            line_of(nb_lines),
+            visible,
+            line_of(margin_offset),
+            data_frame_height as usize,
+        )
+    } else {
+        None
+    };
+    let rect_without_bottom_bar = if thumb.is_some() {
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(None)
+            .end_symbol(None)
+            .style(styled(
+                Style::default(),
+                config.colors.scrollbar.foreground,
+                config.colors.scrollbar.background,
+                config.colors.enabled,
+            ));
+        let mut state = ScrollbarState::new(line_of(nb_lines))
+            .position(line_of(selected))
+            .viewport_content_length(visible);
+
+        frame.render_stateful_widget(
+            scrollbar,
+            Rect::new(
+                frame.size().width.saturating_sub(1),
+                0,
+                1,
+                data_frame_height,
+            ),
+            &mut state,
+        );
+
+        Rect {
+            width: frame.size().width.saturating_sub(line_numbers_width + 1),
+            ..rect_without_bottom_bar
+        }
+    } else {
+        rect_without_bottom_bar
+    };
 
     if show_line_numbers {
         let rect_lines_without_bottom_bar = Rect::new(0, 0, line_numbers_width, data_frame_height);
 
-        let normal_line_style = Style::default()
-            .fg(config.colors.line_numbers.normal.foreground)
-            .bg(config.colors.line_numbers.normal.background);
-        let highlight_line_style = Style::default()
-            .fg(config.colors.line_numbers.selected.foreground)
-            .bg(config.colors.line_numbers.selected.background);
-
-        let mut line_numbers = vec![];
-        // add the lines at the top
-        for i in (1..(selected + 1 - margin_offset)).rev() {
-            let i = if config.relativenumber {
-                i
-            } else {
-                selected + 1 - i
-            };
-            line_numbers.push(i);
-        }
-        // add selected line
-        line_numbers.push(selected + 1);
-        // add the lines at the top
-        for i in 1..(margin_offset as i32 + height - selected as i32) {
-            if selected as i32 + 1 + i > nb_lines as i32 {
-                break;
-            }
-
-            let i = if config.relativenumber {
-                i
+        let normal_line_style = styled(
+            Style::default(),
+            config.colors.line_numbers.normal.foreground,
+            config.colors.line_numbers.normal.background,
+            config.colors.enabled,
+        );
+        let highlight_line_style = styled(
+            if config.colors.enabled {
+                Style::default()
             } else {
-                selected as i32 + 1 + i
-            };
-            line_numbers.push(i as usize);
-        }
+                Style::default().add_modifier(Modifier::REVERSED)
+            },
+            config.colors.line_numbers.selected.foreground,
+            config.colors.line_numbers.selected.background,
+            config.colors.enabled,
+        );
 
         let mut lines = if app.config.layout == Layout::Compact && !is_a_table {
             vec![]
         } else {
-            vec![ListItem::new(Line::from("")); 2]
+            // NOTE: the top border, if any, and the header
+            vec![ListItem::new(Line::from("")); border as usize + 1]
         };
-        for i in line_numbers {
+        let header_lines = lines.len();
+        let numbers = line_numbers(
+            selected,
+            margin_offset,
+            height,
+            nb_lines,
+            config.number,
+            config.relativenumber,
+        );
+        for (row, i) in (margin_offset..).zip(numbers) {
             lines.push(ListItem::new(Line::from(Span::styled(
                 format!("{}", i),
                 normal_line_style,
             ))));
+            // NOTE: the lines of an expanded row, below it, have no number
+            lines.extend(vec![ListItem::new(Line::from("")); extra_lines(row)]);
         }
+        lines.truncate(header_lines + visible);
 
-        let mut offset = selected - margin_offset;
+        let mut offset = line_of(selected) - line_of(margin_offset.min(selected));
         if app.config.layout == Layout::Table || is_a_table {
-            offset += 2;
+            offset += border as usize + 1;
         }
 
         frame.render_stateful_widget(
@@ -379,18 +1341,58 @@ fn render_data(frame: &mut Frame, app: &mut App) {
         );
     }
 
+    // NOTE: binary data is shown as a complete hex dump when at the bottom
+    if let (true, Value::Binary { val, .. }) = (app.is_at_bottom(), &value) {
+        let lines = hex_dump(val)
+            .into_iter()
+            .map(|line| Line::from(Span::styled(line, normal_data_style)))
+            .collect::<Vec<Line>>();
+
+        frame.render_widget(
+            Paragraph::new(lines).block(data_block(config, &data_path)),
+            rect_without_bottom_bar,
+        );
+        return;
+    }
+
     if is_a_table {
-        let (columns, shapes, cells) = match value {
-            Value::List { vals, .. } => {
-                let recs = vals
-                    .iter()
-                    .map(|v| v.as_record().unwrap().clone())
-                    .collect::<Vec<Record>>();
-                repr_table(&recs)
-            }
-            _ => panic!("value is a table but is not a list"),
+        // NOTE: the representation of the table is only computed again when the level, the
+        // filter or the data changes, see `App::cache_rows`
+        let Some((columns, shapes, cells)) = &app.rows_cache.table else {
+            panic!("value is a table but its representation has not been cached")
         };
 
+        // NOTE: the columns scrolled past are dropped, the pinned ones staying on the left
+        let indices = visible_columns(columns.len(), app.horizontal_offset, config.pinned_columns);
+        let pick = |row: &[String]| {
+            indices
+                .iter()
+                .map(|&i| row[i].clone())
+                .collect::<Vec<String>>()
+        };
+        let (columns, shapes) = (pick(columns), pick(shapes));
+
+        // NOTE: only the rows in the frame are picked and measured
+        let start = margin_offset.min(cells.len());
+        let visible_cells = cells[in_frame(cells.len())]
+            .iter()
+            .map(|row| pick(row))
+            .collect::<Vec<_>>();
+        let headers = columns
+            .iter()
+            .zip(&shapes)
+            .map(|(c, s)| format!("{} ({})", c, s))
+            .collect::<Vec<String>>();
+        let cap = config.max_column_width.min(u16::MAX as usize) as u16;
+        let alignments = shapes
+            .iter()
+            .map(|s| data_alignment(config, s))
+            .collect::<Vec<Alignment>>();
+        let widths = column_widths(&visible_cells, &headers, cap)
+            .into_iter()
+            .map(Constraint::Length)
+            .collect::<Vec<Constraint>>();
+
         let header = columns
             .iter()
             .zip(shapes)
@@ -406,53 +1408,99 @@ fn render_data(frame: &mut Frame, app: &mut App) {
             })
             .collect::<Vec<Cell>>();
 
-        let widths = header
-            .iter()
-            // FIXME: use an appropriate constraint here
-            .map(|_| Constraint::Min(25))
-            .collect::<Vec<Constraint>>();
-
         let header = Row::new(header).height(1);
 
-        let rows: Vec<Row> = cells
+        // NOTE: the cells wider than their column are cut, to show that they are incomplete
+        let symbol_width = Span::raw(options.truncation_symbol).width();
+        let rows: Vec<Row> = visible_cells
             .iter()
-            .map(|r| Row::new(r.iter().cloned().map(Cell::from).collect::<Vec<Cell>>()))
+            .enumerate()
+            .map(|(i, r)| {
+                let i = start + i;
+                let cells = r.iter().zip(&alignments).map(|(cell, &alignment)| {
+                    let line = if Span::raw(cell.as_str()).width() > cap as usize {
+                        let (kept, _) =
+                            truncate_styled(cell, (cap as usize).saturating_sub(symbol_width));
+                        let (query, match_style) = search_in(i);
+                        let mut spans = highlight_spans(
+                            &cell[..kept.len()],
+                            query,
+                            Style::default(),
+                            match_style,
+                        );
+                        spans.push(Span::styled(options.truncation_symbol, truncation_style));
+                        Line::from(spans)
+                    } else {
+                        Line::from(data_spans(
+                            cell,
+                            Style::default(),
+                            truncation_style,
+                            search_in(i),
+                            &options,
+                        ))
+                    };
+
+                    Cell::from(line.alignment(alignment))
+                });
+
+                Row::new(cells.collect::<Vec<Cell>>()).style(row_style(i))
+            })
             .collect();
 
         let table = Table::new(rows, widths)
             .header(header)
-            .block(Block::default().borders(Borders::ALL))
+            .block(data_block(config, &data_path))
             .highlight_style(highlight_style)
             .highlight_symbol(config.colors.selected_symbol.clone());
 
         frame.render_stateful_widget(
             table,
             rect_without_bottom_bar,
-            &mut TableState::default()
-                .with_selected(Some(selected))
-                .with_offset(margin_offset),
+            &mut TableState::default().with_selected(selected.checked_sub(start)),
         );
 
         return;
     }
 
+    let expansion_of = |i: usize| expansions.get(i).map(|rows| rows.as_slice());
+    let expansion_style = normal_data_style.add_modifier(Modifier::DIM);
+
     match config.layout {
         Layout::Compact => {
-            let items: Vec<ListItem> = repr_data(&value)
+            let items: Vec<ListItem> = app.rows_cache.rows[in_frame(app.rows_cache.rows.len())]
                 .iter()
-                .cloned()
-                .map(|row| {
+                .enumerate()
+                .map(|(i, row)| {
+                    let i = margin_offset + i;
                     let mut spans = vec![];
-                    if let Some(name) = row.name {
-                        spans.push(Span::styled(name, normal_name_style));
+                    if let Some(name) = &row.name {
+                        let (query, match_style) = search_in(i);
+                        spans.extend(highlight_spans(name, query, normal_name_style, match_style));
                         spans.push(": ".into());
                     }
                     spans.push("(".into());
-                    spans.push(Span::styled(row.shape, normal_shape_style));
+                    spans.push(Span::styled(row.shape.as_str(), normal_shape_style));
                     spans.push(") ".into());
-                    spans.push(Span::styled(row.data, normal_data_style));
-
-                    ListItem::new(Line::from(spans))
+                    let data_style = match (type_style(row), row.shape.as_str()) {
+                        (Some(style), _) => style,
+                        (None, "nothing") => normal_data_style.add_modifier(Modifier::DIM),
+                        (None, "error") => error_style,
+                        (None, _) => normal_data_style,
+                    };
+                    spans.extend(data_spans(
+                        &row.data,
+                        data_style,
+                        truncation_style,
+                        search_in(i),
+                        &options,
+                    ));
+
+                    ListItem::new(with_expansion(
+                        Line::from(spans).alignment(data_alignment(config, &row.type_name)),
+                        expansion_of(i),
+                        expansion_style,
+                    ))
+                    .style(row_style(i))
                 })
                 .collect();
 
@@ -470,8 +1518,7 @@ fn render_data(frame: &mut Frame, app: &mut App) {
                 items,
                 rect_without_bottom_bar,
                 &mut ListState::default()
-                    .with_selected(selected)
-                    .with_offset(margin_offset),
+                    .with_selected(selected.and_then(|s| s.checked_sub(margin_offset))),
             )
         }
         Layout::Table => {
@@ -483,19 +1530,38 @@ fn render_data(frame: &mut Frame, app: &mut App) {
                         Cell::from("shape")
                             .style(normal_shape_style.add_modifier(Modifier::REVERSED)),
                     ]);
-                    let rows: Vec<Row> = repr_data(&value)
+                    let rows: Vec<Row> = app.rows_cache.rows[in_frame(app.rows_cache.rows.len())]
                         .iter()
-                        .cloned()
-                        .map(|row| {
-                            let data_style = match row.data.as_str() {
-                                "record" | "list" => normal_data_style.add_modifier(Modifier::DIM),
-                                _ => normal_data_style,
-                            };
+                        .enumerate()
+                        .map(|(i, row)| {
+                            let i = margin_offset + i;
+                            let data_style =
+                                match (type_style(row), row.data.as_str(), row.shape.as_str()) {
+                                    (Some(style), _, _) => style,
+                                    (None, "record" | "list", _) | (None, _, "nothing") => {
+                                        normal_data_style.add_modifier(Modifier::DIM)
+                                    }
+                                    (None, _, "error") => error_style,
+                                    _ => normal_data_style,
+                                };
 
                             Row::new(vec![
-                                Cell::from(row.data).style(data_style),
-                                Cell::from(row.shape).style(normal_shape_style),
+                                Cell::from(with_expansion(
+                                    Line::from(data_spans(
+                                        &row.data,
+                                        data_style,
+                                        truncation_style,
+                                        search_in(i),
+                                        &options,
+                                    ))
+                                    .alignment(data_alignment(config, &row.type_name)),
+                                    expansion_of(i),
+                                    expansion_style,
+                                )),
+                                Cell::from(row.shape.as_str()).style(normal_shape_style),
                             ])
+                            .height(1 + expansion_of(i).map_or(0, |rows| rows.len()) as u16)
+                            .style(row_style(i))
                         })
                         .collect();
 
@@ -512,20 +1578,45 @@ fn render_data(frame: &mut Frame, app: &mut App) {
                             .style(normal_shape_style.add_modifier(Modifier::REVERSED)),
                     ]);
 
-                    let rows: Vec<Row> = repr_data(&value)
+                    let rows: Vec<Row> = app.rows_cache.rows[in_frame(app.rows_cache.rows.len())]
                         .iter()
-                        .cloned()
-                        .map(|row| {
-                            let data_style = match row.data.as_str() {
-                                "record" | "list" => normal_data_style.add_modifier(Modifier::DIM),
-                                _ => normal_data_style,
-                            };
+                        .enumerate()
+                        .map(|(i, row)| {
+                            let i = margin_offset + i;
+                            let data_style =
+                                match (type_style(row), row.data.as_str(), row.shape.as_str()) {
+                                    (Some(style), _, _) => style,
+                                    (None, "record" | "list", _) | (None, _, "nothing") => {
+                                        normal_data_style.add_modifier(Modifier::DIM)
+                                    }
+                                    (None, _, "error") => error_style,
+                                    _ => normal_data_style,
+                                };
 
                             Row::new(vec![
-                                Cell::from(row.name.unwrap_or("".into())).style(normal_name_style),
-                                Cell::from(row.data).style(data_style),
-                                Cell::from(row.shape).style(normal_shape_style),
+                                Cell::from(Line::from(highlight_spans(
+                                    row.name.as_deref().unwrap_or(""),
+                                    search_in(i).0,
+                                    normal_name_style,
+                                    match_style,
+                                )))
+                                .style(normal_name_style),
+                                Cell::from(with_expansion(
+                                    Line::from(data_spans(
+                                        &row.data,
+                                        data_style,
+                                        truncation_style,
+                                        search_in(i),
+                                        &options,
+                                    ))
+                                    .alignment(data_alignment(config, &row.type_name)),
+                                    expansion_of(i),
+                                    expansion_style,
+                                )),
+                                Cell::from(row.shape.as_str()).style(normal_shape_style),
                             ])
+                            .height(1 + expansion_of(i).map_or(0, |rows| rows.len()) as u16)
+                            .style(row_style(i))
                         })
                         .collect();
 
@@ -538,19 +1629,29 @@ fn render_data(frame: &mut Frame, app: &mut App) {
                     (header, rows, constraints)
                 }
                 v => {
-                    let repr = repr_simple_value(&v);
+                    // NOTE: the whole value is shown at the bottom of the data
+                    let repr = repr_simple_value(
+                        &v,
+                        ReprOptions {
+                            max_length: None,
+                            ..ReprOptions::from_config(config)
+                        },
+                    );
                     let spans = vec![
                         Span::styled(repr.data, normal_data_style),
                         " is of shape ".into(),
                         Span::styled(repr.shape, normal_shape_style),
                     ];
 
-                    frame.render_widget(
-                        Paragraph::new(Line::from(spans))
-                            .block(Block::default().borders(Borders::ALL))
-                            .wrap(Wrap { trim: false }),
-                        rect_without_bottom_bar,
-                    );
+                    let paragraph =
+                        Paragraph::new(Line::from(spans)).block(data_block(config, &data_path));
+                    let paragraph = if config.wrap_bottom_value {
+                        paragraph.wrap(Wrap { trim: false })
+                    } else {
+                        paragraph
+                    };
+
+                    frame.render_widget(paragraph, rect_without_bottom_bar);
                     return;
                 }
             };
@@ -560,199 +1661,1597 @@ fn render_data(frame: &mut Frame, app: &mut App) {
             } else {
                 Table::new(rows, constraints)
             }
-            .block(Block::default().borders(Borders::ALL))
+            .block(data_block(config, &data_path))
             .highlight_style(highlight_style)
             .highlight_symbol(config.colors.selected_symbol.clone());
 
-            frame.render_stateful_widget(
-                table,
-                rect_without_bottom_bar,
-                &mut TableState::default()
-                    .with_selected(Some(selected))
-                    .with_offset(margin_offset),
-            )
+            frame.render_stateful_widget(
+                table,
+                rect_without_bottom_bar,
+                &mut TableState::default().with_selected(selected.checked_sub(margin_offset)),
+            )
+        }
+    }
+}
+
+/// the block around the data at *path*, with borders and a title depending on the *config*
+///
+/// > **Note**  
+/// > the title is drawn on the top border, so there is none without borders
+fn data_block(config: &Config, path: &CellPath) -> Block<'static> {
+    if !config.show_borders {
+        return Block::default();
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(config.border_type);
+    if config.show_frame_title {
+        block.title(format_cell_path(&path.members))
+    } else {
+        block
+    }
+}
+
+/// render the records of a *pair* side by side, one row per key, the keys that differ being in
+/// [`crate::config::ColorConfig::diff`], see [`diff_records`]
+///
+/// the row of the key under the cursor and the first row in the frame are given by
+/// *(selected, top)*, see [`App::diff_cursor`].
+///
+/// > :bulb: **Note**  
+/// > a key missing from one of the records has an empty cell on that side
+fn render_diff(
+    frame: &mut Frame,
+    config: &Config,
+    (a, b): (&Record, &Record),
+    diff: &[(String, DiffKind)],
+    rect: Rect,
+    path: &CellPath,
+    (selected, top): (usize, usize),
+) {
+    let colors = &config.colors;
+    let name_style = styled(
+        Style::default(),
+        colors.normal.name.foreground,
+        colors.normal.name.background,
+        colors.enabled,
+    );
+    let data_style = styled(
+        Style::default(),
+        colors.normal.data.foreground,
+        colors.normal.data.background,
+        colors.enabled,
+    );
+    // NOTE: without colors, the keys that differ are in bold to still be visible
+    let diff_style = styled(
+        if colors.enabled {
+            Style::default()
+        } else {
+            Style::default().add_modifier(Modifier::BOLD)
+        },
+        colors.diff.foreground,
+        colors.diff.background,
+        colors.enabled,
+    );
+
+    let options = ReprOptions::from_config(config);
+    let repr = |rec: &Record, key: &str| {
+        rec.get(key)
+            .map(|val| repr_value(val, options).data)
+            .unwrap_or_default()
+    };
+
+    let header = Row::new(vec![
+        Cell::from("key").style(name_style.add_modifier(Modifier::REVERSED)),
+        Cell::from("left").style(data_style.add_modifier(Modifier::REVERSED)),
+        Cell::from("right").style(data_style.add_modifier(Modifier::REVERSED)),
+    ]);
+    let rows = diff
+        .iter()
+        .map(|(key, kind)| {
+            let (name_style, data_style) = match kind {
+                DiffKind::Same => (name_style, data_style),
+                DiffKind::Changed | DiffKind::Removed | DiffKind::Added => (diff_style, diff_style),
+            };
+            let (left, right) = (repr(a, key), repr(b, key));
+            Row::new(vec![
+                Cell::from(key.clone()).style(name_style),
+                Cell::from(left).style(data_style),
+                Cell::from(right).style(data_style),
+            ])
+        })
+        .collect::<Vec<Row>>();
+
+    let constraints = vec![
+        Constraint::Percentage(20),
+        Constraint::Percentage(40),
+        Constraint::Percentage(40),
+    ];
+    frame.render_stateful_widget(
+        Table::new(rows, constraints)
+            .header(header)
+            .block(data_block(config, path))
+            .highlight_style(Style::default().add_modifier(selection_modifier(colors)))
+            .highlight_symbol(config.colors.selected_symbol.clone()),
+        rect,
+        &mut TableState::default()
+            .with_selected(Some(selected))
+            .with_offset(top),
+    );
+}
+
+/// render the cell path just above the status bar
+///
+/// this line can be removed through config, see [`crate::config::Config::show_cell_path`]
+///
+/// # Examples
+/// > :bulb: **Note**  
+/// > the `...` are here to signify that the bar might be truncated and the `||` at the start and
+/// >   the end of the lines are just to represent the borders of the terminal but will not appear in
+/// >   the TUI.
+///
+/// - at the beginning
+/// ```text
+/// ||cell path: $.   ...||
+/// ```
+/// - after some navigation, might look like
+/// ```text
+/// ||cell path: $.foo.bar.2.baz    ...||
+/// ```
+fn render_cell_path(frame: &mut Frame, app: &App) {
+    let next_to_bottom_bar_rect = Rect::new(
+        0,
+        frame.size().height.saturating_sub(2),
+        frame.size().width,
+        1,
+    );
+
+    let mut spans = vec![Span::raw("cell path: ")];
+    spans.extend(cell_path_spans(&app.position.members, &app.config.colors));
+
+    frame.render_widget(
+        Paragraph::new(Line::from(spans)).alignment(Alignment::Left),
+        next_to_bottom_bar_rect,
+    );
+}
+
+/// compute the styled members of a cell path, starting with `$`
+///
+/// the keys have the color of the names and the indices the color of the shapes, the last
+/// member, i.e. the current one, being in bold.
+fn cell_path_spans(members: &[PathMember], colors: &ColorConfig) -> Vec<Span<'static>> {
+    let name_style = styled(
+        Style::default(),
+        colors.normal.name.foreground,
+        colors.normal.name.background,
+        colors.enabled,
+    );
+    let index_style = styled(
+        Style::default(),
+        colors.normal.shape.foreground,
+        colors.normal.shape.background,
+        colors.enabled,
+    );
+
+    let mut spans = vec![Span::raw("$")];
+    for (i, member) in members.iter().enumerate() {
+        let style = match member {
+            PathMember::Int { .. } => index_style,
+            PathMember::String { .. } => name_style,
+        };
+        let style = if i == members.len() - 1 {
+            style.add_modifier(Modifier::BOLD)
+        } else {
+            style
+        };
+
+        spans.push(Span::raw("."));
+        spans.push(Span::styled(repr_member(member), style));
+    }
+
+    spans
+}
+
+/// represent a cell path as a string starting with `$`, e.g. `$.foo.2.bar`, as shown above the
+/// status bar
+pub(crate) fn format_cell_path(members: &[PathMember]) -> String {
+    let mut repr = "$".to_string();
+    for member in members {
+        repr.push('.');
+        repr.push_str(&repr_member(member));
+    }
+
+    repr
+}
+
+fn repr_member(member: &PathMember) -> String {
+    match member {
+        PathMember::Int { val, .. } => val.to_string(),
+        PathMember::String { val, .. } => val.to_string(),
+    }
+}
+
+/// compute the line numbers of the rows visible in a frame of `height` rows starting at `top`,
+/// the row under the cursor being `selected`
+///
+/// like in Vim, relative numbers are the distance to the row under the cursor, which is shown as
+/// `0`, unless `number` is set as well, in which case it's absolute.
+///
+/// > **Note**  
+/// > a frame too small to show anything is treated as a single row, as in [`scroll_top`]
+fn line_numbers(
+    selected: usize,
+    top: usize,
+    height: i32,
+    nb_rows: usize,
+    number: bool,
+    relativenumber: bool,
+) -> Vec<usize> {
+    let bottom = (top + height.max(1) as usize).min(nb_rows);
+
+    (top..bottom)
+        .map(|i| match (i == selected, relativenumber) {
+            (true, true) if !number => 0,
+            (false, true) => i.abs_diff(selected),
+            _ => i + 1,
+        })
+        .collect()
+}
+
+/// represent a number of *bytes* as a file size, e.g. `1.5 KiB`
+fn repr_size(bytes: usize) -> String {
+    Value::filesize(bytes as i64, nu_protocol::Span::unknown())
+        .to_expanded_string("", &nu_protocol::Config::default())
+}
+
+/// represent the position of the row under the cursor among *nb_rows* rows, e.g. `[12/348]`
+///
+/// > **Note**  
+/// > *selected* starts at 0 but the position starts at 1, an empty level being `[0/0]`
+fn repr_position(selected: usize, nb_rows: usize) -> String {
+    if nb_rows == 0 {
+        return "[0/0]".into();
+    }
+
+    format!("[{}/{}]", selected + 1, nb_rows)
+}
+
+/// the modifiers of the row under the cursor, which is also reversed when there are no colors
+fn selection_modifier(colors: &ColorConfig) -> Modifier {
+    if colors.enabled {
+        colors.selected_modifier
+    } else {
+        colors.selected_modifier | Modifier::REVERSED
+    }
+}
+
+/// render a summary of the shapes of the current level, to the right of the cell path line
+///
+/// this line can be enabled through config, see [`crate::config::Config::show_shape_summary`]
+///
+/// # Examples
+/// ```text
+/// ||cell path: $.foo                                          2 records, 1 string, 1 null||
+/// ```
+fn render_shape_summary(frame: &mut Frame, app: &App) {
+    let mut data_path = app.position.members.clone();
+    if !app.is_at_bottom() {
+        data_path.pop();
+    }
+
+    if let Some(summary) = shape_summary(&app.view_at(&CellPath { members: data_path })) {
+        frame.render_widget(
+            Paragraph::new(summary).alignment(Alignment::Right),
+            Rect::new(
+                0,
+                frame.size().height.saturating_sub(2),
+                frame.size().width,
+                1,
+            ),
+        );
+    }
+}
+
+/// the hints of the status bar in the current mode of the *app*, e.g. the main bindings
+///
+/// > :bulb: **Note**  
+/// > without [`crate::config::Config::show_hints`], only the modes waiting for more input, e.g.
+/// > a count or a search query, have a short hint and the other ones have none
+fn status_bar_hints(app: &App) -> String {
+    let config = &app.config;
+    if !config.show_hints
+        && !matches!(
+            app.mode,
+            Mode::Waiting(..)
+                | Mode::Searching
+                | Mode::Visual(..)
+                | Mode::Command
+                | Mode::Goto(..)
+                | Mode::Scroll
+                | Mode::Yank
+        )
+    {
+        return String::new();
+    }
+
+    match app.mode {
+        Mode::Normal => format!(
+            "{} to {} | {}{}{}{} to move around | {} to peek | {} to transpose | {} for help | {} to quit",
+            repr_key(&config.keybindings.insert),
+            Mode::Insert,
+            repr_key(&config.keybindings.navigation.left),
+            repr_key(&config.keybindings.navigation.down),
+            repr_key(&config.keybindings.navigation.up),
+            repr_key(&config.keybindings.navigation.right),
+            repr_key(&config.keybindings.peek),
+            repr_key(&config.keybindings.transpose),
+            repr_key(&config.keybindings.help),
+            repr_key(&config.keybindings.quit),
+        ),
+        Mode::Waiting(n) => {
+            if config.show_hints {
+                format!(
+                    "{} to quit | will run next motion {} times",
+                    repr_key(&KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+                    n
+                )
+            } else {
+                format!("{}", n)
+            }
+        },
+        Mode::Goto(_) => {
+            if config.show_hints {
+                format!(
+                    "{} to go to the top | {} to go to the end | {} to go to the middle | {}",
+                    repr_key(&config.keybindings.goto.top),
+                    repr_key(&config.keybindings.goto.end),
+                    repr_key(&config.keybindings.goto.middle),
+                    repr_key(&config.keybindings.goto.prefix),
+                )
+            } else {
+                repr_key(&config.keybindings.goto.prefix)
+            }
+        }
+        Mode::Scroll => {
+            if config.show_hints {
+                format!(
+                    "{} to put the cursor at the top | {} in the middle | {} at the bottom | {}",
+                    repr_key(&config.keybindings.scroll.top),
+                    repr_key(&config.keybindings.scroll.center),
+                    repr_key(&config.keybindings.scroll.bottom),
+                    repr_key(&config.keybindings.scroll.prefix),
+                )
+            } else {
+                repr_key(&config.keybindings.scroll.prefix)
+            }
+        }
+        Mode::Yank => {
+            if config.show_hints {
+                format!(
+                    "{} to give back the value under the cursor and quit | {}",
+                    repr_key(&config.keybindings.yank.under),
+                    repr_key(&config.keybindings.yank.prefix),
+                )
+            } else {
+                repr_key(&config.keybindings.yank.prefix)
+            }
+        }
+        Mode::Searching => {
+            if config.show_hints {
+                format!(
+                    "{} to quit | {} to search | /{}",
+                    repr_key(&config.keybindings.normal),
+                    repr_key(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+                    app.search
+                )
+            } else {
+                format!("/{}", app.search)
+            }
+        }
+        Mode::Command => {
+            if config.show_hints {
+                format!(
+                    "{} to quit | {} to run | :{}",
+                    repr_key(&config.keybindings.normal),
+                    repr_key(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+                    app.command
+                )
+            } else {
+                format!(":{}", app.command)
+            }
+        }
+        Mode::Visual(_) => {
+            // NOTE: the selection always exists in VISUAL mode
+            let (start, end) = app.visual_selection().unwrap();
+            let count = end - start + 1;
+            if config.show_hints {
+                format!(
+                    "{} to {} | {}{} to extend the selection | {} to peek the selection | {} selected",
+                    repr_key(&config.keybindings.normal),
+                    Mode::Normal,
+                    repr_key(&config.keybindings.navigation.down),
+                    repr_key(&config.keybindings.navigation.up),
+                    repr_key(&config.keybindings.peek),
+                    count
+                )
+            } else {
+                format!("{} selected", count)
+            }
+        }
+        Mode::Insert => format!(
+            "{} to quit | {}{}{}{} to move the cursor | {}{} to delete characters | {} to confirm",
+            repr_key(&KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+            repr_key(&KeyEvent::new(KeyCode::Left, KeyModifiers::NONE)),
+            repr_key(&KeyEvent::new(KeyCode::Right, KeyModifiers::NONE)),
+            repr_key(&KeyEvent::new(KeyCode::Up, KeyModifiers::NONE)),
+            repr_key(&KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)),
+            repr_key(&KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE)),
+            repr_key(&KeyEvent::new(KeyCode::Delete, KeyModifiers::NONE)),
+            repr_key(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+        ),
+        Mode::Peeking => format!(
+            "{} to {} | {} to peek all | {} to peek {} | {} to peek under cursor | {} to peek the cell path | {} to peek it as a string | {} to copy the schema | {} to peek the type | {} to peek as NUON | {} to peek as Markdown | {} to copy | {} to write to a file",
+            repr_key(&config.keybindings.normal),
+            Mode::Normal,
+            repr_key(&config.keybindings.peeking.all),
+            repr_key(&config.keybindings.peeking.view),
+            if config.peek_view_is_parent {
+                "current view"
+            } else {
+                "view under cursor"
+            },
+            repr_key(&config.keybindings.peeking.under),
+            repr_key(&config.keybindings.peeking.cell_path),
+            repr_key(&config.keybindings.peeking.cell_path_string),
+            repr_key(&config.keybindings.peeking.yank_schema),
+            repr_key(&config.keybindings.peeking.ty),
+            repr_key(&config.keybindings.peeking.nuon),
+            repr_key(&config.keybindings.peeking.markdown),
+            repr_key(&config.keybindings.peeking.clipboard),
+            repr_key(&config.keybindings.peeking.write),
+        ),
+        Mode::Writing => format!(
+            "{} to {} | {} to write",
+            repr_key(&KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+            Mode::Normal,
+            repr_key(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+        ),
+        Mode::Filtering => format!(
+            "{} to {} | {} to filter",
+            repr_key(&KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+            Mode::Normal,
+            repr_key(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+        ),
+        Mode::Help => format!(
+            "{}{} to scroll | any other key to close the help",
+            repr_key(&config.keybindings.navigation.down),
+            repr_key(&config.keybindings.navigation.up),
+        ),
+        Mode::Bottom => format!(
+            "{} to {} | {} to peek | {} to toggle wrapping | {} to quit",
+            repr_key(&config.keybindings.navigation.left),
+            Mode::Normal,
+            repr_key(&config.keybindings.peek),
+            repr_key(&config.keybindings.toggle_wrap),
+            repr_key(&config.keybindings.quit),
+        ),
+    }
+}
+
+/// render the status bar at the bottom
+///
+/// the bar takes the last line of the TUI only and renders, from left to right
+/// - the current mode
+/// - the position of the cursor in the current level, e.g. `[12/348]`, except at the bottom
+/// - hints about next bindings to press and actions to do
+///
+/// the color depending of the mode is completely configurable!
+///
+/// # Examples
+/// > :bulb: **Note**  
+/// > - the `...` are here to signify that the bar might be truncated and the `||` at the start and
+/// >   the end of the lines are just to represent the borders of the terminal but will not appear in
+/// >   the TUI.
+/// > - these examples use the default bindings
+///
+/// - in NORMAL mode
+/// ```text
+/// ||NORMAL [1/3] ...                                i to INSERT | hjkl to move around | p to peek | q to quit||
+/// ```
+/// - in INSERT mode
+/// ```text
+/// ||INSERT [1/3] ...                                                                          <esc> to NORMAL||
+/// ```
+/// - in PEEKING mode
+/// ```text
+/// ||PEEKING [1/3] ... <esc> to NORMAL | a to peek all | c to peek current view | u to peek under cursor | q to quit||
+/// ```
+fn render_status_bar(frame: &mut Frame, app: &App) {
+    let config = &app.config;
+    let bottom_bar_rect = Rect::new(
+        0,
+        frame.size().height.saturating_sub(1),
+        frame.size().width,
+        1,
+    );
+
+    let colors = match app.mode {
+        Mode::Normal
+        | Mode::Waiting(_)
+        | Mode::Searching
+        | Mode::Visual(_)
+        | Mode::Command
+        | Mode::Filtering
+        | Mode::Goto(_)
+        | Mode::Scroll
+        | Mode::Yank
+        | Mode::Help => &config.colors.status_bar.normal,
+        Mode::Insert => &config.colors.status_bar.insert,
+        Mode::Peeking | Mode::Writing => &config.colors.status_bar.peek,
+        Mode::Bottom => &config.colors.status_bar.bottom,
+    };
+    let bg_style = if config.colors.enabled {
+        Style::default().bg(colors.background)
+    } else {
+        Style::default()
+    };
+    let style = styled(
+        Style::default(),
+        colors.foreground,
+        colors.background,
+        config.colors.enabled,
+    );
+
+    let mut spans = vec![Span::styled(
+        format!(" {} ", app.mode),
+        style.add_modifier(Modifier::REVERSED),
+    )];
+    // NOTE: there are no rows when looking at a single value at the bottom
+    if !app.is_at_bottom() {
+        // NOTE: the rows of the level have just been cached by `render_data`
+        spans.push(Span::styled(
+            format!(
+                " {}",
+                repr_position(app.selected_row(), app.rows_cache.len())
+            ),
+            style,
+        ));
+    }
+    if let Some((_, size)) = app.size_cache.as_ref().filter(|_| config.show_size) {
+        spans.push(Span::styled(format!(" ~{}", repr_size(*size)), style));
+    }
+    if let Some(summary) = &app.root_summary {
+        spans.push(Span::styled(format!(" {}", summary), style));
+    }
+
+    frame.render_widget(
+        Paragraph::new(Line::from(spans))
+            .alignment(Alignment::Left)
+            .style(bg_style),
+        bottom_bar_rect,
+    );
+
+    let hints = status_bar_hints(app);
+    if !hints.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(hints, style))).alignment(Alignment::Right),
+            bottom_bar_rect,
+        );
+    }
+}
+
+// TODO: add proper assert error messages
+#[cfg(test)]
+mod tests {
+    use nu_protocol::{ast::CellPath, engine::Closure, record, BlockId, Record, ShellError, Value};
+    use ratatui::{
+        backend::TestBackend,
+        prelude::Alignment,
+        style::{Color, Modifier, Style},
+        text::{Line, Span},
+        widgets::BorderType,
+        Terminal,
+    };
+
+    use crate::{
+        app::{App, Filter, Mode},
+        config::{BgFgColorConfig, Config, DataAlignment},
+        error::ErrorView,
+        navigation::Direction,
+        nu::{
+            cell_path::{to_path_member_vec, PM},
+            value::{is_table, Table},
+        },
+    };
+
+    use super::{
+        anchored_top, cell_path_spans, column_widths, data_alignment, expansion, hex_dump,
+        highlight_spans, line_numbers, line_starts, pluralize, render_loading, render_ui,
+        repr_data, repr_inline_list, repr_list, repr_position, repr_record, repr_simple_value,
+        repr_size, repr_table, root_summary, scroll_top, scroll_top_in_lines, scrollbar_thumb,
+        selection_modifier, shape_summary, status_bar_hints, styled, to_markdown, truncate_styled,
+        visible_columns, with_expansion, DataRowRepr, ReprOptions, ScrollAnchor,
+    };
+
+    #[test]
+    fn simple_value() {
+        #[rustfmt::skip]
+        let cases = vec![
+            (Value::test_string("foo"), DataRowRepr::unnamed("foo", "string")),
+            (Value::test_int(1), DataRowRepr::unnamed("1", "int")),
+            (Value::test_bool(true), DataRowRepr::unnamed("true", "bool")),
+            (Value::test_nothing(), DataRowRepr::unnamed("∅", "nothing")),
+            (Value::test_string("foo"), DataRowRepr::unnamed("foo", "string")),
+            (Value::test_string(""), DataRowRepr::unnamed(r#""""#, "string")),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(repr_simple_value(&value, ReprOptions::default()), expected);
+        }
+
+        let options = ReprOptions {
+            null_placeholder: "null",
+            empty_string_quotes: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            repr_simple_value(&Value::test_nothing(), options),
+            DataRowRepr::unnamed("null", "nothing")
+        );
+        assert_eq!(
+            repr_simple_value(&Value::test_string(""), options),
+            DataRowRepr::unnamed("", "string")
+        );
+
+        // the booleans can be glyphs, still being booleans
+        let config = Config {
+            bool_glyphs: true,
+            ..Default::default()
+        };
+        let options = ReprOptions::from_config(&config);
+        assert_eq!(
+            repr_simple_value(&Value::test_bool(true), options),
+            DataRowRepr::unnamed("✓", "bool")
+        );
+        assert_eq!(
+            repr_simple_value(&Value::test_bool(false), options),
+            DataRowRepr::unnamed("✗", "bool")
+        );
+    }
+
+    #[test]
+    fn cell_paths_and_globs() {
+        let cell_path = CellPath {
+            members: to_path_member_vec(&[PM::S("a"), PM::S("b"), PM::I(2)]),
+        };
+        assert_eq!(
+            repr_simple_value(&Value::test_cell_path(cell_path), ReprOptions::default()),
+            DataRowRepr::unnamed("$.a.b.2", "cell-path")
+        );
+        assert_eq!(
+            repr_simple_value(
+                &Value::test_cell_path(CellPath { members: vec![] }),
+                ReprOptions::default()
+            ),
+            DataRowRepr::unnamed("$", "cell-path")
+        );
+
+        assert_eq!(
+            repr_simple_value(&Value::test_glob("**/*.rs"), ReprOptions::default()),
+            DataRowRepr::unnamed("**/*.rs", "glob")
+        );
+    }
+
+    #[test]
+    fn closures_and_errors() {
+        let closure = Value::test_closure(Closure {
+            block_id: BlockId::new(0),
+            captures: vec![],
+        });
+        assert_eq!(
+            repr_simple_value(&closure, ReprOptions::default()),
+            DataRowRepr::unnamed("<closure>", "closure")
+        );
+
+        let error = Value::error(
+            ShellError::GenericError {
+                error: "something went wrong".into(),
+                msg: "here".into(),
+                span: None,
+                help: None,
+                inner: vec![],
+            },
+            nu_protocol::Span::test_data(),
+        );
+        assert_eq!(
+            repr_simple_value(&error, ReprOptions::default()),
+            DataRowRepr::unnamed("something went wrong", "error")
+        );
+    }
+
+    #[test]
+    fn human_friendly_values() {
+        let date = Value::test_date("2024-01-02T03:04:05+01:00".parse().unwrap());
+
+        #[rustfmt::skip]
+        let cases = vec![
+            (Value::test_duration(3_780_000_000_000), None, DataRowRepr::unnamed("1hr 3min", "duration")),
+            (Value::test_filesize(4_404_019), None, DataRowRepr::unnamed("4.2 MiB", "filesize")),
+            (date.clone(), None, DataRowRepr::unnamed("2024-01-02T03:04:05+01:00", "date")),
+            (date.clone(), Some("%Y-%m-%d %H:%M"), DataRowRepr::unnamed("2024-01-02 03:04", "date")),
+            // NOTE: an invalid format falls back to RFC3339
+            (date, Some("%Q"), DataRowRepr::unnamed("2024-01-02T03:04:05+01:00", "date")),
+        ];
+
+        for (value, date_format, expected) in cases {
+            let options = ReprOptions {
+                date_format,
+                ..Default::default()
+            };
+            assert_eq!(repr_simple_value(&value, options), expected);
+        }
+    }
+
+    #[test]
+    fn binary_value() {
+        #[rustfmt::skip]
+        let cases = vec![
+            (vec![], Some(64), "0x (0 bytes)"),
+            (vec![0xde], Some(64), "0x DE (1 byte)"),
+            (vec![0xde, 0xad, 0xbe, 0xef], Some(64), "0x DE AD BE EF (4 bytes)"),
+            ((0..16).collect(), None, "0x 00 01 02 03 04 05 06 07 08 09 0A 0B 0C 0D 0E 0F (16 bytes)"),
+            ([0xde, 0xad, 0xbe, 0xef].repeat(300), Some(11), "0x DE AD BE EF … (1200 bytes)"),
+            ([0xde, 0xad, 0xbe, 0xef].repeat(300), Some(12), "0x DE AD BE EF … (1200 bytes)"),
+        ];
+
+        for (bytes, max_length, expected) in cases {
+            let options = ReprOptions {
+                max_length,
+                ..Default::default()
+            };
+            assert_eq!(
+                repr_simple_value(&Value::test_binary(bytes), options),
+                DataRowRepr::unnamed(expected, "binary")
+            );
+        }
+    }
+
+    #[test]
+    fn cell_path() {
+        let colors = Config::default().colors;
+        let name = styled(
+            Style::default(),
+            colors.normal.name.foreground,
+            colors.normal.name.background,
+            true,
+        );
+        let index = styled(
+            Style::default(),
+            colors.normal.shape.foreground,
+            colors.normal.shape.background,
+            true,
+        );
+
+        assert_eq!(cell_path_spans(&[], &colors), vec![Span::raw("$")]);
+
+        let members = to_path_member_vec(&[PM::S("foo"), PM::I(2), PM::S("bar")]);
+        assert_eq!(
+            cell_path_spans(&members, &colors),
+            vec![
+                Span::raw("$"),
+                Span::raw("."),
+                Span::styled("foo", name),
+                Span::raw("."),
+                Span::styled("2", index),
+                Span::raw("."),
+                Span::styled("bar", name.add_modifier(Modifier::BOLD)),
+            ]
+        );
+
+        let members = to_path_member_vec(&[PM::I(0)]);
+        assert_eq!(
+            cell_path_spans(&members, &colors),
+            vec![
+                Span::raw("$"),
+                Span::raw("."),
+                Span::styled("0", index.add_modifier(Modifier::BOLD)),
+            ]
+        );
+    }
+
+    #[test]
+    fn keep_the_scroll_offset_after_the_bottom() {
+        let mut app = App::from_value(Value::test_list(
+            (0..100)
+                .map(|i| Value::test_string(format!("{}", i)))
+                .collect(),
+        ));
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        let mut draw = |app: &mut App| {
+            terminal.draw(|frame| render_ui(frame, app, None)).unwrap();
+        };
+
+        app.go_up_or_down_in_data(Direction::At(50));
+        draw(&mut app);
+        let top = app.rendering_tops.clone();
+        assert_eq!(app.selected_row(), 50);
+        assert_ne!(top, vec![0]);
+
+        app.go_deeper_in_data();
+        assert!(app.is_at_bottom());
+        draw(&mut app);
+
+        app.go_back_in_data();
+        draw(&mut app);
+        assert_eq!(app.selected_row(), 50);
+        assert_eq!(app.rendering_tops, top);
+    }
+
+    #[test]
+    fn borders_and_title() {
+        let mut app = App::from_value(Value::test_record(record! {
+            "a" => Value::test_int(1),
+        }));
+        let mut terminal = Terminal::new(TestBackend::new(30, 10)).unwrap();
+        let mut top_left_corner = |app: &mut App| {
+            terminal.draw(|frame| render_ui(frame, app, None)).unwrap();
+            let buffer = terminal.backend().buffer();
+            (0..8)
+                .map(|x| buffer.get(x, 0).symbol().to_string())
+                .collect::<String>()
+        };
+
+        assert_eq!(top_left_corner(&mut app), "┌───────");
+
+        app.config.border_type = BorderType::Rounded;
+        app.config.show_frame_title = true;
+        assert_eq!(top_left_corner(&mut app), "╭$──────");
+
+        // NOTE: the title is on the top border, so it goes away with it
+        app.config.show_borders = false;
+        assert!(top_left_corner(&mut app).starts_with("key "));
+    }
+
+    #[test]
+    fn colors_of_types() {
+        // NOTE: the first row is under the cursor, which has its own colors
+        let mut app = App::from_value(Value::test_record(record! {
+            "cursor" => Value::test_nothing(),
+            "a" => Value::test_int(1),
+            "b" => Value::test_string("x"),
+        }));
+        app.config.colors.types.insert(
+            "int".into(),
+            BgFgColorConfig {
+                background: Color::Reset,
+                foreground: Color::Red,
+            },
+        );
+        let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+        terminal
+            .draw(|frame| render_ui(frame, &mut app, None))
+            .unwrap();
+
+        // NOTE: the rows come after the top border and the header
+        let buffer = terminal.backend().buffer();
+        let foreground_of = |y: u16, symbol: &str| {
+            (0..40)
+                .map(|x| buffer.get(x, y))
+                .find(|cell| cell.symbol() == symbol)
+                .unwrap()
+                .fg
+        };
+        assert_eq!(foreground_of(3, "1"), Color::Red);
+        assert_eq!(
+            foreground_of(4, "x"),
+            app.config.colors.normal.data.foreground
+        );
+    }
+
+    #[test]
+    fn color_of_truncation() {
+        // NOTE: the first row is under the cursor, which has its own colors
+        let mut app = App::from_value(Value::test_record(record! {
+            "cursor" => Value::test_nothing(),
+            "a" => Value::test_string("abcdef"),
+        }));
+        app.config.preview_max_length = 3;
+        let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+        terminal
+            .draw(|frame| render_ui(frame, &mut app, None))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let foreground_of = |y: u16, symbol: &str| {
+            (0..40)
+                .map(|x| buffer.get(x, y))
+                .find(|cell| cell.symbol() == symbol)
+                .unwrap()
+                .fg
+        };
+        assert_eq!(
+            foreground_of(3, "c"),
+            app.config.colors.normal.data.foreground
+        );
+        assert_eq!(
+            foreground_of(3, "…"),
+            app.config.colors.truncation.foreground
+        );
+    }
+
+    #[test]
+    fn highlight_matches() {
+        let base = Style::default().fg(Color::White);
+        let hl = Style::default().bg(Color::Yellow);
+
+        assert_eq!(
+            highlight_spans("hello", "xyz", base, hl),
+            vec![Span::styled("hello", base)]
+        );
+        assert_eq!(
+            highlight_spans("hello", "", base, hl),
+            vec![Span::styled("hello", base)]
+        );
+        assert_eq!(
+            highlight_spans("hello", "ell", base, hl),
+            vec![
+                Span::styled("h", base),
+                Span::styled("ell", hl),
+                Span::styled("o", base),
+            ]
+        );
+        assert_eq!(
+            highlight_spans("abcab", "ab", base, hl),
+            vec![
+                Span::styled("ab", hl),
+                Span::styled("c", base),
+                Span::styled("ab", hl),
+            ]
+        );
+        assert_eq!(
+            highlight_spans("aaa", "a", base, hl),
+            vec![Span::styled("a", hl); 3]
+        );
+        assert_eq!(
+            highlight_spans("", "a", base, hl),
+            vec![Span::styled("", base)]
+        );
+    }
+
+    #[test]
+    fn color_of_search_matches() {
+        // NOTE: the first row is under the cursor, which has its own colors
+        let mut app = App::from_value(Value::test_record(record! {
+            "cursor" => Value::test_nothing(),
+            "foo" => Value::test_string("xbarx"),
+        }));
+        app.search = "bar".into();
+        let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+        terminal
+            .draw(|frame| render_ui(frame, &mut app, None))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let background_of = |y: u16, symbol: &str| {
+            (0..40)
+                .map(|x| buffer.get(x, y))
+                .find(|cell| cell.symbol() == symbol)
+                .unwrap()
+                .bg
+        };
+        assert_eq!(background_of(3, "b"), app.config.colors.r#match.background);
+        assert_eq!(
+            background_of(3, "x"),
+            app.config.colors.normal.data.background
+        );
+    }
+
+    #[test]
+    fn size() {
+        assert_eq!(repr_size(0), "0 B");
+        assert_eq!(repr_size(1536), "1.5 KiB");
+    }
+
+    #[test]
+    fn position() {
+        assert_eq!(repr_position(0, 0), "[0/0]");
+        assert_eq!(repr_position(0, 1), "[1/1]");
+        assert_eq!(repr_position(11, 348), "[12/348]");
+        assert_eq!(repr_position(347, 348), "[348/348]");
+    }
+
+    #[test]
+    fn styles_without_colors() {
+        let style = styled(Style::default(), Color::Red, Color::Blue, true);
+        assert_eq!((style.fg, style.bg), (Some(Color::Red), Some(Color::Blue)));
+
+        let base = Style::default().add_modifier(Modifier::BOLD);
+        let style = styled(base, Color::Red, Color::Blue, false);
+        assert_eq!((style.fg, style.bg), (None, None));
+        assert_eq!(style, base);
+
+        let mut colors = Config::default().colors;
+        colors.enabled = false;
+        let members = to_path_member_vec(&[PM::S("foo"), PM::I(2)]);
+        for span in cell_path_spans(&members, &colors) {
+            assert_eq!((span.style.fg, span.style.bg), (None, None));
+        }
+        assert!(selection_modifier(&colors).contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn render_diff() {
+        let value = Value::test_list(vec![
+            Value::test_record(record! {
+                "same" => Value::test_int(1),
+                "changed" => Value::test_string("foo"),
+            }),
+            Value::test_record(record! {
+                "same" => Value::test_int(1),
+                "changed" => Value::test_string("bar"),
+            }),
+        ]);
+        let config = Config {
+            diff: true,
+            ..Config::default()
+        };
+        let mut app = App::from_value(value).with_config(config);
+        assert!(app.flash.is_none());
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+        terminal
+            .draw(|frame| render_ui(frame, &mut app, None))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let line = |y: u16| {
+            (0..40)
+                .map(|x| buffer.get(x, y).symbol())
+                .collect::<String>()
+        };
+        // NOTE: the top border and the header come first
+        assert!(line(2).contains("same"), "{}", line(2));
+        assert!(
+            line(3).contains("foo") && line(3).contains("bar"),
+            "{}",
+            line(3)
+        );
+        assert_eq!(
+            buffer.get(1, 2).fg,
+            app.config.colors.normal.name.foreground
+        );
+        assert_eq!(buffer.get(1, 3).fg, app.config.colors.diff.foreground);
+
+        // the key under the cursor is always in the frame
+        let rec = |side: &str| {
+            Value::test_record(
+                (0..20)
+                    .map(|i| (format!("k{:02}", i), Value::test_string(side)))
+                    .collect(),
+            )
+        };
+        let config = Config {
+            diff: true,
+            ..Config::default()
+        };
+        let mut app =
+            App::from_value(Value::test_list(vec![rec("l"), rec("r")])).with_config(config);
+        let position = app.position.clone();
+        for (direction, cursor) in [(Direction::Bottom, 19), (Direction::Up(3), 16)] {
+            app.go_up_or_down_in_data(direction);
+            assert_eq!(app.diff_cursor, cursor);
+            assert_eq!(app.position, position, "the level should not change");
+
+            terminal
+                .draw(|frame| render_ui(frame, &mut app, None))
+                .unwrap();
+            let buffer = terminal.backend().buffer();
+            let y = (0..10)
+                .find(|&y| {
+                    (0..40)
+                        .map(|x| buffer.get(x, y).symbol())
+                        .collect::<String>()
+                        .contains(&format!("k{}", cursor))
+                })
+                .unwrap_or_else(|| panic!("k{} should be in the frame", cursor));
+            assert!(buffer
+                .get(5, y)
+                .modifier
+                .contains(app.config.colors.selected_modifier));
+        }
+
+        // anything else can't be compared
+        let config = Config {
+            diff: true,
+            ..Config::default()
+        };
+        let app = App::from_value(Value::test_list(vec![Value::test_int(1)])).with_config(config);
+        assert!(app.flash.is_some());
+    }
+
+    #[test]
+    fn render_sorted_keys() {
+        let value = Value::test_record(record! {
+            "b" => Value::test_string("second"),
+            "c" => Value::test_string("third"),
+            "a" => Value::test_string("first"),
+        });
+
+        // the line of each piece of data, from top to bottom
+        let order = |view_sort_keys: bool| {
+            let config = Config {
+                view_sort_keys,
+                ..Config::default()
+            };
+            let mut app = App::from_value(value.clone()).with_config(config);
+            let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+            terminal
+                .draw(|frame| render_ui(frame, &mut app, None))
+                .unwrap();
+            assert_eq!(app.value, value, "the data should not be modified");
+
+            let buffer = terminal.backend().buffer();
+            let mut lines = (0..10)
+                .filter_map(|y| {
+                    let line = (0..40)
+                        .map(|x| buffer.get(x, y).symbol())
+                        .collect::<String>();
+                    ["first", "second", "third"]
+                        .into_iter()
+                        .find(|data| line.contains(data))
+                })
+                .collect::<Vec<_>>();
+            lines.dedup();
+            lines
+        };
+
+        assert_eq!(order(false), vec!["second", "third", "first"]);
+        assert_eq!(order(true), vec!["first", "second", "third"]);
+    }
+
+    #[test]
+    fn hints_of_the_status_bar() {
+        let mut app = App::from_value(Value::test_list(vec![Value::test_int(1)]));
+        assert!(status_bar_hints(&app).contains("for help"));
+
+        app.config.show_hints = false;
+        for mode in [Mode::Normal, Mode::Insert, Mode::Peeking, Mode::Bottom] {
+            app.mode = mode.clone();
+            assert_eq!(status_bar_hints(&app), "", "in {} mode", mode);
+        }
+
+        // NOTE: the modes waiting for more input still show it
+        app.mode = Mode::Searching;
+        app.search = "foo".into();
+        assert_eq!(status_bar_hints(&app), "/foo");
+        app.mode = Mode::Waiting(12);
+        assert_eq!(status_bar_hints(&app), "12");
+    }
+
+    #[test]
+    fn summary_of_the_root() {
+        let table = Value::test_list(vec![
+            Value::test_record(record! { "a" => Value::test_int(1) }),
+            Value::test_record(record! { "a" => Value::test_int(2) }),
+        ]);
+        assert_eq!(root_summary(&table, false), "root: table (2 rows)");
+
+        let record = Value::test_record(record! { "a" => Value::test_int(1) });
+        assert_eq!(root_summary(&record, false), "root: record (1 field)");
+
+        let list = Value::test_list(vec![Value::test_int(1), record.clone()]);
+        assert_eq!(root_summary(&list, false), "root: list (2 items)");
+        assert_eq!(
+            root_summary(&Value::test_list(vec![]), false),
+            "root: list (0 items)"
+        );
+
+        assert_eq!(root_summary(&Value::test_int(1), false), "root: int");
+        assert_eq!(
+            root_summary(&Value::test_string("foo"), false),
+            "root: string"
+        );
+    }
+
+    #[test]
+    fn loading_placeholder() {
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        terminal.draw(render_loading).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let line = (0..20)
+            .map(|x| buffer.get(x, 2).symbol())
+            .collect::<String>();
+        assert_eq!(line.trim(), "loading…");
+
+        // a tiny terminal does not panic
+        for (width, height) in [(0, 0), (5, 0), (0, 5), (3, 1)] {
+            let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+            terminal.draw(render_loading).unwrap();
+        }
+    }
+
+    #[test]
+    fn scroll_in_tiny_frames() {
+        for height in [-2, 0, 1, 2] {
+            for (top, cursor) in [(0, 0), (0, 5), (5, 0), (3, 9), (9, 3)] {
+                let new_top = scroll_top(top, cursor, height, 10, 10);
+                assert!(
+                    new_top <= cursor && cursor < new_top + height.max(1),
+                    "cursor {} should be visible from top {} in a frame of {} rows, top is {}",
+                    cursor,
+                    top,
+                    height,
+                    new_top
+                );
+            }
+        }
+
+        // drawing in a tiny terminal, e.g. after a resize, does not panic
+        let mut app = App::from_value(Value::test_list(
+            (0..100)
+                .map(|i| Value::test_string(format!("{}", i)))
+                .collect(),
+        ));
+        app.go_up_or_down_in_data(Direction::At(50));
+        for height in 0..10 {
+            for width in [0, 1, 5, 10, 20, 30] {
+                let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+                terminal
+                    .draw(|frame| render_ui(frame, &mut app, Some(&ErrorView::new("error"))))
+                    .unwrap();
+                terminal
+                    .draw(|frame| {
+                        render_ui(
+                            frame,
+                            &mut app,
+                            Some(&ErrorView::new(&"error\n".repeat(20))),
+                        )
+                    })
+                    .unwrap();
+                app.flash = Some("flash".into());
+                app.config.number = true;
+                terminal
+                    .draw(|frame| render_ui(frame, &mut app, None))
+                    .unwrap();
+                app.flash = None;
+                app.config.number = false;
+
+                for mode in [Mode::Insert, Mode::Writing, Mode::Bottom] {
+                    app.mode = mode;
+                    terminal
+                        .draw(|frame| render_ui(frame, &mut app, None))
+                        .unwrap();
+                }
+                app.mode = Mode::Normal;
+            }
+        }
+
+        // the margin is kept when the frame is large enough
+        assert_eq!(scroll_top(0, 8, 10, 2, 100), 1);
+        assert_eq!(scroll_top(5, 6, 10, 2, 100), 4);
+        assert_eq!(scroll_top(5, 9, 10, 2, 100), 5);
+        // the last rows fill the frame
+        assert_eq!(scroll_top(0, 99, 10, 2, 100), 90);
+    }
+
+    #[test]
+    fn scroll_over_expanded_rows() {
+        let expansions = |extra: &[usize]| {
+            extra
+                .iter()
+                .map(|&n| vec![String::new(); n])
+                .collect::<Vec<_>>()
+        };
+
+        assert!(line_starts(&expansions(&[0, 0, 0])).is_empty());
+        assert_eq!(line_starts(&expansions(&[0, 2, 0])), vec![0, 1, 4, 5]);
+
+        // 20 rows, the second one taking 5 lines
+        let mut extra = vec![0; 20];
+        extra[1] = 4;
+        let starts = line_starts(&expansions(&extra));
+
+        // (top, cursor, height, margin) => new top
+        #[rustfmt::skip]
+        let cases = vec![
+            // the cursor is still in the frame, once the expanded row is counted
+            ((0, 4, 10, 0), 0),
+            // ... and not anymore further down, where one row per line would still fit
+            ((0, 6, 10, 0), 1),
+            ((0, 9, 10, 0), 2),
+            // the expanded row under the cursor is shown in full
+            ((0, 1, 3, 0), 1),
+            ((0, 1, 10, 2), 0),
+            // going back up
+            ((5, 1, 10, 2), 0),
+            // the last rows fill the frame
+            ((0, 19, 10, 0), 10),
+        ];
+
+        for ((top, cursor, height, margin), expected) in cases {
+            assert_eq!(
+                scroll_top_in_lines(top, cursor, height, margin, &starts),
+                expected,
+                "top {} with the cursor on {} in {} lines with a margin of {}",
+                top,
+                cursor,
+                height,
+                margin
+            );
+        }
+    }
+
+    #[test]
+    fn render_expanded_rows() {
+        let mut app = App::from_value(Value::test_list(
+            (0..20)
+                .map(|i| Value::test_list(vec![Value::test_int(i), Value::test_int(i)]))
+                .collect(),
+        ));
+        app.config.number = true;
+        app.config.margin = 0;
+        app.config.colors.selected_symbol = ">".into();
+        app.toggle_expansion(&CellPath {
+            members: to_path_member_vec(&[PM::I(1)]),
+        });
+        app.position.members = to_path_member_vec(&[PM::I(6)]);
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 14)).unwrap();
+        terminal
+            .draw(|frame| render_ui(frame, &mut app, None))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let lines = (0..14)
+            .map(|y| {
+                (0..40)
+                    .map(|x| buffer.get(x, y).symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>();
+
+        // NOTE: the row under the cursor, `$.6`, is numbered `7` on the same line
+        let selected = lines.iter().find(|l| l.contains('>'));
+        assert!(
+            selected.is_some_and(|l| l.starts_with("7 ")),
+            "{:#?}",
+            lines
+        );
+        // NOTE: the lines of the expanded row have no number, and none overflows on the border
+        assert!(lines[3].starts_with("  │"), "{:#?}", lines);
+        assert!(lines[10].starts_with("  └"), "{:#?}", lines);
+    }
+
+    #[test]
+    fn number_the_lines() {
+        // (selected, top, height, nb_rows) => (absolute, relative, hybrid)
+        #[rustfmt::skip]
+        let cases = vec![
+            // the cursor on the first row
+            ((0, 0, 5, 100), (vec![1, 2, 3, 4, 5], vec![0, 1, 2, 3, 4], vec![1, 1, 2, 3, 4])),
+            // the view can't go above the top, whatever the margin
+            ((1, 0, 5, 100), (vec![1, 2, 3, 4, 5], vec![1, 0, 1, 2, 3], vec![1, 2, 1, 2, 3])),
+            // in the middle of the data
+            ((50, 48, 5, 100), (vec![49, 50, 51, 52, 53], vec![2, 1, 0, 1, 2], vec![2, 1, 51, 1, 2])),
+            // fewer rows than the height of the frame
+            ((2, 0, 5, 3), (vec![1, 2, 3], vec![2, 1, 0], vec![2, 1, 3])),
+            // a frame too small to show anything
+            ((7, 7, 0, 100), (vec![8], vec![0], vec![8])),
+        ];
+
+        for ((selected, top, height, nb_rows), (absolute, relative, hybrid)) in cases {
+            let numbers = |number, relativenumber| {
+                line_numbers(selected, top, height, nb_rows, number, relativenumber)
+            };
+            assert_eq!(numbers(true, false), absolute, "absolute at {}", selected);
+            assert_eq!(numbers(false, true), relative, "relative at {}", selected);
+            assert_eq!(numbers(true, true), hybrid, "hybrid at {}", selected);
         }
     }
-}
 
-/// render the cell path just above the status bar
-///
-/// this line can be removed through config, see [`crate::config::Config::show_cell_path`]
-///
-/// # Examples
-/// > :bulb: **Note**  
-/// > the `...` are here to signify that the bar might be truncated and the `||` at the start and
-/// >   the end of the lines are just to represent the borders of the terminal but will not appear in
-/// >   the TUI.
-///
-/// - at the beginning
-/// ```text
-/// ||cell path: $.   ...||
-/// ```
-/// - after some navigation, might look like
-/// ```text
-/// ||cell path: $.foo.bar.2.baz    ...||
-/// ```
-fn render_cell_path(frame: &mut Frame, app: &App) {
-    let next_to_bottom_bar_rect = Rect::new(0, frame.size().height - 2, frame.size().width, 1);
-    let cell_path = format!(
-        "cell path: $.{}",
-        app.position
-            .members
-            .iter()
-            .map(|m| {
-                match m {
-                    PathMember::Int { val, .. } => val.to_string(),
-                    PathMember::String { val, .. } => val.to_string(),
-                }
-            })
-            .collect::<Vec<String>>()
-            .join(".")
-    );
+    #[test]
+    fn anchor_the_cursor() {
+        #[rustfmt::skip]
+        let cases = vec![
+            (ScrollAnchor::Top, 50, 48),
+            (ScrollAnchor::Center, 50, 46),
+            (ScrollAnchor::Bottom, 50, 43),
+            // the frame can't go above the first row...
+            (ScrollAnchor::Bottom, 3, 0),
+            (ScrollAnchor::Center, 3, 0),
+            // ... nor below the last one
+            (ScrollAnchor::Top, 95, 90),
+            (ScrollAnchor::Center, 97, 90),
+        ];
 
-    frame.render_widget(
-        Paragraph::new(cell_path).alignment(Alignment::Left),
-        next_to_bottom_bar_rect,
-    );
-}
+        for (anchor, cursor, expected) in cases {
+            assert_eq!(
+                anchored_top(anchor, cursor, 10, 2, 100),
+                expected,
+                "{:?} at {}",
+                anchor,
+                cursor
+            );
+        }
 
-/// render the status bar at the bottom
-///
-/// the bar takes the last line of the TUI only and renders, from left to right
-/// - the current mode
-/// - hints about next bindings to press and actions to do
-///
-/// the color depending of the mode is completely configurable!
-///
-/// # Examples
-/// > :bulb: **Note**  
-/// > - the `...` are here to signify that the bar might be truncated and the `||` at the start and
-/// >   the end of the lines are just to represent the borders of the terminal but will not appear in
-/// >   the TUI.
-/// > - these examples use the default bindings
-///
-/// - in NORMAL mode
-/// ```text
-/// ||NORMAL  ...                                     i to INSERT | hjkl to move around | p to peek | q to quit||
-/// ```
-/// - in INSERT mode
-/// ```text
-/// ||INSERT  ...                                                                               <esc> to NORMAL||
-/// ```
-/// - in PEEKING mode
-/// ```text
-/// ||PEEKING ... <esc> to NORMAL | a to peek all | c to peek current view | u to peek under cursor | q to quit||
-/// ```
-fn render_status_bar(frame: &mut Frame, app: &App) {
-    let config = &app.config;
-    let bottom_bar_rect = Rect::new(0, frame.size().height - 1, frame.size().width, 1);
+        // the margins can't overlap in a small frame
+        assert_eq!(anchored_top(ScrollAnchor::Top, 50, 3, 10, 100), 49);
+    }
 
-    let bg_style = match app.mode {
-        Mode::Normal | Mode::Waiting(_) => {
-            Style::default().bg(config.colors.status_bar.normal.background)
-        }
-        Mode::Insert => Style::default().bg(config.colors.status_bar.insert.background),
-        Mode::Peeking => Style::default().bg(config.colors.status_bar.peek.background),
-        Mode::Bottom => Style::default().bg(config.colors.status_bar.bottom.background),
-    };
+    #[test]
+    fn scrollbar() {
+        // all the rows fit on screen
+        assert_eq!(scrollbar_thumb(5, 10, 0, 10), None);
+        assert_eq!(scrollbar_thumb(10, 10, 0, 10), None);
+        assert_eq!(scrollbar_thumb(100, 10, 0, 0), None);
 
-    let style = match app.mode {
-        Mode::Normal | Mode::Waiting(_) => bg_style.fg(config.colors.status_bar.normal.foreground),
-        Mode::Insert => bg_style.fg(config.colors.status_bar.insert.foreground),
-        Mode::Peeking => bg_style.fg(config.colors.status_bar.peek.foreground),
-        Mode::Bottom => bg_style.fg(config.colors.status_bar.bottom.foreground),
-    };
+        #[rustfmt::skip]
+        let cases = vec![
+            ((100, 10, 0, 10), (0, 1)),
+            ((100, 10, 45, 10), (4, 1)),
+            ((100, 10, 90, 10), (9, 1)),
+            // the top cannot go further than the last page
+            ((100, 10, 95, 10), (9, 1)),
+            ((20, 10, 0, 10), (0, 5)),
+            ((20, 10, 5, 10), (2, 5)),
+            ((20, 10, 10, 10), (5, 5)),
+            // the thumb is never empty
+            ((10_000, 10, 0, 10), (0, 1)),
+        ];
 
-    frame.render_widget(
-        Paragraph::new(Line::from(Span::styled(
-            format!(" {} ", app.mode),
-            style.add_modifier(Modifier::REVERSED),
-        )))
-        .alignment(Alignment::Left)
-        .style(bg_style),
-        bottom_bar_rect,
-    );
+        for ((nb_rows, visible, top, track), expected) in cases {
+            assert_eq!(
+                scrollbar_thumb(nb_rows, visible, top, track),
+                Some(expected),
+                "unexpected thumb for {} rows, {} visible from {} on a track of {}",
+                nb_rows,
+                visible,
+                top,
+                track
+            );
+        }
+    }
 
-    if app.config.show_hints || matches!(app.mode, Mode::Waiting(..)) {
-        let hints = match app.mode {
-            Mode::Normal => format!(
-                "{} to {} | {}{}{}{} to move around | {} to peek | {} to transpose | {} to quit",
-                repr_key(&config.keybindings.insert),
-                Mode::Insert,
-                repr_key(&config.keybindings.navigation.left),
-                repr_key(&config.keybindings.navigation.down),
-                repr_key(&config.keybindings.navigation.up),
-                repr_key(&config.keybindings.navigation.right),
-                repr_key(&config.keybindings.peek),
-                repr_key(&config.keybindings.transpose),
-                repr_key(&config.keybindings.quit),
-            ),
-            Mode::Waiting(n) => {
-                if app.config.show_hints {
-                    format!(
-                        "{} to quit | will run next motion {} times",
-                        repr_key(&KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
-                        n
-                    )
-                } else {
-                    format!("{}", n)
-                }
-            },
-            Mode::Insert => format!(
-                "{} to quit | {}{}{}{} to move the cursor | {}{} to delete characters | {} to confirm",
-                repr_key(&KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
-                repr_key(&KeyEvent::new(KeyCode::Left, KeyModifiers::NONE)),
-                repr_key(&KeyEvent::new(KeyCode::Right, KeyModifiers::NONE)),
-                repr_key(&KeyEvent::new(KeyCode::Up, KeyModifiers::NONE)),
-                repr_key(&KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)),
-                repr_key(&KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE)),
-                repr_key(&KeyEvent::new(KeyCode::Delete, KeyModifiers::NONE)),
-                repr_key(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
-            ),
-            Mode::Peeking => format!(
-                "{} to {} | {} to peek all | {} to peek current view | {} to peek under cursor | {} to peek the cell path",
-                repr_key(&config.keybindings.normal),
-                Mode::Normal,
-                repr_key(&config.keybindings.peeking.all),
-                repr_key(&config.keybindings.peeking.view),
-                repr_key(&config.keybindings.peeking.under),
-                repr_key(&config.keybindings.peeking.cell_path),
-            ),
-            Mode::Bottom => format!(
-                "{} to {} | {} to peek | {} to quit",
-                repr_key(&config.keybindings.navigation.left),
-                Mode::Normal,
-                repr_key(&config.keybindings.peek),
-                repr_key(&config.keybindings.quit),
+    #[test]
+    fn render_the_rows_in_the_frame() {
+        let lines = |app: &mut App| {
+            let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+            terminal.draw(|frame| render_ui(frame, app, None)).unwrap();
+            let buffer = terminal.backend().buffer();
+            (0..10)
+                .map(|y| {
+                    (0..40)
+                        .map(|x| buffer.get(x, y).symbol())
+                        .collect::<String>()
+                })
+                .collect::<Vec<String>>()
+        };
+
+        for value in [
+            Value::test_list((0..100).map(|i| Value::test_int(1000 + i)).collect()),
+            Value::test_list(
+                (0..100)
+                    .map(|i| Value::test_record(record! { "a" => Value::test_int(1000 + i) }))
+                    .collect(),
             ),
+        ] {
+            let mut app = App::from_value(value);
+            app.config.colors.selected_symbol = ">".into();
+            app.position.members = to_path_member_vec(&[PM::I(50)]);
+
+            // NOTE: the row under the cursor is the one highlighted, even though the rows above
+            // the frame are not rendered at all
+            let lines = lines(&mut app);
+            let selected = lines.iter().find(|l| l.contains('>'));
+            assert!(selected.is_some_and(|l| l.contains("1050")), "{:#?}", lines);
+            assert!(!lines.iter().any(|l| l.contains("1000")), "{:#?}", lines);
+        }
+    }
+
+    #[test]
+    fn render_scrollbar() {
+        let scrollbar_column = |app: &mut App| {
+            let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+            terminal.draw(|frame| render_ui(frame, app, None)).unwrap();
+            let buffer = terminal.backend().buffer();
+            // NOTE: the rows below the data are the bars at the bottom of the frame
+            (0..7)
+                .map(|y| buffer.get(39, y).symbol().to_string())
+                .collect::<String>()
         };
-        frame.render_widget(
-            Paragraph::new(Line::from(Span::styled(hints, style))).alignment(Alignment::Right),
-            bottom_bar_rect,
+
+        let mut app = App::from_value(Value::test_list(
+            (0..100).map(Value::test_int).collect::<Vec<_>>(),
+        ));
+        let column = scrollbar_column(&mut app);
+        assert!(column.starts_with('█'), "{}", column);
+        assert!(!column.ends_with('█'), "{}", column);
+
+        // the thumb follows the cursor
+        app.position.members = to_path_member_vec(&[PM::I(99)]);
+        let column = scrollbar_column(&mut app);
+        assert!(!column.starts_with('█'), "{}", column);
+        assert!(column.ends_with('█'), "{}", column);
+
+        // there is no scrollbar when all the rows fit on screen
+        let mut app = App::from_value(Value::test_list(vec![Value::test_int(1)]));
+        assert!(!scrollbar_column(&mut app).contains('█'));
+    }
+
+    #[test]
+    fn binary_hex_dump() {
+        assert!(hex_dump(&[]).is_empty());
+
+        let bytes = b"hello, world!\x00\x01\x02\xff".to_vec();
+        assert_eq!(
+            hex_dump(&bytes),
+            vec![
+                "00000000  68 65 6c 6c 6f 2c 20 77 6f 72 6c 64 21 00 01 02  |hello, world!...|",
+                "00000010  ff                                               |.|",
+            ]
         );
     }
-}
 
-// TODO: add proper assert error messages
-#[cfg(test)]
-mod tests {
-    use nu_protocol::{record, Value};
+    #[test]
+    fn truncated_simple_value() {
+        let config = Config {
+            preview_max_length: 10,
+            ..Default::default()
+        };
+
+        let repr = repr_simple_value(
+            &Value::test_string("a".repeat(100)),
+            ReprOptions::from_config(&config),
+        );
+        assert_eq!(repr.data.chars().count(), 11);
+        assert_eq!(repr.data, format!("{}…", "a".repeat(10)));
 
-    use super::{repr_data, repr_list, repr_record, repr_simple_value, repr_table, DataRowRepr};
+        // multibyte characters are not split
+        let options = ReprOptions {
+            max_length: Some(10),
+            ..Default::default()
+        };
+        let repr = repr_simple_value(&Value::test_string("é".repeat(100)), options);
+        assert_eq!(repr.data, format!("{}…", "é".repeat(10)));
+
+        // short strings are left untouched
+        let repr = repr_simple_value(&Value::test_string("a".repeat(10)), options);
+        assert_eq!(repr.data, "a".repeat(10));
+
+        // the truncation symbol can be changed
+        let options = ReprOptions {
+            truncation_symbol: ">>",
+            ..options
+        };
+        let repr = repr_simple_value(&Value::test_string("a".repeat(100)), options);
+        assert_eq!(repr.data, format!("{}>>", "a".repeat(10)));
+    }
 
     #[test]
-    fn simple_value() {
+    fn truncate_strings() {
         #[rustfmt::skip]
         let cases = vec![
-            (Value::test_string("foo"), DataRowRepr::unnamed("foo", "string")),
-            (Value::test_int(1), DataRowRepr::unnamed("1", "int")),
-            (Value::test_bool(true), DataRowRepr::unnamed("true", "bool")),
-            (Value::test_nothing(), DataRowRepr::unnamed("", "nothing")),
-            (Value::test_string("foo"), DataRowRepr::unnamed("foo", "string")),
+            ("abcdef", 3, ("abc", true)),
+            ("abc", 3, ("abc", false)),
+            ("ab", 3, ("ab", false)),
+            ("abc", 0, ("", true)),
+            ("", 0, ("", false)),
+            // multibyte characters are never split
+            ("éèàù", 2, ("éè", true)),
+            ("éèàù", 4, ("éèàù", false)),
+            ("日本語のテキスト", 3, ("日本語", true)),
+            ("🦀🦀🦀", 1, ("🦀", true)),
         ];
 
-        for (value, expected) in cases {
-            assert_eq!(repr_simple_value(&value), expected);
+        for (s, max, (kept, truncated)) in cases {
+            assert_eq!(
+                truncate_styled(s, max),
+                (kept.to_string(), truncated),
+                "truncating {:?} to {} characters",
+                s,
+                max
+            );
         }
     }
 
@@ -772,10 +3271,47 @@ mod tests {
         ];
 
         for (list, expected) in cases {
-            assert_eq!(repr_list(&list), expected);
+            assert_eq!(repr_list(&list, ReprOptions::default()), expected);
+        }
+
+        let options = ReprOptions {
+            first_item: true,
+            ..Default::default()
+        };
+        let nested = vec![
+            Value::test_list(vec![Value::test_list(vec![Value::test_int(1)])]),
+            Value::test_int(2),
+        ];
+
+        #[rustfmt::skip]
+        let cases = vec![
+            (vec![Value::test_string("a"), Value::test_int(1)], None, "[2 items: a, …]"),
+            (vec![], None, "[]"),
+            (vec![Value::test_nothing()], None, "[1 item: ∅]"),
+            // only one level of nesting is previewed inline
+            (nested, None, "[2 items: [[1 item]], …]"),
+            (vec![Value::test_string("a".repeat(20))], Some(10), "[1 item: a…"),
+        ];
+
+        for (list, max_length, expected) in cases {
+            let options = ReprOptions {
+                max_length,
+                ..options
+            };
+            assert_eq!(
+                repr_list(&list, options),
+                DataRowRepr::unnamed(expected, "list")
+            );
         }
     }
 
+    #[test]
+    fn pluralize_counts() {
+        assert_eq!(pluralize(0, "item"), "0 items");
+        assert_eq!(pluralize(1, "item"), "1 item");
+        assert_eq!(pluralize(2, "field"), "2 fields");
+    }
+
     #[test]
     fn record() {
         #[rustfmt::skip]
@@ -785,19 +3321,95 @@ mod tests {
             (vec!["a"], DataRowRepr::unnamed("{1 field}", "record")),
         ];
 
+        for (record, expected) in cases {
+            let record = record
+                .iter()
+                .map(|x| (x.to_string(), Value::test_nothing()))
+                .collect::<Record>();
+            assert_eq!(repr_record(&record, ReprOptions::default()), expected);
+        }
+
+        let options = ReprOptions {
+            first_item: true,
+            ..Default::default()
+        };
+
+        #[rustfmt::skip]
+        let cases = vec![
+            (record! { "a" => Value::test_int(1), "b" => Value::test_int(2) }, "{2 fields: a: 1, …}"),
+            (record! {}, "{}"),
+            (record! { "a" => Value::test_list(vec![Value::test_int(1)]) }, "{1 field: a: [1]}"),
+        ];
+
         for (record, expected) in cases {
             assert_eq!(
-                repr_record(
-                    &record
-                        .iter()
-                        .map(|x| x.to_string())
-                        .collect::<Vec<String>>()
-                ),
-                expected
+                repr_record(&record, options),
+                DataRowRepr::unnamed(expected, "record")
             );
         }
     }
 
+    #[test]
+    fn inline_nested_values() {
+        let options = ReprOptions {
+            inline_nested: true,
+            ..Default::default()
+        };
+
+        let record = record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_string("x"),
+            "c" => Value::test_nothing(),
+        };
+        assert_eq!(
+            repr_record(&record, options),
+            DataRowRepr::unnamed("{a: 1, b: x, c: ∅}", "record")
+        );
+        assert_eq!(
+            repr_list(&[Value::test_int(1), Value::test_int(2)], options),
+            DataRowRepr::unnamed("[1, 2]", "list")
+        );
+        assert_eq!(repr_list(&[], options), DataRowRepr::unnamed("[]", "list"));
+
+        // only one level of nesting is previewed inline
+        let nested = record! {
+            "a" => Value::test_record(record! {
+                "b" => Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+                "c" => Value::test_list(vec![]),
+            }),
+        };
+        assert_eq!(
+            repr_record(&nested, options),
+            DataRowRepr::unnamed("{a: {b: [2 items], c: []}}", "record")
+        );
+
+        // the whole preview is truncated
+        let options = ReprOptions {
+            max_length: Some(10),
+            ..options
+        };
+        assert_eq!(
+            repr_record(&nested, options),
+            DataRowRepr::unnamed("{a: {b: [2…", "record")
+        );
+
+        // a huge list is not previewed as a whole before being truncated
+        let huge = (0..1_000_000).map(Value::test_int).collect::<Vec<_>>();
+        assert_eq!(
+            repr_list(&huge, options),
+            DataRowRepr::unnamed("[0, 1, 2, …", "list")
+        );
+        assert_eq!(
+            repr_inline_list(&huge, options, 1, options.max_length).len(),
+            "[0, 1, 2, 3]".len()
+        );
+        let nested = record! { "a" => Value::test_list(huge) };
+        assert_eq!(
+            repr_record(&nested, options),
+            DataRowRepr::unnamed("{a: [0, 1,…", "record")
+        );
+    }
+
     #[ignore = "repr_value is just a direct wrapper around repr_list, repr_record and repr_simple_value"]
     #[test]
     fn value() {}
@@ -818,7 +3430,7 @@ mod tests {
             "i" => Value::test_int(123),
         });
 
-        let result = repr_data(&data);
+        let result = repr_data(&data, ReprOptions::default());
         let expected: Vec<DataRowRepr> = vec![
             DataRowRepr::named("l", "[3 items]", "list"),
             DataRowRepr::named("r", "{2 fields}", "record"),
@@ -847,7 +3459,112 @@ mod tests {
             vec![vec!["x".into(), "1".into()], vec!["y".into(), "2".into()]],
         );
 
-        assert_eq!(repr_table(&table), expected);
+        assert_eq!(repr_table(&table, ReprOptions::default()), expected);
+    }
+
+    #[test]
+    fn align_the_data() {
+        let config = Config::default();
+        assert_eq!(data_alignment(&config, "int"), Alignment::Left);
+        assert_eq!(data_alignment(&config, "string"), Alignment::Left);
+
+        let config = Config {
+            data_alignment: DataAlignment::Center,
+            ..Default::default()
+        };
+        assert_eq!(data_alignment(&config, "int"), Alignment::Center);
+        assert_eq!(data_alignment(&config, "string"), Alignment::Center);
+
+        // only the numbers are right-aligned in auto mode
+        let config = Config {
+            data_alignment: DataAlignment::Center,
+            auto_align_numbers: true,
+            ..Default::default()
+        };
+        for ty in ["int", "float", "filesize", "duration"] {
+            assert_eq!(data_alignment(&config, ty), Alignment::Right, "{}", ty);
+        }
+        for ty in ["string", "bool", "record", "list", "any"] {
+            assert_eq!(data_alignment(&config, ty), Alignment::Center, "{}", ty);
+        }
+    }
+
+    #[test]
+    fn expand_rows() {
+        let options = ReprOptions::default();
+
+        let rec = Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_list(vec![Value::test_int(2)]),
+        });
+        assert_eq!(
+            expansion(&rec, options),
+            vec!["  a: 1 (int)", "  b: [1 item] (list)"]
+        );
+
+        let list = Value::test_list(vec![Value::test_string("x"), Value::test_nothing()]);
+        assert_eq!(
+            expansion(&list, options),
+            vec!["  0: x (string)", "  1: ∅ (nothing)"]
+        );
+
+        assert_eq!(expansion(&Value::test_list(vec![]), options), vec!["  []"]);
+        assert_eq!(
+            expansion(&Value::test_record(record! {}), options),
+            vec!["  {}"]
+        );
+        assert!(expansion(&Value::test_int(1), options).is_empty());
+
+        // the rows of the expansion are below the row itself
+        let rows = expansion(&rec, options);
+        let text = with_expansion(Line::from("{2 fields}"), Some(&rows), Style::default());
+        assert_eq!(
+            text.lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<String>>(),
+            vec!["{2 fields}", "  a: 1 (int)", "  b: [1 item] (list)"]
+        );
+        assert_eq!(
+            with_expansion(Line::from("1"), None, Style::default()).height(),
+            1
+        );
+    }
+
+    #[test]
+    fn slice_the_columns() {
+        assert_eq!(visible_columns(4, 0, 0), vec![0, 1, 2, 3]);
+        assert_eq!(visible_columns(4, 2, 0), vec![2, 3]);
+        assert_eq!(visible_columns(4, 1, 1), vec![0, 2, 3]);
+        assert_eq!(visible_columns(4, 2, 1), vec![0, 3]);
+        // at least one column that is not pinned is kept
+        assert_eq!(visible_columns(4, 10, 0), vec![3]);
+        assert_eq!(visible_columns(4, 10, 2), vec![0, 1, 3]);
+        // pinning all the columns, or more, leaves nothing to scroll
+        assert_eq!(visible_columns(4, 1, 4), vec![0, 1, 2, 3]);
+        assert_eq!(visible_columns(4, 1, 10), vec![0, 1, 2, 3]);
+        assert_eq!(visible_columns(0, 1, 1), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn widths_of_columns() {
+        let headers = vec!["a (int)".to_string(), "name (string)".to_string()];
+        let cells = vec![
+            vec!["1".to_string(), "foo".to_string()],
+            vec!["123456789".to_string(), "a much longer name".to_string()],
+        ];
+
+        assert_eq!(column_widths(&cells, &headers, 100), vec![9, 18]);
+        assert_eq!(column_widths(&cells, &headers, 10), vec![9, 10]);
+        // the header is wider than the cells
+        assert_eq!(column_widths(&cells[..1], &headers, 100), vec![7, 13]);
+        assert_eq!(column_widths(&[], &headers, 100), vec![7, 13]);
+        // wide characters take two cells
+        assert_eq!(
+            column_widths(&[vec!["日本語".to_string()]], &["x".to_string()], 100),
+            vec![6]
+        );
+        assert_eq!(column_widths(&[], &[], 100), Vec::<u16>::new());
     }
 
     #[test]
@@ -866,10 +3583,10 @@ mod tests {
         let expected = (
             vec!["a".into(), "b".into()],
             vec!["nothing".into(), "int".into()],
-            vec![vec!["".into(), "1".into()], vec!["".into(), "2".into()]],
+            vec![vec!["∅".into(), "1".into()], vec!["∅".into(), "2".into()]],
         );
 
-        assert_eq!(repr_table(&table), expected);
+        assert_eq!(repr_table(&table, ReprOptions::default()), expected);
     }
 
     #[test]
@@ -891,7 +3608,7 @@ mod tests {
             vec![vec!["1".into(), "x".into()], vec!["2".into(), "y".into()]],
         );
 
-        assert_eq!(repr_table(&table), expected);
+        assert_eq!(repr_table(&table, ReprOptions::default()), expected);
     }
 
     #[test]
@@ -910,10 +3627,40 @@ mod tests {
         let expected = (
             vec!["a".into(), "b".into()],
             vec!["string".into(), "int".into()],
-            vec![vec!["x".into(), "".into()], vec!["".into(), "2".into()]],
+            vec![vec!["x".into(), "∅".into()], vec!["∅".into(), "2".into()]],
         );
 
-        assert_eq!(repr_table(&table), expected);
+        assert_eq!(repr_table(&table, ReprOptions::default()), expected);
+    }
+
+    #[test]
+    fn repr_table_with_ragged_rows() {
+        let table = Value::test_list(vec![
+            Value::test_record(record! {
+                "a" => Value::test_string("x"),
+            }),
+            Value::test_record(record! {
+                "b" => Value::test_int(2),
+                "a" => Value::test_string("y"),
+            }),
+        ]);
+
+        // NOTE: a strict table would show a warning banner instead
+        assert_eq!(is_table(&table, false), Table::RowIncompatibleLen(1, 2, 1));
+
+        assert_eq!(is_table(&table, true), Table::IsValid);
+        let recs = table
+            .as_list()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_record().unwrap().clone())
+            .collect::<Vec<_>>();
+        let expected = (
+            vec!["a".into(), "b".into()],
+            vec!["string".into(), "int".into()],
+            vec![vec!["x".into(), "∅".into()], vec!["y".into(), "2".into()]],
+        );
+        assert_eq!(repr_table(&recs, ReprOptions::default()), expected);
     }
 
     #[test]
@@ -938,6 +3685,109 @@ mod tests {
             ],
         );
 
-        assert_eq!(repr_table(&table), expected);
+        assert_eq!(repr_table(&table, ReprOptions::default()), expected);
+    }
+
+    #[test]
+    fn repr_table_with_nested_types() {
+        let list = |vals: Vec<Value>| Value::test_list(vals);
+        let table = vec![
+            record! {
+                "a" => list(vec![Value::test_int(1)]),
+                "b" => list(vec![Value::test_int(1)]),
+                "c" => Value::test_record(record! { "x" => Value::test_int(1) }),
+                "d" => Value::test_record(record! { "x" => Value::test_int(1) }),
+            },
+            record! {
+                "a" => list(vec![Value::test_int(2), Value::test_int(3)]),
+                "b" => list(vec![Value::test_string("x")]),
+                "c" => Value::test_record(record! { "x" => Value::test_int(2) }),
+                "d" => Value::test_record(record! { "y" => Value::test_string("y") }),
+            },
+            record! {
+                "a" => Value::test_nothing(),
+                "b" => Value::test_nothing(),
+                "c" => Value::test_nothing(),
+                "d" => Value::test_nothing(),
+            },
+        ];
+
+        let (_, shapes, _) = repr_table(&table, ReprOptions::default());
+        assert_eq!(
+            shapes,
+            vec!["list<int>", "list", "record<x: int>", "record"]
+        );
+    }
+
+    #[test]
+    fn markdown_tables() {
+        let columns = vec!["a".to_string(), "b".to_string()];
+        let rows = vec![
+            vec!["1".to_string(), "x".to_string()],
+            vec!["a|b".to_string(), "two\nlines".to_string()],
+        ];
+
+        assert_eq!(
+            to_markdown(&columns, &rows),
+            [
+                "| a | b |",
+                "| --- | --- |",
+                "| 1 | x |",
+                "| a\\|b | two<br>lines |",
+            ]
+            .join("\n")
+        );
+        assert_eq!(to_markdown(&columns, &[]), "| a | b |\n| --- | --- |");
+    }
+
+    #[test]
+    fn summarize_shapes() {
+        let list = Value::test_list(vec![
+            Value::test_string("a"),
+            Value::test_record(record! { "a" => Value::test_int(1) }),
+            Value::test_nothing(),
+            Value::test_record(record! { "b" => Value::test_int(2) }),
+            Value::test_record(record! { "c" => Value::test_int(3) }),
+            Value::test_string("b"),
+            Value::test_int(1),
+        ]);
+        assert_eq!(
+            shape_summary(&list),
+            Some("3 records, 2 strings, 1 null, 1 int".into())
+        );
+
+        let record = Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_int(2),
+        });
+        assert_eq!(shape_summary(&record), Some("2 ints".into()));
+
+        assert_eq!(shape_summary(&Value::test_list(vec![])), Some("".into()));
+        assert_eq!(shape_summary(&Value::test_int(1)), None);
+    }
+
+    #[test]
+    fn summarize_the_shapes_in_view() {
+        let mut app = App::from_value(Value::test_list(vec![
+            Value::test_string("foo"),
+            Value::test_string("bar"),
+            Value::test_int(1),
+        ]));
+        app.config.show_shape_summary = true;
+        let summary = |app: &mut App| {
+            let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+            terminal.draw(|frame| render_ui(frame, app, None)).unwrap();
+            let buffer = terminal.backend().buffer();
+            (0..40)
+                .map(|x| buffer.get(x, 8).symbol())
+                .collect::<String>()
+        };
+
+        // NOTE: the summary is right-aligned, on the same line as the cell path
+        assert!(summary(&mut app).ends_with(" 2 strings, 1 int"));
+
+        // only the rows that pass through the filter are shown, and thus counted
+        app.filter = Some((CellPath { members: vec![] }, Filter::Query("foo".into())));
+        assert!(summary(&mut app).ends_with(" 1 string"));
     }
 }